@@ -1,4 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::{Duration, Instant};
+
+/// Sujet sur lequel les agents diffusent leurs signaux
+const SIGNAL_TOPIC: &str = "signals";
+/// Durée d'écoute des messages des autres agents
+const LISTEN_FOR: Duration = Duration::from_secs(30);
+
+/// Message envoyé à l'orchestrateur (même forme que `AgentMessage` côté orchestrateur)
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Outgoing<'a> {
+    Heartbeat,
+    Subscribe { topic: &'a str },
+    Publish { topic: &'a str, body: &'a str },
+    Send { to: &'a str, body: &'a str },
+}
+
+/// Message reçu de l'orchestrateur : seuls les messages des autres agents sont lus
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Incoming {
+    Message {
+        from: String,
+        body: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Communication avec les autres agents via le broker de l'orchestrateur,
+/// sur le canal IPC passé en argument (`--ipc <socket>`) : abonnement au sujet
+/// des signaux, signal de présence, puis réponse directe à chaque signal reçu.
+/// Chaque envoi coûte de l'énergie à l'agent.
 pub fn run() {
     println!("Communication module: sending/receiving messages...");
-    // TODO: Écrire/lire un message dans un fichier partagé ou pipe
+    let Some(socket) = socket_from_args() else {
+        println!("Communication module: no IPC channel");
+        return;
+    };
+    let Ok(mut stream) = UnixStream::connect(&socket) else {
+        println!("Communication module: cannot connect to {}", socket);
+        return;
+    };
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let _ = reader.set_read_timeout(Some(Duration::from_secs(1)));
+
+    send(
+        &mut stream,
+        &Outgoing::Subscribe {
+            topic: SIGNAL_TOPIC,
+        },
+    );
+    send(
+        &mut stream,
+        &Outgoing::Publish {
+            topic: SIGNAL_TOPIC,
+            body: "hello",
+        },
+    );
+
+    let deadline = Instant::now() + LISTEN_FOR;
+    let mut lines = BufReader::new(reader).lines();
+    while Instant::now() < deadline {
+        send(&mut stream, &Outgoing::Heartbeat);
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            // Délai de lecture écoulé : on continue d'écouter
+            Some(Err(_)) => continue,
+            None => break,
+        };
+        let Ok(Incoming::Message { from, body }) = serde_json::from_str(&line) else {
+            continue;
+        };
+        println!("Communication module: received {:?} from {}", body, from);
+        // Répond aux signaux, pas aux réponses (sinon les échanges ne s'arrêtent pas)
+        if body != "ack" {
+            send(
+                &mut stream,
+                &Outgoing::Send {
+                    to: &from,
+                    body: "ack",
+                },
+            );
+        }
+    }
+}
+
+/// Envoie un message (une ligne JSON)
+fn send(stream: &mut UnixStream, message: &Outgoing) {
+    if let Ok(line) = serde_json::to_string(message) {
+        let _ = writeln!(stream, "{}", line);
+    }
+}
+
+/// Lit le chemin de la socket passé en argument (`--ipc <socket>`)
+fn socket_from_args() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2)
+        .find(|pair| pair[0] == "--ipc")
+        .map(|pair| pair[1].clone())
 }
@@ -1,5 +1,6 @@
 use crate::genome::genes::AgentGenes;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 #[derive(Debug, Clone)]
 pub struct Genome {
@@ -7,12 +8,22 @@ pub struct Genome {
 }
 
 impl Genome {
+    /// Tire un génome aléatoire.
+    /// Si l'orchestrateur a transmis `--seed N`, le tirage est reproductible.
     pub fn random() -> Self {
-        let mut genes = AgentGenes::new(uuid::Uuid::new_v4().to_string());
-        let mut rng = rand::thread_rng();
+        let mut rng = match seed_from_args() {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut genes = AgentGenes::new(format!("{:032x}", rng.gen::<u128>()));
 
-        for config in genes.modules.values_mut() {
-            config.active = rng.gen();
+        // Ordre trié : l'ordre d'une HashMap change d'un processus à l'autre
+        let mut names: Vec<String> = genes.modules.keys().cloned().collect();
+        names.sort();
+        for name in names {
+            if let Some(config) = genes.modules.get_mut(&name) {
+                config.active = rng.gen();
+            }
         }
 
         Self { genes }
@@ -26,3 +37,11 @@ impl Genome {
             .unwrap_or(false)
     }
 }
+
+/// Lit la graine passée en argument (`--seed N`)
+fn seed_from_args() -> Option<u64> {
+    let args: Vec<String> = std::env::args().collect();
+    args.windows(2)
+        .find(|pair| pair[0] == "--seed")
+        .and_then(|pair| pair[1].parse().ok())
+}
@@ -10,7 +10,17 @@ mod rl;
 mod selfmod;
 mod symbolic;
 
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+use std::io::{BufRead, Write};
+
 fn main() {
+    // Évaluation par le harnais de tâches : un problème JSON par ligne sur stdin
+    if std::env::args().any(|arg| arg == "--tasks") {
+        answer_tasks();
+        return;
+    }
+
     let genome = genome::Genome::random();
     println!("Agent genome: {:?}", genome);
 
@@ -46,3 +56,76 @@ fn main() {
         meta::run();
     }
 }
+
+/// Répond à chaque problème (`{"id", "suite", "input"}`) par `{"id", "answer"}`
+/// jusqu'à la fermeture de stdin. Un problème non compris reçoit une réponse nulle.
+fn answer_tasks() {
+    let stdout = std::io::stdout();
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        let Ok(request) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let input = &request["input"];
+        let answer = match request["suite"].as_str() {
+            Some("sequence_prediction") => next_term(input),
+            Some("arithmetic") => arithmetic(input),
+            Some("grid_navigation") => grid_path(input),
+            _ => None,
+        };
+        let mut out = stdout.lock();
+        let _ = writeln!(out, "{}", json!({ "id": request["id"], "answer": answer }));
+        let _ = out.flush();
+    }
+}
+
+/// Terme suivant d'une suite arithmétique
+fn next_term(input: &Value) -> Option<Value> {
+    let sequence: Vec<i64> = input["sequence"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_i64)
+        .collect();
+    let last = *sequence.last()?;
+    let step = match sequence.len() {
+        1 => 0,
+        n => last.wrapping_sub(sequence[n - 2]),
+    };
+    Some(json!(last.wrapping_add(step)))
+}
+
+/// Résultat de `a op b` (arithmétique modulaire, comme le harnais)
+fn arithmetic(input: &Value) -> Option<Value> {
+    let (a, b) = (input["a"].as_i64()?, input["b"].as_i64()?);
+    let result = match input["op"].as_str()? {
+        "+" => a.wrapping_add(b),
+        "-" => a.wrapping_sub(b),
+        "*" => a.wrapping_mul(b),
+        _ => return None,
+    };
+    Some(json!(result))
+}
+
+/// Plus court chemin (`U`, `D`, `L`, `R`) du départ à l'arrivée, par parcours en largeur
+fn grid_path(input: &Value) -> Option<Value> {
+    let cell = |value: &Value| Some((value[0].as_i64()?, value[1].as_i64()?));
+    let size = input["size"].as_i64()?;
+    let walls: HashSet<(i64, i64)> = input["walls"].as_array()?.iter().filter_map(cell).collect();
+    let (start, goal) = (cell(&input["start"])?, cell(&input["goal"])?);
+
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, String::new())]);
+    while let Some(((x, y), moves)) = queue.pop_front() {
+        if (x, y) == goal {
+            return Some(json!(moves));
+        }
+        for (step, dx, dy) in [('U', 0, -1), ('D', 0, 1), ('L', -1, 0), ('R', 1, 0)] {
+            let next = (x + dx, y + dy);
+            let inside = (0..size).contains(&next.0) && (0..size).contains(&next.1);
+            if inside && !walls.contains(&next) && seen.insert(next) {
+                queue.push_back((next, format!("{}{}", moves, step)));
+            }
+        }
+    }
+    None
+}
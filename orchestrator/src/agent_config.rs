@@ -5,6 +5,9 @@ use std::{path::PathBuf, time::Duration};
 pub struct AgentConfig {
     pub max_retries: u32,
     pub backoff_delay: Duration,
+    pub archive_after: Duration, // Délai avant archivage d'un agent mort ou en quarantaine
+    pub shutdown_grace: Duration, // Délai entre SIGTERM et SIGKILL à l'arrêt
+    pub build_timeout: Duration, // Durée maximale d'une compilation d'agent
     pub memory_limit_mb: u64,
     pub cpu_limit_percent: u8,
     pub log_dir: PathBuf,
@@ -16,6 +19,9 @@ impl Default for AgentConfig {
         Self {
             max_retries: 3,
             backoff_delay: Duration::from_secs(30),
+            archive_after: Duration::from_secs(300),
+            shutdown_grace: Duration::from_secs(5),
+            build_timeout: Duration::from_secs(300),
             memory_limit_mb: 500,
             cpu_limit_percent: 50,
             log_dir: PathBuf::from("logs"),
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Généalogie d'un agent
+/// - index: position de l'agent dans son flux de création (dérivation de la graine)
+/// - generation: génération de naissance
+/// - parents: noms des agents parents (vide pour la population initiale)
+/// - seed: graine de l'agent, dérivée de la graine maîtresse de l'expérience
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentLineage {
+    pub index: u64,
+    pub generation: u32,
+    pub parents: Vec<String>,
+    pub seed: u64,
+}
@@ -1,20 +1,37 @@
 use crate::agent_file_hashes::AgentFileHashes;
+use crate::agent_lineage::AgentLineage;
+use crate::agent_selection::SelectionMode;
+use crate::agent_state::{AgentState, IllegalTransition, StateTransition};
 use crate::agent_structural_code::AgentStructuralCode;
+use crate::audit::audit_log::{current_timestamp, AuditEvent, AuditLog};
+use crate::novelty::ArchivedBehaviour;
+use crate::pareto::ParetoRank;
+use crate::seeding::{stream_rng, SeedStream};
+use crate::tasks::coevolution::CoevolutionState;
+use crate::world::WorldState;
+use protocol::MessageStats;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use uuid::Uuid;
+use uuid::Builder;
+
+/// Énergie d'un agent à sa création
+pub const INITIAL_ENERGY: i32 = 1000;
 
 /// Structure de données pour suivre l'état d'un agent
 /// - name: Nom du dossier de l'agent (UUID v7 raccourci)
 /// - path: chemin vers le dossier de l'agent
-/// - active: si l'agent doit être exécuté
+/// - island: île (sous-population) de l'agent
+/// - species: espèce de l'agent (génomes proches), attribuée à chaque tour
+/// - novelty: nouveauté de son comportement, calculée à chaque tour
+/// - pareto: rang dans la sélection multi-objectif, calculé à chaque tour
+/// - selection_score: dernier score de pénalité de la sélection naturelle
+/// - task_score / task_fitness: score (0 à 1) et aptitude de la dernière évaluation aux tâches
+/// - messages: statistiques des messages échangés avec les autres agents (broker)
+/// - state: état du cycle de vie (voir `AgentState`) ; un code dangereux met l'agent
+///   en quarantaine, un code qui ne compile pas le tue (événement d'audit `Validity`)
 /// - code_hash: empreinte du code pour détecter les modifications
-/// - is_safe: vérifié et ne contient pas de code dangereux
-/// - is_valid: compile correctement
-/// - is_running: actuellement en cours d'exécution
 /// - last_modified: timestamp dernière modification
-/// Structure de données pour suivre l'état d'un agent
-/// Structure de données pour suivre l'état d'un agent
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AgentInfo {
     // Identifiant unique (UUID v7 complet)
@@ -25,71 +42,286 @@ pub struct AgentInfo {
     // Nom du dossier de l'agent (UUID v7 raccourci)
     pub name: String,
     pub path: PathBuf,
+    pub island: String,
 
-    // État opérationnel
-    pub active: bool,
-    pub is_running: bool,
+    // État du cycle de vie (privé : modifié uniquement via `transition` / `transition_forced`)
+    state: AgentState,
+    state_since: u64, // Timestamp UNIX de la dernière transition
+    state_history: Vec<StateTransition>,
 
-    // Qualité et sécurité du code
+    // Code de l'agent
     pub code_hash: String,
-    pub is_safe: bool,
-    pub is_valid: bool,
     pub last_modified: u64,
 
     // Données pour la sélection naturelle
     pub energy: i32,
+    pub lineage: AgentLineage,
+    pub species: Option<u32>,
+    pub novelty: Option<f64>,
+    pub pareto: Option<ParetoRank>,
+    pub selection_score: Option<u32>,
+    pub task_score: Option<f64>,
+    pub task_fitness: f64,
+    pub messages: MessageStats,
 
     // Métriques et hashes
     pub file_hashes: AgentFileHashes,
     pub file_metrics: std::collections::HashMap<String, AgentStructuralCode>,
 
-    // État des crashs
-    pub last_crash: Option<u64>, // Timestamp UNIX
+    // Nombre de crashs consécutifs
     pub crash_count: u32,
 }
 
-/// Collection d'agents sauvegardée dans listing_agents.bin
+/// Collection d'agents sauvegardée dans listing_agents.bin,
+/// avec l'archive des comportements nouveaux (recherche de nouveauté),
+/// l'état du monde simulé et les problèmes coévolués
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AgentsListing {
     pub agents: Vec<AgentInfo>,
+    pub novelty_archive: Vec<ArchivedBehaviour>,
+    pub world: WorldState,
+    pub coevolution: CoevolutionState,
+}
+
+impl AgentsListing {
+    /// Agents d'une île
+    pub fn island_agents<'a>(&'a self, island: &'a str) -> impl Iterator<Item = &'a AgentInfo> {
+        self.agents
+            .iter()
+            .filter(move |agent| agent.island == island)
+    }
+
+    /// Nombre d'agents vivants d'une île (comptés dans son plafond de population)
+    pub fn living_count(&self, island: &str) -> usize {
+        self.island_agents(island)
+            .filter(|agent| agent.state().is_alive())
+            .count()
+    }
+
+    /// Aptitude partagée (fitness sharing) : l'aptitude d'un agent est divisée par
+    /// le nombre de membres vivants de son espèce, ce qui protège les espèces
+    /// nouvelles et peu peuplées face aux espèces dominantes
+    pub fn shared_fitness(&self, agent: &AgentInfo) -> f64 {
+        let members = match agent.species {
+            Some(species) => self
+                .agents
+                .iter()
+                .filter(|a| a.species == Some(species) && a.state().is_alive())
+                .count(),
+            None => 1,
+        };
+        agent.fitness() / members.max(1) as f64
+    }
+
+    /// Valeur d'un agent pour le classement choisi (la plus haute est préférée)
+    pub fn selection_value(&self, agent: &AgentInfo, mode: SelectionMode) -> f64 {
+        let novelty = agent.novelty.unwrap_or(0.0);
+        match mode {
+            SelectionMode::Fitness => self.shared_fitness(agent),
+            SelectionMode::Novelty => novelty,
+            SelectionMode::Combined { novelty_weight } => {
+                self.shared_fitness(agent) + novelty_weight * novelty
+            }
+            SelectionMode::Pareto => agent
+                .pareto
+                .map_or(f64::NEG_INFINITY, |rank| rank.selection_value()),
+        }
+    }
+
+    /// Prochain index libre du flux de création (dérivation de la graine d'un nouvel agent)
+    pub fn next_index(&self) -> u64 {
+        self.agents
+            .iter()
+            .map(|agent| agent.lineage.index)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
 }
 
 impl AgentInfo {
+    /// Nouvel agent, à l'état Created, avec l'énergie initiale
+    pub fn new(
+        id: String,
+        name: String,
+        path: PathBuf,
+        island: String,
+        lineage: AgentLineage,
+    ) -> Self {
+        AgentInfo {
+            id,
+            name,
+            path,
+            island,
+            state: AgentState::Created,
+            state_since: current_timestamp(),
+            state_history: Vec::new(),
+            code_hash: String::new(),
+            last_modified: 0,
+            energy: INITIAL_ENERGY,
+            lineage,
+            species: None,
+            novelty: None,
+            pareto: None,
+            selection_score: None,
+            task_score: None,
+            task_fitness: 0.0,
+            messages: MessageStats::default(),
+            file_hashes: AgentFileHashes::default(),
+            file_metrics: std::collections::HashMap::new(),
+            crash_count: 0,
+        }
+    }
+
+    /// État courant du cycle de vie
+    pub fn state(&self) -> AgentState {
+        self.state
+    }
+
+    /// Timestamp UNIX de la dernière transition
+    pub fn state_since(&self) -> u64 {
+        self.state_since
+    }
+
+    /// Transitions passées, de la plus ancienne à la plus récente
+    pub fn state_history(&self) -> &[StateTransition] {
+        &self.state_history
+    }
+
+    /// Applique une transition d'état validée par `AgentState::can_transition_to`.
+    /// La raison et l'horodatage sont conservés dans l'historique de l'agent.
+    pub fn transition(&mut self, to: AgentState, reason: &str) -> Result<(), IllegalTransition> {
+        if !self.state.can_transition_to(to) {
+            return Err(IllegalTransition {
+                agent: self.name.clone(),
+                from: self.state,
+                to,
+            });
+        }
+        self.push_transition(to, reason);
+        Ok(())
+    }
+
+    /// Transition hors de la table, réservée aux dérogations de l'opérateur
+    /// (ex: relancer un agent mort). Toujours consignée comme `OperatorAction`
+    /// avec l'opérateur et l'état d'origine, en plus de l'historique de l'agent.
+    pub fn transition_forced(
+        &mut self,
+        to: AgentState,
+        reason: &str,
+        operator: &str,
+        audit: &AuditLog,
+    ) {
+        let from = self.state;
+        self.push_transition(to, reason);
+        audit.record(AuditEvent::OperatorAction {
+            operator: operator.to_string(),
+            action: "forced_transition".into(),
+            agent: Some(self.name.clone()),
+            success: true,
+            detail: format!("{} -> {} : {}", from, to, reason),
+        });
+    }
+
+    fn push_transition(&mut self, to: AgentState, reason: &str) {
+        let timestamp = current_timestamp();
+        self.state_history.push(StateTransition {
+            from: self.state,
+            to,
+            reason: reason.to_string(),
+            timestamp,
+        });
+        self.state = to;
+        self.state_since = timestamp;
+    }
+
     // Méthodes pour gérer les crashs
     pub fn increment_crash(&mut self) {
         self.crash_count += 1;
-        self.last_crash = Some(
-            std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        );
     }
 
     pub fn reset_crash_count(&mut self) {
         self.crash_count = 0;
-        self.last_crash = None;
-    }
-
-    pub fn has_recent_crash(&self, backoff_delay: u64) -> bool {
-        if let Some(last_crash) = self.last_crash {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            now < last_crash + backoff_delay
-        } else {
-            false
-        }
+    }
+
+    /// Aptitude de l'agent, comparée au sein d'une génération :
+    /// son énergie plus l'aptitude gagnée à la dernière évaluation aux tâches
+    pub fn fitness(&self) -> f64 {
+        self.energy as f64 + self.task_fitness
+    }
+
+    /// Vrai tant que le délai d'attente depuis l'entrée en Backoff n'est pas écoulé
+    pub fn is_backing_off(&self, backoff_delay: u64) -> bool {
+        self.state == AgentState::Backoff && current_timestamp() < self.state_since + backoff_delay
     }
 }
 
-/// Génère un identifiant UUID v7 pour un agent
-pub fn generate_agent_id() -> String {
-    Uuid::now_v7().to_string() // Utilise Uuid::now_v7 pour simplifier
+/// Génère l'identifiant UUID de l'agent d'index de création `index`, tiré de son flux
+/// aléatoire : une même graine maîtresse redonne les mêmes identifiants et noms
+pub fn generate_agent_id(master_seed: u64, index: u64) -> String {
+    let mut rng = stream_rng(master_seed, SeedStream::Agent { index });
+    Builder::from_random_bytes(rng.gen())
+        .into_uuid()
+        .to_string()
 }
 
-/// Génère une version raccourcie de l'UUID v7 pour le dossier
+/// Génère une version raccourcie de l'UUID pour le dossier (ses 8 derniers caractères,
+/// hors des bits de version et de variante).
 pub fn generate_short_uuid(uuid: &str) -> String {
-    uuid.chars().take(8).collect()
+    let hex: String = uuid.chars().filter(|c| *c != '-').collect();
+    hex[hex.len().saturating_sub(8)..].to_string()
+}
+
+/// Agents et listings minimaux pour les tests unitaires
+#[cfg(test)]
+impl AgentInfo {
+    /// Agent Running (passé par Created et Building) sur l'île par défaut
+    pub fn for_test(name: &str) -> Self {
+        let mut agent = AgentInfo::new(
+            name.to_string(),
+            name.to_string(),
+            PathBuf::from(name),
+            crate::island::DEFAULT_ISLAND.to_string(),
+            AgentLineage::default(),
+        );
+        agent.transition(AgentState::Building, "test").unwrap();
+        agent.transition(AgentState::Running, "test").unwrap();
+        agent
+    }
+}
+
+#[cfg(test)]
+impl AgentsListing {
+    pub fn for_test() -> Self {
+        AgentsListing {
+            agents: Vec::new(),
+            novelty_archive: Vec::new(),
+            world: WorldState::default(),
+            coevolution: CoevolutionState::default(),
+        }
+    }
+
+    /// Un agent Running par entrée (nom, île, énergie, espèce),
+    /// index de création dans l'ordre des entrées
+    pub fn with_agents(agents: &[(&str, &str, i32, u32)]) -> Self {
+        let mut listing = Self::for_test();
+        for (index, &(name, island, energy, species)) in agents.iter().enumerate() {
+            let mut agent = AgentInfo::for_test(name);
+            agent.island = island.to_string();
+            agent.energy = energy;
+            agent.species = Some(species);
+            agent.lineage.index = index as u64;
+            listing.agents.push(agent);
+        }
+        listing
+    }
+
+    /// Agents Running de l'île par défaut, à l'énergie initiale et sans espèce distincte
+    pub fn with_names(names: &[&str]) -> Self {
+        let agents: Vec<_> = names
+            .iter()
+            .map(|&name| (name, crate::island::DEFAULT_ISLAND, INITIAL_ENERGY, 0))
+            .collect();
+        Self::with_agents(&agents)
+    }
 }
@@ -1,6 +1,7 @@
 use crate::agent_structural_code::AgentStructuralCode;
+use serde::{Deserialize, Serialize};
 /// Critères de sélection naturelle pour un agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectionCriteria {
     pub penalty_if_not_safe: u32,
     pub penalty_if_not_valid: u32,
@@ -21,9 +22,30 @@ impl Default for SelectionCriteria {
     }
 }
 
-/// Décide si l'agent doit survivre selon toutes les règles de sélection naturelle.
-/// Le pipeline collecte les métriques et les passe ici.
-pub fn should_survive_agent(
+/// Classement des agents quand il faut choisir : migrants, agent remplacé par un
+/// migrant et agents éliminés d'une île au-delà de son plafond (survie)
+/// - fitness: aptitude partagée au sein de l'espèce
+/// - novelty: nouveauté du comportement (recherche de nouveauté)
+/// - combined: aptitude partagée + `novelty_weight` × nouveauté
+/// - pareto: tri non dominé sur plusieurs objectifs, départagé par la distance
+///   d'encombrement (NSGA-II) ; remplace aussi le score de pénalité dans la survie
+///   (voir `should_survive_pareto`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionMode {
+    #[default]
+    Fitness,
+    Novelty,
+    Combined {
+        novelty_weight: f64,
+    },
+    Pareto,
+}
+
+/// Calcule le score de pénalité d'un agent (plus il est élevé, moins l'agent est apte).
+/// Utilisé par `should_survive_agent` et consigné dans le journal d'audit.
+#[allow(clippy::too_many_arguments)]
+pub fn selection_score(
     is_safe: bool,
     is_valid: bool,
     memory_mb: u64,
@@ -32,11 +54,7 @@ pub fn should_survive_agent(
     cpu_limit: u8,
     code_metrics: &AgentStructuralCode,
     criteria: &SelectionCriteria,
-    energy: i32, // Ajout
-) -> bool {
-    if energy <= 0 {
-        return false;
-    }
+) -> u32 {
     let mut score = 0;
 
     if !is_safe {
@@ -50,5 +68,43 @@ pub fn should_survive_agent(
     }
     score += code_metrics.unwrap_count * criteria.penalty_per_unwrap;
 
+    score
+}
+
+/// Survie en mode multi-objectif : seules les contraintes dures (sécurité, compilation,
+/// énergie) décident ici. Les compromis que le score de pénalité additionnait
+/// (mémoire, unwraps, taille du code, crashs...) sont des objectifs du tri non dominé :
+/// les agents des derniers fronts sont éliminés quand leur île dépasse son plafond.
+pub fn should_survive_pareto(is_safe: bool, is_valid: bool, energy: i32) -> bool {
+    is_safe && is_valid && energy > 0
+}
+
+/// Décide si l'agent doit survivre selon toutes les règles de sélection naturelle.
+/// Le pipeline collecte les métriques et les passe ici.
+pub fn should_survive_agent(
+    is_safe: bool,
+    is_valid: bool,
+    memory_mb: u64,
+    cpu_percent: u8,
+    memory_limit: u64,
+    cpu_limit: u8,
+    code_metrics: &AgentStructuralCode,
+    criteria: &SelectionCriteria,
+    energy: i32, // Ajout
+) -> bool {
+    if energy <= 0 {
+        return false;
+    }
+    let score = selection_score(
+        is_safe,
+        is_valid,
+        memory_mb,
+        cpu_percent,
+        memory_limit,
+        cpu_limit,
+        code_metrics,
+        criteria,
+    );
+
     score <= criteria.max_score
 }
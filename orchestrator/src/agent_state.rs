@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// États du cycle de vie d'un agent (définis dans le protocole partagé, avec la table
+/// des transitions autorisées)
+pub use protocol::AgentState;
+
+/// Transition enregistrée dans l'historique de l'agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub from: AgentState,
+    pub to: AgentState,
+    pub reason: String,
+    pub timestamp: u64, // Timestamp UNIX
+}
+
+/// Erreur renvoyée pour une transition non autorisée (ex: relancer un agent mort)
+#[derive(Debug, Clone)]
+pub struct IllegalTransition {
+    pub agent: String,
+    pub from: AgentState,
+    pub to: AgentState,
+}
+
+impl fmt::Display for IllegalTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transition interdite pour {} : {} -> {}",
+            self.agent, self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for IllegalTransition {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_listing::AgentInfo;
+
+    #[test]
+    fn nominal_life_cycle_is_allowed() {
+        use AgentState::*;
+        let path = [
+            Created, Building, Running, Crashed, Backoff, Building, Running, Dead,
+        ];
+        for pair in path.windows(2) {
+            assert!(
+                pair[0].can_transition_to(pair[1]),
+                "{} -> {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn dead_and_archived_agents_stay_out_of_the_life_cycle() {
+        use AgentState::*;
+        // Dead -> Building passe par la dérogation de l'opérateur, pas par la table
+        let allowed: Vec<AgentState> = AgentState::ALL
+            .into_iter()
+            .filter(|next| Dead.can_transition_to(*next))
+            .collect();
+        assert_eq!(allowed, vec![Archived]);
+        assert!(AgentState::ALL
+            .iter()
+            .all(|next| !Archived.can_transition_to(*next)));
+        assert!(!Crashed.can_transition_to(Building));
+        assert!(!Created.can_transition_to(Running));
+    }
+
+    #[test]
+    fn only_quarantined_dead_and_archived_are_not_alive() {
+        let not_alive: Vec<AgentState> = AgentState::ALL
+            .into_iter()
+            .filter(|state| !state.is_alive())
+            .collect();
+        assert_eq!(
+            not_alive,
+            vec![
+                AgentState::Quarantined,
+                AgentState::Dead,
+                AgentState::Archived
+            ]
+        );
+    }
+
+    #[test]
+    fn illegal_transition_leaves_the_agent_untouched() {
+        let mut agent = AgentInfo::for_test("a");
+        agent.transition(AgentState::Crashed, "crash").unwrap();
+        let history_len = agent.state_history().len();
+        let error = agent
+            .transition(AgentState::Running, "relance")
+            .unwrap_err();
+        assert_eq!(
+            (error.from, error.to),
+            (AgentState::Crashed, AgentState::Running)
+        );
+        assert_eq!(agent.state(), AgentState::Crashed);
+        assert_eq!(agent.state_history().len(), history_len);
+
+        agent.transition(AgentState::Backoff, "attente").unwrap();
+        let last = agent.state_history().last().unwrap();
+        assert_eq!(
+            (last.from, last.to),
+            (AgentState::Crashed, AgentState::Backoff)
+        );
+        assert_eq!(last.reason, "attente");
+    }
+
+    #[test]
+    fn forced_transition_bypasses_the_table_and_is_audited() {
+        use crate::audit::audit_log::{read_records, AuditEvent, AuditLog};
+        let dir = std::env::temp_dir().join(format!("forced-transition-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let audit = AuditLog::open(&dir.join("audit.jsonl")).unwrap();
+
+        let mut agent = AgentInfo::for_test("a");
+        agent.transition(AgentState::Dead, "mort").unwrap();
+        assert!(!AgentState::Dead.can_transition_to(AgentState::Running));
+        agent.transition_forced(AgentState::Running, "dérogation", "alice", &audit);
+
+        assert_eq!(agent.state(), AgentState::Running);
+        let last = agent.state_history().last().unwrap();
+        assert_eq!(
+            (last.from, last.to, last.reason.as_str()),
+            (AgentState::Dead, AgentState::Running, "dérogation")
+        );
+        let records = read_records(&dir.join("audit.jsonl")).unwrap().records;
+        match &records[..] {
+            [record] => match &record.event {
+                AuditEvent::OperatorAction {
+                    operator,
+                    action,
+                    agent,
+                    detail,
+                    ..
+                } => {
+                    assert_eq!(
+                        (operator.as_str(), action.as_str()),
+                        ("alice", "forced_transition")
+                    );
+                    assert_eq!(agent.as_deref(), Some("a"));
+                    assert_eq!(detail, "dead -> running : dérogation");
+                }
+                event => panic!("événement inattendu : {:?}", event),
+            },
+            records => panic!("{} enregistrements au lieu d'un", records.len()),
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
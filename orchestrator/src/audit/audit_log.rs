@@ -0,0 +1,172 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Événements du cycle de vie et lignes du journal (définis dans le protocole partagé,
+/// relus tels quels par la passerelle ws et l'interface)
+pub use protocol::{AuditEvent, EventRecord as AuditRecord};
+
+/// Journal d'audit en ajout seul (JSONL)
+/// - Une ligne JSON par événement
+/// - Les numéros de séquence reprennent après le dernier enregistré
+/// - Partageable entre threads (écritures sérialisées par un Mutex)
+pub struct AuditLog {
+    file: Mutex<File>,
+    next_seq: AtomicU64,
+}
+
+impl AuditLog {
+    /// Ouvre (ou crée) le journal et reprend la numérotation
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let next_seq = read_records(path)?.next_seq;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Dernière ligne tronquée (arrêt brutal) : la terminer pour ne pas y coller la suivante
+        if !ends_with_newline(path)? {
+            writeln!(file)?;
+        }
+
+        Ok(Self {
+            file: Mutex::new(file),
+            next_seq: AtomicU64::new(next_seq),
+        })
+    }
+
+    /// Ajoute un événement au journal.
+    /// Les erreurs d'écriture sont signalées mais ne bloquent jamais le pipeline.
+    pub fn record(&self, event: AuditEvent) {
+        // Le verrou est pris avant d'attribuer le numéro pour garder l'ordre du fichier
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let record = AuditRecord {
+            seq: self.next_seq.fetch_add(1, Ordering::SeqCst),
+            timestamp: current_timestamp(),
+            event,
+        };
+        let result = serde_json::to_string(&record)
+            .map_err(std::io::Error::from)
+            .and_then(|line| writeln!(file, "{}", line))
+            .and_then(|_| file.flush());
+        if let Err(e) = result {
+            eprintln!("⚠️ Erreur écriture journal d'audit: {}", e);
+        }
+    }
+}
+
+/// Contenu relu d'un journal d'audit
+/// - skipped: lignes illisibles ignorées (ligne tronquée, événement inconnu...)
+/// - next_seq: numéro suivant le plus grand `seq` du fichier, lignes ignorées comprises
+///   (un événement inconnu ne doit pas faire réutiliser son numéro)
+#[derive(Debug, Default)]
+pub struct AuditEntries {
+    pub records: Vec<AuditRecord>,
+    pub skipped: usize,
+    pub next_seq: u64,
+}
+
+/// Relit toutes les entrées d'un journal d'audit (fichier absent = journal vide)
+pub fn read_records(path: &Path) -> std::io::Result<AuditEntries> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(AuditEntries::default()),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = AuditEntries::default();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditRecord>(&line) {
+            Ok(record) => {
+                entries.next_seq = entries.next_seq.max(record.seq + 1);
+                entries.records.push(record);
+            }
+            // Une ligne tronquée (arrêt brutal) ne doit pas rendre le journal illisible
+            Err(e) => {
+                eprintln!("⚠️ Ligne {} du journal ignorée: {}", index + 1, e);
+                entries.skipped += 1;
+                let seq = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|value| value.get("seq")?.as_u64());
+                if let Some(seq) = seq {
+                    entries.next_seq = entries.next_seq.max(seq + 1);
+                }
+            }
+        }
+    }
+    if entries.skipped > 0 {
+        eprintln!(
+            "⚠️ {} ligne(s) illisible(s) ignorée(s) dans {}",
+            entries.skipped,
+            path.display()
+        );
+    }
+    Ok(entries)
+}
+
+/// Vrai si le fichier est vide ou se termine par un saut de ligne
+fn ends_with_newline(path: &Path) -> std::io::Result<bool> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+/// Timestamp UNIX courant en secondes
+pub fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreadable_lines_are_counted_and_their_seq_is_not_reused() {
+        let dir = std::env::temp_dir().join(format!("audit-skipped-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.jsonl");
+        let lines = [
+            // Journal antérieur aux graines : pas de champ `seed`
+            r#"{"seq":0,"timestamp":1,"event":{"type":"agent_created","agent":"a","id":"1","path":"a","energy":10}}"#,
+            r#"{"seq":1,"timestamp":2,"event":{"type":"future_event"}}"#,
+            r#"{"seq":2,"timestamp":3,"event":{"type":"killed","ag"#,
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+
+        let entries = read_records(&path).unwrap();
+        assert_eq!(entries.records.len(), 1);
+        assert!(matches!(
+            entries.records[0].event,
+            AuditEvent::AgentCreated { seed: 0, .. }
+        ));
+        assert_eq!(entries.skipped, 2);
+        // La ligne tronquée n'a pas de seq lisible : la reprise suit l'événement inconnu
+        assert_eq!(entries.next_seq, 2);
+
+        let log = AuditLog::open(&path).unwrap();
+        log.record(AuditEvent::Killed {
+            agent: "a".into(),
+            reason: "test".into(),
+        });
+        let entries = read_records(&path).unwrap();
+        assert_eq!(entries.records.last().unwrap().seq, 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::{read_records, AuditEvent, AuditRecord};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Point d'arrêt de la relecture du journal
+#[derive(Debug, Clone, Copy)]
+pub enum ReplayPoint {
+    /// Tout le journal
+    End,
+    /// Jusqu'au numéro de séquence inclus
+    Seq(u64),
+    /// Jusqu'au timestamp UNIX inclus
+    Timestamp(u64),
+}
+
+impl ReplayPoint {
+    fn includes(&self, record: &AuditRecord) -> bool {
+        match self {
+            ReplayPoint::End => true,
+            ReplayPoint::Seq(seq) => record.seq <= *seq,
+            ReplayPoint::Timestamp(timestamp) => record.timestamp <= *timestamp,
+        }
+    }
+}
+
+/// État reconstruit d'un agent à partir du journal
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayedAgent {
+    pub id: String,
+    pub island: String,
+    pub state: AgentState,
+    pub is_safe: bool,
+    pub is_valid: bool,
+    pub code_hash: String,
+    pub energy: i32,
+    pub crash_count: u32,
+    pub last_score: Option<u32>,
+    pub task_score: Option<f64>,
+    pub kill_reason: Option<String>,
+    pub last_seq: u64,
+    pub last_update: u64,
+}
+
+/// État de la population à un instant donné
+#[derive(Debug, Default, Serialize)]
+pub struct PopulationState {
+    pub last_seq: Option<u64>,
+    /// Lignes illisibles du journal, ignorées par la reconstruction
+    pub skipped_lines: usize,
+    pub last_timestamp: Option<u64>,
+    pub agents: BTreeMap<String, ReplayedAgent>,
+}
+
+impl PopulationState {
+    /// Applique un enregistrement à l'état courant
+    pub fn apply(&mut self, record: &AuditRecord) {
+        self.last_seq = Some(record.seq);
+        self.last_timestamp = Some(record.timestamp);
+
+        let Some(name) = record.event.agent() else {
+            return;
+        };
+        let agent = self.agents.entry(name.to_string()).or_default();
+        agent.last_seq = record.seq;
+        agent.last_update = record.timestamp;
+
+        match &record.event {
+            AuditEvent::AgentCreated {
+                id, energy, island, ..
+            } => {
+                agent.id = id.clone();
+                agent.island = island.clone();
+                agent.state = AgentState::Created;
+                agent.energy = *energy;
+            }
+            AuditEvent::ScanResult { code_hash, .. } => {
+                agent.code_hash = code_hash.clone();
+            }
+            AuditEvent::Validity {
+                is_safe, is_valid, ..
+            } => {
+                agent.is_safe = *is_safe;
+                agent.is_valid = *is_valid;
+            }
+            AuditEvent::SelectionScore { score, energy, .. } => {
+                agent.last_score = Some(*score);
+                agent.energy = *energy;
+            }
+            AuditEvent::TaskEvaluated { score, energy, .. } => {
+                agent.task_score = Some(*score);
+                agent.energy = *energy;
+            }
+            AuditEvent::AgentRequest { energy, .. } | AuditEvent::EnergyChanged { energy, .. } => {
+                agent.energy = *energy;
+            }
+            AuditEvent::Crash { crash_count, .. } => {
+                agent.crash_count = *crash_count;
+            }
+            AuditEvent::StateChanged { to, .. } => {
+                agent.state = *to;
+            }
+            AuditEvent::Killed { reason, .. } => {
+                agent.kill_reason = Some(reason.clone());
+            }
+            // La copie a déjà son propre événement de création
+            AuditEvent::GenerationSummary { .. }
+            | AuditEvent::SpeciesSummary { .. }
+            | AuditEvent::SpeciesExtinct { .. }
+            | AuditEvent::DiversityMetrics { .. }
+            | AuditEvent::DiversityIntervention { .. }
+            | AuditEvent::ParetoFront { .. }
+            | AuditEvent::CoevolutionGeneration { .. }
+            | AuditEvent::Migrated { .. }
+            | AuditEvent::NoveltyArchived { .. }
+            | AuditEvent::HeartbeatLost { .. }
+            | AuditEvent::WorldInteraction { .. }
+            | AuditEvent::OperatorAction { .. }
+            | AuditEvent::SnapshotRestored { .. }
+            | AuditEvent::AccessDenied { .. } => {}
+        }
+    }
+}
+
+/// Reconstruit l'état de la population jusqu'au point demandé
+pub fn replay_audit_log(path: &Path, until: ReplayPoint) -> std::io::Result<PopulationState> {
+    let entries = read_records(path)?;
+    let mut state = PopulationState {
+        skipped_lines: entries.skipped,
+        ..Default::default()
+    };
+    // Filtre plutôt que de s'arrêter au premier enregistrement hors limite : seq suit
+    // l'ordre du fichier, mais pas forcément les timestamps (horloge recalée entre
+    // deux reprises du journal)
+    for record in entries
+        .records
+        .iter()
+        .filter(|record| until.includes(record))
+    {
+        state.apply(record);
+    }
+    Ok(state)
+}
+
+/// Affiche un récapitulatif lisible de l'état reconstruit
+pub fn print_population_state(state: &PopulationState) {
+    match (state.last_seq, state.last_timestamp) {
+        (Some(seq), Some(timestamp)) => {
+            println!("📜 État reconstruit jusqu'à seq={} (t={})", seq, timestamp)
+        }
+        _ => {
+            println!("📜 Journal d'audit vide");
+            return;
+        }
+    }
+    if state.skipped_lines > 0 {
+        println!(
+            "⚠️ {} ligne(s) illisible(s) ignorée(s) : état possiblement incomplet",
+            state.skipped_lines
+        );
+    }
+    for (name, agent) in &state.agents {
+        println!(
+            "  {} island={} state={} safe={} valid={} energy={} crashes={} score={} {}",
+            name,
+            agent.island,
+            agent.state,
+            agent.is_safe,
+            agent.is_valid,
+            agent.energy,
+            agent.crash_count,
+            agent
+                .last_score
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "-".into()),
+            agent
+                .kill_reason
+                .as_ref()
+                .map(|r| format!("(tué: {})", r))
+                .unwrap_or_default(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::audit_log::AuditLog;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Journal d'un agent créé, validé, noté, lancé puis tué (seq 0 à 4)
+    fn write_log(name: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("replay-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("audit.jsonl");
+        let log = AuditLog::open(&path).unwrap();
+        let agent = || "agent_1".to_string();
+        log.record(AuditEvent::AgentCreated {
+            agent: agent(),
+            id: "id-1".into(),
+            path: PathBuf::from("agents/main/agent_1"),
+            energy: 1000,
+            seed: 42,
+            generation: 0,
+            island: "main".into(),
+        });
+        log.record(AuditEvent::Validity {
+            agent: agent(),
+            is_safe: true,
+            is_valid: true,
+        });
+        log.record(AuditEvent::SelectionScore {
+            agent: agent(),
+            score: 12,
+            max_score: 100,
+            energy: 950,
+            survived: true,
+        });
+        log.record(AuditEvent::StateChanged {
+            agent: agent(),
+            from: AgentState::Created,
+            to: AgentState::Building,
+            reason: "compilation".into(),
+        });
+        log.record(AuditEvent::Killed {
+            agent: agent(),
+            reason: "énergie épuisée".into(),
+        });
+        (dir, path)
+    }
+
+    #[test]
+    fn full_replay_rebuilds_the_last_known_state() {
+        let (dir, path) = write_log("end");
+        let state = replay_audit_log(&path, ReplayPoint::End).unwrap();
+        assert_eq!(state.last_seq, Some(4));
+        assert_eq!(state.skipped_lines, 0);
+        let agent = &state.agents["agent_1"];
+        assert_eq!((agent.id.as_str(), agent.island.as_str()), ("id-1", "main"));
+        assert!(agent.is_safe && agent.is_valid);
+        assert_eq!((agent.energy, agent.last_score), (950, Some(12)));
+        assert_eq!(agent.state, AgentState::Building);
+        assert_eq!(agent.kill_reason.as_deref(), Some("énergie épuisée"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn replay_stops_at_the_requested_point() {
+        let (dir, path) = write_log("until");
+        let state = replay_audit_log(&path, ReplayPoint::Seq(1)).unwrap();
+        assert_eq!(state.last_seq, Some(1));
+        let agent = &state.agents["agent_1"];
+        assert_eq!((agent.state, agent.energy), (AgentState::Created, 1000));
+        assert!(agent.last_score.is_none() && agent.kill_reason.is_none());
+
+        let state = replay_audit_log(&path, ReplayPoint::Timestamp(0)).unwrap();
+        assert!(state.last_seq.is_none() && state.agents.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_log_replays_to_an_empty_population() {
+        let path = std::env::temp_dir().join(format!("replay-missing-{}", std::process::id()));
+        let state = replay_audit_log(&path, ReplayPoint::End).unwrap();
+        assert!(state.last_seq.is_none() && state.agents.is_empty());
+    }
+}
@@ -1,9 +1,11 @@
-use std::process::{Child, Command, Output};
+use std::process::{Child, Command, Output, Stdio};
+use std::time::{Duration, Instant};
 
 /// Commandes cargo génériques :
 /// - check: vérifie la compilation d'un projet Rust
-/// - build: compile un projet Rust
-/// - run: lance un projet Rust via cargo
+/// - build: compile un projet Rust (interrompu au-delà d'un délai)
+/// - run: lance un projet Rust via cargo (avec arguments pour le binaire et sorties redirigées)
+/// - run_piped: idem, stdin et stdout reliés à l'appelant (harnais de tâches)
 
 pub fn check(manifest_path: &str) -> std::io::Result<Output> {
     Command::new("cargo")
@@ -11,14 +13,63 @@ pub fn check(manifest_path: &str) -> std::io::Result<Output> {
         .output()
 }
 
-pub fn build(manifest_path: &str) -> std::io::Result<Output> {
-    Command::new("cargo")
+/// Issue d'une compilation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildOutcome {
+    Success,
+    Failed,
+    TimedOut,
+}
+
+pub fn build(manifest_path: &str, timeout: Duration) -> std::io::Result<BuildOutcome> {
+    let mut child = Command::new("cargo")
         .args(["build", "--manifest-path", manifest_path])
-        .output()
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(if status.success() {
+                BuildOutcome::Success
+            } else {
+                BuildOutcome::Failed
+            });
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(BuildOutcome::TimedOut);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+pub fn run(
+    manifest_path: &str,
+    program_args: &[String],
+    stdout: Stdio,
+    stderr: Stdio,
+) -> std::io::Result<Child> {
+    Command::new("cargo")
+        .args(["run", "--manifest-path", manifest_path, "--"])
+        .args(program_args)
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()
 }
 
-pub fn run(manifest_path: &str) -> std::io::Result<Child> {
+pub fn run_piped(
+    manifest_path: &str,
+    program_args: &[String],
+    stderr: Stdio,
+) -> std::io::Result<Child> {
     Command::new("cargo")
-        .args(["run", "--manifest-path", manifest_path])
+        .args(["run", "--quiet", "--manifest-path", manifest_path, "--"])
+        .args(program_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(stderr)
         .spawn()
 }
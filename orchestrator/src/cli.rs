@@ -0,0 +1,67 @@
+use crate::audit::replay::ReplayPoint;
+use std::path::PathBuf;
+
+/// Commandes disponibles en ligne de commande
+/// - (aucune) / run : initialise le projet et lance le cycle de vie
+/// - resume : relance le cycle de vie sur la population existante (ex: après restore)
+/// - replay [--until-seq N | --until-time T] : reconstruit la population depuis le journal d'audit
+/// - snapshot [LABEL] : archive la population courante (orchestrateur arrêté, sinon POST /snapshot)
+/// - restore ARCHIVE [--workspace DIR] : restaure une population (DIR pour forker l'expérience)
+pub enum CliCommand {
+    Run,
+    Resume,
+    Replay {
+        until: ReplayPoint,
+    },
+    Snapshot {
+        label: Option<String>,
+    },
+    Restore {
+        archive: PathBuf,
+        workspace: Option<PathBuf>,
+    },
+}
+
+/// Analyse les arguments (sans le nom du binaire)
+pub fn parse_args(args: &[String]) -> Result<CliCommand, String> {
+    match args.first().map(String::as_str) {
+        None | Some("run") => Ok(CliCommand::Run),
+        Some("resume") => Ok(CliCommand::Resume),
+        Some("replay") => {
+            let until = match (args.get(1).map(String::as_str), args.get(2)) {
+                (None, _) => ReplayPoint::End,
+                (Some("--until-seq"), Some(value)) => ReplayPoint::Seq(parse_u64(value)?),
+                (Some("--until-time"), Some(value)) => ReplayPoint::Timestamp(parse_u64(value)?),
+                (Some(flag), _) => return Err(format!("Option replay invalide: {}", flag)),
+            };
+            Ok(CliCommand::Replay { until })
+        }
+        Some("snapshot") => Ok(CliCommand::Snapshot {
+            label: args.get(1).cloned(),
+        }),
+        Some("restore") => {
+            let archive = args
+                .get(1)
+                .map(PathBuf::from)
+                .ok_or_else(|| "restore: chemin de l'archive manquant".to_string())?;
+            let workspace = match (args.get(2).map(String::as_str), args.get(3)) {
+                (None, _) => None,
+                (Some("--workspace"), Some(dir)) => Some(PathBuf::from(dir)),
+                (Some(flag), _) => return Err(format!("Option restore invalide: {}", flag)),
+            };
+            Ok(CliCommand::Restore { archive, workspace })
+        }
+        Some(other) => Err(format!("Commande inconnue: {}", other)),
+    }
+}
+
+fn parse_u64(value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Valeur numérique invalide: {}", value))
+}
+
+pub fn usage() -> &'static str {
+    "Usage: orchestrator [run | resume | replay [--until-seq N | --until-time UNIX_SECS]\n\
+     | snapshot [LABEL] | restore ARCHIVE [--workspace DIR]]"
+}
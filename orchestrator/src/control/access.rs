@@ -0,0 +1,35 @@
+use protocol::AccessTokens;
+use rand::RngCore;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Génère de nouveaux jetons (viewer / operator) et les écrit dans `path`,
+/// lisible par le seul utilisateur courant. Les jetons précédents sont invalidés.
+pub fn generate_access_tokens(path: &Path) -> std::io::Result<AccessTokens> {
+    let tokens = AccessTokens {
+        viewer: random_token(),
+        operator: random_token(),
+    };
+    let content = serde_json::to_string_pretty(&tokens).map_err(std::io::Error::from)?;
+
+    // Recréé (et non tronqué) : un ancien fichier trop permissif ne garde pas ses droits
+    let _ = std::fs::remove_file(path);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(tokens)
+}
+
+/// 32 octets aléatoires en hexadécimal
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,154 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::audit::audit_log::read_records;
+use crate::control::command::ControlError;
+use crate::genome::GenomeConfig;
+use crate::project_paths::ProjectPaths;
+use protocol::{
+    AgentLogs, DiffHunk, DiffLine, DiffLineKind, EventRecord, FileDiff, GeneView, GenomeView,
+    SourceDiff,
+};
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lignes de contexte autour de chaque modification
+const DIFF_CONTEXT: usize = 3;
+
+/// Génome d'un agent, lu depuis son genome.bin
+pub fn genome_view(agent: &AgentInfo, paths: &ProjectPaths) -> Result<GenomeView, ControlError> {
+    let name = &agent.name;
+    let bytes = fs::read(paths.agent_genome_path(&agent.island, name))
+        .map_err(|e| ControlError::not_found(format!("Génome de {} : {}", name, e)))?;
+    let genome: GenomeConfig = bincode::deserialize(&bytes)
+        .map_err(|e| ControlError::internal(format!("Génome illisible : {}", e)))?;
+    Ok(GenomeView {
+        files: genome
+            .files
+            .into_iter()
+            .map(|gene| GeneView {
+                path: gene.path,
+                active: gene.active,
+                functions: gene.functions.into_iter().collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Différences des sources (`src/` et Cargo.toml) entre un agent et son premier parent
+/// encore présent sur disque, ou le template à défaut
+pub fn source_diff(agent: &AgentInfo, listing: &AgentsListing, paths: &ProjectPaths) -> SourceDiff {
+    let parent = agent.lineage.parents.iter().find_map(|name| {
+        listing
+            .agents
+            .iter()
+            .find(|a| &a.name == name && a.path.exists())
+    });
+    let (reference, reference_dir) = match parent {
+        Some(parent) => (format!("parent {}", parent.name), parent.path.clone()),
+        None => ("template".to_string(), paths.template_dir.clone()),
+    };
+
+    let files: BTreeSet<PathBuf> = source_files(&reference_dir)
+        .into_iter()
+        .chain(source_files(&agent.path))
+        .collect();
+    let files = files
+        .into_iter()
+        .filter_map(|relative| {
+            let old = fs::read_to_string(reference_dir.join(&relative)).unwrap_or_default();
+            let new = fs::read_to_string(agent.path.join(&relative)).unwrap_or_default();
+            if old == new {
+                return None;
+            }
+            Some(FileDiff {
+                path: relative.display().to_string(),
+                hunks: diff_hunks(&old, &new),
+            })
+        })
+        .collect();
+
+    SourceDiff { reference, files }
+}
+
+/// Fichiers sources relatifs à la racine d'un agent (target/ exclu)
+fn source_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from("Cargo.toml")];
+    collect_rs_files(root, &root.join("src"), &mut files);
+    files
+}
+
+fn collect_rs_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rs_files(root, &path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            if let Ok(relative) = path.strip_prefix(root) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+}
+
+fn diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    diff.unified_diff()
+        .context_radius(DIFF_CONTEXT)
+        .iter_hunks()
+        .map(|hunk| DiffHunk {
+            header: hunk.header().to_string(),
+            lines: hunk
+                .iter_changes()
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Context,
+                        ChangeTag::Insert => DiffLineKind::Added,
+                        ChangeTag::Delete => DiffLineKind::Removed,
+                    },
+                    text: change.value().trim_end_matches('\n').to_string(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Dernières lignes de stdout.log et stderr.log d'un agent (vides s'il n'a jamais tourné)
+pub fn agent_logs(paths: &ProjectPaths, name: &str, lines: usize) -> AgentLogs {
+    let dir = paths.agent_logs_dir(name);
+    AgentLogs {
+        stdout: tail(&dir.join("stdout.log"), lines),
+        stderr: tail(&dir.join("stderr.log"), lines),
+    }
+}
+
+fn tail(path: &Path, lines: usize) -> Vec<String> {
+    let content = fs::read(path).unwrap_or_default();
+    let content = String::from_utf8_lossy(&content);
+    let all: Vec<&str> = content.lines().collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Derniers événements du journal d'audit concernant un agent
+pub fn agent_events(
+    paths: &ProjectPaths,
+    name: &str,
+    limit: usize,
+) -> Result<Vec<EventRecord>, ControlError> {
+    let entries = read_records(&paths.audit_log_path())
+        .map_err(|e| ControlError::internal(format!("Journal illisible : {}", e)))?;
+    let mut events: Vec<EventRecord> = entries
+        .records
+        .into_iter()
+        .filter(|record| record.agent() == Some(name))
+        .collect();
+    let skip = events.len().saturating_sub(limit);
+    events.drain(..skip);
+    Ok(events)
+}
@@ -0,0 +1,125 @@
+use crate::agent_listing::AgentInfo;
+use crate::agent_selection::SelectionCriteria;
+use crate::audit::audit_log::AuditEvent;
+use std::sync::mpsc::Sender;
+
+/// Commandes opérateur (définies dans le protocole partagé), exécutées par le cycle
+/// de vie entre deux tours, avec le listing verrouillé : aucune course avec le pipeline
+pub use protocol::Command as ControlCommand;
+
+/// Erreur renvoyée à l'appelant (code HTTP + message)
+#[derive(Debug, Clone)]
+pub struct ControlError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl ControlError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: 404,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: 400,
+            message: message.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: 409,
+            message: message.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: 500,
+            message: message.into(),
+        }
+    }
+}
+
+pub type ControlResult = Result<serde_json::Value, ControlError>;
+
+/// Commande accompagnée de son émetteur (nom d'opérateur) et du canal de réponse
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub operator: String,
+    pub reply: Sender<ControlResult>,
+}
+
+/// Événement d'audit d'une commande opérateur (nom de la commande, agent visé, issue)
+pub fn operator_event(
+    operator: &str,
+    command: &ControlCommand,
+    result: &ControlResult,
+) -> AuditEvent {
+    let params = serde_json::to_value(command).unwrap_or_default();
+    let field = |key: &str| params.get(key).and_then(|v| v.as_str()).map(String::from);
+    let (success, detail) = match result {
+        Ok(_) => (true, params.to_string()),
+        Err(e) => (false, e.message.clone()),
+    };
+    AuditEvent::OperatorAction {
+        operator: operator.to_string(),
+        action: command.name(),
+        agent: field("name"),
+        success,
+        detail,
+    }
+}
+
+/// Paramètres de sélection exposés aux clients (type du protocole partagé)
+pub use protocol::SelectionParams;
+
+impl From<&SelectionCriteria> for SelectionParams {
+    fn from(criteria: &SelectionCriteria) -> Self {
+        Self {
+            penalty_if_not_safe: criteria.penalty_if_not_safe,
+            penalty_if_not_valid: criteria.penalty_if_not_valid,
+            penalty_if_resource_exceeded: criteria.penalty_if_resource_exceeded,
+            penalty_per_unwrap: criteria.penalty_per_unwrap,
+            max_score: criteria.max_score,
+        }
+    }
+}
+
+impl From<SelectionParams> for SelectionCriteria {
+    fn from(params: SelectionParams) -> Self {
+        Self {
+            penalty_if_not_safe: params.penalty_if_not_safe,
+            penalty_if_not_valid: params.penalty_if_not_valid,
+            penalty_if_resource_exceeded: params.penalty_if_resource_exceeded,
+            penalty_per_unwrap: params.penalty_per_unwrap,
+            max_score: params.max_score,
+        }
+    }
+}
+
+/// Vue résumée d'un agent pour les listes (type du protocole partagé)
+pub use protocol::AgentSummary;
+
+impl From<&AgentInfo> for AgentSummary {
+    fn from(agent: &AgentInfo) -> Self {
+        Self {
+            name: agent.name.clone(),
+            id: agent.id.clone(),
+            island: agent.island.clone(),
+            species: agent.species,
+            novelty: agent.novelty,
+            pareto_front: agent.pareto.map(|rank| rank.front),
+            task_score: agent.task_score,
+            state: agent.state(),
+            state_since: agent.state_since(),
+            energy: agent.energy,
+            fitness: agent.fitness(),
+            crash_count: agent.crash_count,
+            generation: agent.lineage.generation,
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use crate::control::command::{ControlCommand, ControlError, ControlRequest, ControlResult};
+use crate::monitoring::metrics::Metrics;
+use crate::shutdown::ShutdownToken;
+use protocol::{AccessTokens, Role};
+use std::io::Read;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Délai maximal d'attente d'une réponse du cycle de vie (un tour peut compiler des agents)
+const REPLY_TIMEOUT: Duration = Duration::from_secs(120);
+/// Threads servant les requêtes : une commande qui attend le cycle de vie n'en bloque
+/// qu'un, les autres continuent de répondre (/metrics compris)
+const WORKERS: usize = 4;
+/// Taille maximale du corps d'une requête (commande ou paramètres JSON)
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+/// En-tête où un client déclare le nom de la personne qui agit (non vérifié)
+const OPERATOR_HEADER: &str = "X-Operator";
+/// Longueur maximale d'un nom d'opérateur déclaré
+const MAX_OPERATOR_LEN: usize = 64;
+/// Valeurs par défaut des paramètres `lines` et `limit`
+const DEFAULT_LOG_LINES: usize = 200;
+const DEFAULT_EVENT_LIMIT: usize = 100;
+
+/// Vrai si une API de contrôle (donc un orchestrateur) écoute déjà sur `addr`
+pub fn is_serving(addr: &str) -> bool {
+    addr.parse::<SocketAddr>()
+        .is_ok_and(|addr| TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok())
+}
+
+/// Démarre l'API HTTP/JSON de contrôle (`WORKERS` threads se partagent les requêtes).
+/// Routes :
+/// - GET  /agents, /agents/{name}, /agents/{name}/genome, /config, /generations, /species,
+///   /pareto
+/// - GET  /agents/{name}/diff, /agents/{name}/resources,
+///   /agents/{name}/logs[?lines=N], /agents/{name}/events[?limit=N]
+/// - GET  /metrics (format texte Prometheus, servi sans passer par le cycle de vie)
+/// - POST /agents/{name}/pause, /agents/{name}/resume, /agents/{name}/clone
+/// - POST /agents/{name}/kill?confirm=true, /agents/{name}/revive?confirm=true
+///   (`confirm: true` pour ces commandes envoyées à /commands ; refusées sinon)
+/// - POST /snapshot[?label=LABEL]
+/// - GET  /selection, PUT /selection (corps JSON : `protocol::SelectionParams`)
+/// - POST /commands (corps JSON : une `protocol::Command`, utilisé par la passerelle ws ;
+///   `report_access_denied` y consigne les refus constatés par la passerelle)
+///
+/// Les commandes sont transmises au cycle de vie, qui les exécute entre deux tours.
+/// L'auteur des actions dans le journal d'audit est le rôle du jeton présenté, suivi du
+/// nom déclaré dans l'en-tête `X-Operator` (ex: `operator (déclaré : alice)`).
+///
+/// Chaque requête présente un jeton (`Authorization: Bearer <jeton>`) : le jeton viewer
+/// donne accès aux lectures, le jeton operator aussi aux actions. Les refus sont journalisés.
+pub fn spawn_http_api(
+    addr: &str,
+    commands: Sender<ControlRequest>,
+    metrics: Arc<Metrics>,
+    tokens: AccessTokens,
+    audit: Arc<AuditLog>,
+    shutdown: ShutdownToken,
+) -> std::io::Result<Vec<JoinHandle<()>>> {
+    let socket_addr: SocketAddr = addr
+        .parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    if !socket_addr.ip().is_loopback() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("L'API de contrôle doit écouter sur localhost ({})", addr),
+        ));
+    }
+    let server = Server::http(socket_addr).map_err(|e| std::io::Error::other(e.to_string()))?;
+    println!("🌐 API de contrôle sur http://{}", socket_addr);
+
+    let server = Arc::new(server);
+    let workers = (0..WORKERS)
+        .map(|_| {
+            let server = server.clone();
+            let commands = commands.clone();
+            let metrics = metrics.clone();
+            let tokens = tokens.clone();
+            let audit = audit.clone();
+            let shutdown = shutdown.clone();
+            thread::spawn(move || {
+                while !shutdown.is_cancelled() {
+                    match server.recv_timeout(Duration::from_millis(200)) {
+                        Ok(Some(request)) => {
+                            handle_request(request, &commands, &metrics, &tokens, &audit)
+                        }
+                        Ok(None) => {}
+                        // Erreur d'une connexion : l'API continue de servir les suivantes
+                        Err(e) => {
+                            eprintln!("⚠️ Erreur de l'API de contrôle : {}", e);
+                            shutdown.sleep(Duration::from_millis(200));
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    Ok(workers)
+}
+
+fn handle_request(
+    mut request: Request,
+    commands: &Sender<ControlRequest>,
+    metrics: &Metrics,
+    tokens: &AccessTokens,
+    audit: &AuditLog,
+) {
+    let role = bearer_token(&request).and_then(|token| tokens.role_of(&token));
+    let operator = operator(&request, role);
+    let authorize = |required: Role, action: &str| {
+        check_access(role, required).inspect_err(|e| {
+            eprintln!("⛔ Accès refusé ({}, {}) : {}", operator, action, e.message);
+            audit.record(AuditEvent::AccessDenied {
+                operator: operator.clone(),
+                action: action.to_string(),
+                reason: e.message.clone(),
+            });
+        })
+    };
+
+    if request.method() == &Method::Get && request.url() == "/metrics" {
+        if let Err(e) = authorize(Role::Viewer, "metrics") {
+            respond(request, Err(e));
+            return;
+        }
+        let header = Header::from_bytes("Content-Type", "text/plain; version=0.0.4")
+            .expect("En-tête Content-Type valide");
+        let response = Response::from_string(metrics.render()).with_header(header);
+        if let Err(e) = request.respond(response) {
+            eprintln!("⚠️ Réponse HTTP impossible : {}", e);
+        }
+        return;
+    }
+
+    let command = if request.method() == &Method::Post && request.url() == "/commands" {
+        read_json(&mut request)
+    } else if request.method() == &Method::Put && request.url() == "/selection" {
+        read_json(&mut request).map(|params| ControlCommand::SetSelectionParams { params })
+    } else {
+        route(request.method(), request.url()).ok_or_else(|| {
+            ControlError::not_found(format!(
+                "Route inconnue : {} {}",
+                request.method(),
+                request.url()
+            ))
+        })
+    };
+    let result = command.and_then(|command| {
+        authorize(command.required_role(), &command.name())?;
+        if command.lacks_confirmation() {
+            return Err(ControlError::bad_request(format!(
+                "Confirmation requise pour {} (confirm: true)",
+                command.name()
+            )));
+        }
+        match command {
+            // Refus signalé par la passerelle ws : consigné ici, sans passer par le cycle de vie
+            ControlCommand::ReportAccessDenied {
+                operator: reported,
+                action,
+                reason,
+            } => {
+                audit.record(AuditEvent::AccessDenied {
+                    operator: reported,
+                    action,
+                    reason,
+                });
+                Ok(serde_json::Value::Null)
+            }
+            command => execute(commands, command, operator.clone()),
+        }
+    });
+    respond(request, result);
+}
+
+/// Associe méthode + chemin à une commande
+fn route(method: &Method, url: &str) -> Option<ControlCommand> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let name = |s: &str| s.to_string();
+
+    let command = match (method, segments.as_slice()) {
+        (Method::Get, ["agents"]) => ControlCommand::ListAgents,
+        (Method::Get, ["agents", agent]) => ControlCommand::GetAgent { name: name(agent) },
+        (Method::Get, ["agents", agent, "genome"]) => {
+            ControlCommand::GetGenome { name: name(agent) }
+        }
+        (Method::Get, ["agents", agent, "diff"]) => {
+            ControlCommand::GetSourceDiff { name: name(agent) }
+        }
+        (Method::Get, ["agents", agent, "logs"]) => ControlCommand::GetLogs {
+            name: name(agent),
+            lines: number_param(query, "lines").unwrap_or(DEFAULT_LOG_LINES),
+        },
+        (Method::Get, ["agents", agent, "resources"]) => {
+            ControlCommand::GetResourceHistory { name: name(agent) }
+        }
+        (Method::Get, ["agents", agent, "events"]) => ControlCommand::GetAgentEvents {
+            name: name(agent),
+            limit: number_param(query, "limit").unwrap_or(DEFAULT_EVENT_LIMIT),
+        },
+        (Method::Get, ["agents", agent, "channel"]) => {
+            ControlCommand::GetAgentChannel { name: name(agent) }
+        }
+        (Method::Post, ["agents", agent, "kill"]) => ControlCommand::Kill {
+            name: name(agent),
+            confirm: query_param(query, "confirm").as_deref() == Some("true"),
+        },
+        (Method::Post, ["agents", agent, "pause"]) => ControlCommand::Pause { name: name(agent) },
+        (Method::Post, ["agents", agent, "resume"]) => ControlCommand::Resume { name: name(agent) },
+        (Method::Post, ["agents", agent, "revive"]) => ControlCommand::Revive {
+            name: name(agent),
+            confirm: query_param(query, "confirm").as_deref() == Some("true"),
+        },
+        (Method::Post, ["agents", agent, "clone"]) => ControlCommand::Clone { name: name(agent) },
+        (Method::Post, ["snapshot"]) => ControlCommand::Snapshot {
+            label: query_param(query, "label"),
+        },
+        (Method::Get, ["config"]) => ControlCommand::GetConfig,
+        (Method::Get, ["generations"]) => ControlCommand::ListGenerations,
+        (Method::Get, ["species"]) => ControlCommand::ListSpecies,
+        (Method::Get, ["pareto"]) => ControlCommand::ListParetoFronts,
+        (Method::Get, ["coevolution"]) => ControlCommand::ListCoevolution,
+        (Method::Get, ["world"]) => ControlCommand::GetWorld,
+        (Method::Get, ["selection"]) => ControlCommand::GetSelectionParams,
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Lit le corps JSON de la requête (commande ou paramètres)
+fn read_json<T: serde::de::DeserializeOwned>(request: &mut Request) -> Result<T, ControlError> {
+    let body = read_body(request.as_reader())?;
+    serde_json::from_str(&body)
+        .map_err(|e| ControlError::bad_request(format!("Commande invalide : {}", e)))
+}
+
+/// Lit au plus `MAX_BODY_BYTES` (413 au-delà, sans lire la suite)
+fn read_body(reader: &mut dyn Read) -> Result<String, ControlError> {
+    let mut body = String::new();
+    reader
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ControlError::bad_request(format!("Corps illisible : {}", e)))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(ControlError {
+            status: 413,
+            message: format!("Corps trop volumineux (max {} octets)", MAX_BODY_BYTES),
+        });
+    }
+    Ok(body)
+}
+
+/// Jeton présenté dans l'en-tête `Authorization: Bearer <jeton>`
+fn bearer_token(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+}
+
+/// 401 sans jeton valide, 403 si le rôle du jeton ne suffit pas
+fn check_access(role: Option<Role>, required: Role) -> Result<(), ControlError> {
+    match role {
+        None => Err(ControlError {
+            status: 401,
+            message: "Jeton d'accès manquant ou invalide".into(),
+        }),
+        Some(role) if role < required => Err(ControlError {
+            status: 403,
+            message: "Rôle operator requis".into(),
+        }),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Auteur journalisé : le rôle du jeton (seule identité vérifiée), suivi du nom
+/// déclaré dans l'en-tête `X-Operator` (tronqué) s'il y en a un
+fn operator(request: &Request, role: Option<Role>) -> String {
+    let declared = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(OPERATOR_HEADER))
+        .map(|h| h.value.as_str().trim())
+        .filter(|name| !name.is_empty());
+    operator_name(role, declared)
+}
+
+fn operator_name(role: Option<Role>, declared: Option<&str>) -> String {
+    let authenticated = match role {
+        Some(Role::Operator) => "operator",
+        Some(Role::Viewer) => "viewer",
+        None => "anonyme",
+    };
+    match declared {
+        Some(name) => {
+            let name: String = name.chars().take(MAX_OPERATOR_LEN).collect();
+            format!("{} (déclaré : {})", authenticated, name)
+        }
+        None => authenticated.to_string(),
+    }
+}
+
+fn number_param(query: &str, key: &str) -> Option<usize> {
+    query_param(query, key).and_then(|v| v.parse().ok())
+}
+
+/// Valeur décodée d'un paramètre de requête (`+` et séquences `%XX`)
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| percent_decode(k).as_deref() == Some(key))
+        .and_then(|(_, v)| percent_decode(v))
+}
+
+/// Décode `+` (espace) et `%XX` ; None si le résultat n'est pas de l'UTF-8 valide.
+/// Un `%` non suivi de deux chiffres hexadécimaux est gardé tel quel.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+        match (bytes[i], hex) {
+            (b'+', _) => decoded.push(b' '),
+            (b'%', Some(hex)) => {
+                let hex = std::str::from_utf8(hex).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 2;
+            }
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Transmet la commande au cycle de vie et attend sa réponse
+fn execute(
+    commands: &Sender<ControlRequest>,
+    command: ControlCommand,
+    operator: String,
+) -> ControlResult {
+    let (reply, response) = mpsc::channel();
+    commands
+        .send(ControlRequest {
+            command,
+            operator,
+            reply,
+        })
+        .map_err(|_| ControlError::internal("Cycle de vie arrêté"))?;
+    response
+        .recv_timeout(REPLY_TIMEOUT)
+        .map_err(|_| ControlError {
+            status: 504,
+            message: "Pas de réponse du cycle de vie".into(),
+        })?
+}
+
+fn respond(request: Request, result: ControlResult) {
+    let (status, body) = match result {
+        Ok(value) => (200, value),
+        Err(e) => (e.status, serde_json::json!({ "error": e.message })),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json")
+        .expect("En-tête Content-Type valide");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("⚠️ Réponse HTTP impossible : {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kill_and_revive_need_an_explicit_confirmation() {
+        for action in ["kill", "revive"] {
+            let url = format!("/agents/a/{}", action);
+            let command = route(&Method::Post, &url).unwrap();
+            assert!(command.lacks_confirmation(), "{}", url);
+            let command = route(&Method::Post, &format!("{}?confirm=true", url)).unwrap();
+            assert!(!command.lacks_confirmation(), "{}", url);
+        }
+        let command: ControlCommand =
+            serde_json::from_str(r#"{"command":"kill","name":"a"}"#).unwrap();
+        assert!(command.lacks_confirmation());
+        assert!(!route(&Method::Post, "/agents/a/pause")
+            .unwrap()
+            .lacks_confirmation());
+    }
+
+    #[test]
+    fn query_values_are_percent_decoded() {
+        assert_eq!(
+            percent_decode("run%2D1+b%C3%A9ta").as_deref(),
+            Some("run-1 béta")
+        );
+        assert_eq!(percent_decode("100%").as_deref(), Some("100%"));
+        assert_eq!(percent_decode("%zz%4").as_deref(), Some("%zz%4"));
+        assert!(percent_decode("%FF").is_none());
+        let command = route(&Method::Post, "/snapshot?label=avant%5Fmigration").unwrap();
+        assert!(matches!(
+            command,
+            ControlCommand::Snapshot { label: Some(label) } if label == "avant_migration"
+        ));
+        let command = route(&Method::Post, "/agents/a/kill?confirm=%74rue").unwrap();
+        assert!(!command.lacks_confirmation());
+    }
+
+    #[test]
+    fn oversized_bodies_are_rejected() {
+        let body = vec![b' '; MAX_BODY_BYTES as usize];
+        assert!(read_body(&mut body.as_slice()).is_ok());
+        let body = vec![b' '; MAX_BODY_BYTES as usize + 1];
+        assert_eq!(read_body(&mut body.as_slice()).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn the_audited_operator_comes_from_the_token() {
+        assert_eq!(operator_name(Some(Role::Operator), None), "operator");
+        assert_eq!(
+            operator_name(Some(Role::Viewer), Some("alice")),
+            "viewer (déclaré : alice)"
+        );
+        assert_eq!(
+            operator_name(None, Some("operator")),
+            "anonyme (déclaré : operator)"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod access;
+pub mod agent_detail;
+pub mod command;
+pub mod http_api;
@@ -0,0 +1,269 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::audit::audit_log::AuditEvent;
+use crate::genome::GenomeConfig;
+use crate::genome_distance::{genome_distance, DistanceWeights};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Mesures prises quand la diversité devient trop faible (définies dans le protocole
+/// partagé, consignées par l'événement `diversity_intervention`)
+pub use protocol::DiversityIntervention;
+
+/// Surveillance de la diversité
+/// - min_mean_distance: distance moyenne entre génomes vivants en dessous de laquelle
+///   la population est considérée comme convergée
+/// - interventions: mesures appliquées, dans l'ordre
+/// - mutation_rate / boosted_mutation_rate: taux de mutation des clones et migrants,
+///   en temps normal et tant que la diversité reste faible
+/// - immigrants: agents ajoutés par intervention (immigrants ou agents archivés)
+/// - cooldown_ticks: tours minimum entre deux ajouts d'agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiversityConfig {
+    pub min_mean_distance: f64,
+    pub interventions: Vec<DiversityIntervention>,
+    pub mutation_rate: f64,
+    pub boosted_mutation_rate: f64,
+    pub immigrants: usize,
+    pub cooldown_ticks: u64,
+}
+
+impl Default for DiversityConfig {
+    fn default() -> Self {
+        Self {
+            min_mean_distance: 0.05,
+            interventions: vec![
+                DiversityIntervention::RaiseMutationRate,
+                DiversityIntervention::RandomImmigrants,
+            ],
+            mutation_rate: 0.02,
+            boosted_mutation_rate: 0.1,
+            immigrants: 1,
+            cooldown_ticks: 12, // 1 minute à un tour toutes les 5 s
+        }
+    }
+}
+
+/// Mesures de diversité d'un groupe d'agents vivants
+/// - mean_distance: distance moyenne entre paires de génomes
+/// - unique_code_hashes: nombre d'empreintes de code distinctes
+/// - gene_entropy: entropie (en bits) de l'activation de chaque fichier du génome
+/// - mean_entropy: moyenne de `gene_entropy`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiversityMetrics {
+    pub alive: usize,
+    pub mean_distance: f64,
+    pub unique_code_hashes: usize,
+    pub gene_entropy: BTreeMap<String, f64>,
+    pub mean_entropy: f64,
+}
+
+impl DiversityMetrics {
+    /// Événement d'audit (`generation` absent : population entière)
+    pub fn event(&self, generation: Option<u32>) -> AuditEvent {
+        AuditEvent::DiversityMetrics {
+            generation,
+            alive: self.alive,
+            mean_distance: self.mean_distance,
+            unique_code_hashes: self.unique_code_hashes,
+            mean_entropy: self.mean_entropy,
+            gene_entropy: self.gene_entropy.clone(),
+        }
+    }
+
+    /// Vrai si la population est trop homogène (il faut au moins deux génomes à comparer)
+    pub fn is_low(&self, config: &DiversityConfig) -> bool {
+        self.alive >= 2 && self.mean_distance < config.min_mean_distance
+    }
+}
+
+/// Mesure la diversité d'agents (ceux sans génome lisible ne comptent que pour les empreintes)
+pub fn measure(
+    agents: &[&AgentInfo],
+    genomes: &HashMap<String, GenomeConfig>,
+    weights: &DistanceWeights,
+) -> DiversityMetrics {
+    let hashes: HashSet<&str> = agents
+        .iter()
+        .map(|agent| agent.file_hashes.code_hash.as_str())
+        .collect();
+    let agent_genomes: Vec<&GenomeConfig> = agents
+        .iter()
+        .filter_map(|agent| genomes.get(&agent.name))
+        .collect();
+
+    let mut total = 0.0;
+    let mut pairs = 0usize;
+    for (i, a) in agent_genomes.iter().enumerate() {
+        for b in &agent_genomes[i + 1..] {
+            total += genome_distance(a, b, weights);
+            pairs += 1;
+        }
+    }
+
+    // Un fichier absent d'un génome compte comme inactif
+    let paths: BTreeSet<&str> = agent_genomes
+        .iter()
+        .flat_map(|genome| genome.files.iter().map(|file| file.path.as_str()))
+        .collect();
+    let gene_entropy: BTreeMap<String, f64> = paths
+        .into_iter()
+        .map(|path| {
+            let active = agent_genomes
+                .iter()
+                .filter(|genome| genome.files.iter().any(|f| f.path == path && f.active))
+                .count();
+            let entropy = binary_entropy(active as f64 / agent_genomes.len() as f64);
+            (path.to_string(), entropy)
+        })
+        .collect();
+    let mean_entropy = if gene_entropy.is_empty() {
+        0.0
+    } else {
+        gene_entropy.values().sum::<f64>() / gene_entropy.len() as f64
+    };
+
+    DiversityMetrics {
+        alive: agents.len(),
+        mean_distance: if pairs == 0 {
+            0.0
+        } else {
+            total / pairs as f64
+        },
+        unique_code_hashes: hashes.len(),
+        gene_entropy,
+        mean_entropy,
+    }
+}
+
+/// Diversité de chaque génération et de toute la population (clé `None`), agents vivants
+pub fn measure_population(
+    listing: &AgentsListing,
+    genomes: &HashMap<String, GenomeConfig>,
+    weights: &DistanceWeights,
+) -> BTreeMap<Option<u32>, DiversityMetrics> {
+    let living: Vec<&AgentInfo> = listing
+        .agents
+        .iter()
+        .filter(|agent| agent.state().is_alive())
+        .collect();
+    let mut by_generation: BTreeMap<u32, Vec<&AgentInfo>> = BTreeMap::new();
+    for agent in &living {
+        by_generation
+            .entry(agent.lineage.generation)
+            .or_default()
+            .push(agent);
+    }
+
+    let mut metrics: BTreeMap<Option<u32>, DiversityMetrics> = by_generation
+        .into_iter()
+        .map(|(generation, agents)| (Some(generation), measure(&agents, genomes, weights)))
+        .collect();
+    metrics.insert(None, measure(&living, genomes, weights));
+    metrics
+}
+
+/// Entropie (en bits) d'un gène actif avec la probabilité `p`
+fn binary_entropy(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    -(p * p.log2() + (1.0 - p) * (1.0 - p).log2())
+}
+
+/// État des interventions, conservé d'un tour à l'autre
+/// - metrics: dernières mesures journalisées (seuls les changements sont émis)
+/// - boosted: taux de mutation relevé
+/// - last_injection: tour du dernier ajout d'agents (immigrants ou archives)
+#[derive(Debug, Default)]
+pub struct DiversityState {
+    pub metrics: BTreeMap<Option<u32>, DiversityMetrics>,
+    pub boosted: bool,
+    pub last_injection: Option<u64>,
+}
+
+impl DiversityState {
+    /// Taux de mutation des clones et migrants
+    pub fn mutation_rate(&self, config: &DiversityConfig) -> f64 {
+        if self.boosted {
+            config.boosted_mutation_rate
+        } else {
+            config.mutation_rate
+        }
+    }
+
+    /// Vrai si un ajout d'agents est permis au tour `tick` (délai écoulé)
+    pub fn can_inject(&self, tick: u64, config: &DiversityConfig) -> bool {
+        self.last_injection
+            .is_none_or(|last| tick >= last + config.cooldown_ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::FileGene;
+
+    /// Génome d'un seul fichier, actif ou non
+    fn genome(active: bool) -> GenomeConfig {
+        GenomeConfig {
+            files: vec![FileGene {
+                path: "src/neural.rs".to_string(),
+                active,
+                functions: HashMap::new(),
+            }],
+        }
+    }
+
+    fn agents(names: &[&str]) -> Vec<AgentInfo> {
+        names.iter().map(|name| AgentInfo::for_test(name)).collect()
+    }
+
+    #[test]
+    fn converged_population_is_low_diversity() {
+        let agents = agents(&["a", "b", "c"]);
+        let refs: Vec<&AgentInfo> = agents.iter().collect();
+        let genomes: HashMap<String, GenomeConfig> = ["a", "b", "c"]
+            .iter()
+            .map(|name| (name.to_string(), genome(true)))
+            .collect();
+        let metrics = measure(&refs, &genomes, &DistanceWeights::default());
+        assert_eq!(metrics.alive, 3);
+        assert_eq!(metrics.mean_distance, 0.0);
+        assert_eq!(metrics.mean_entropy, 0.0);
+        assert!(metrics.is_low(&DiversityConfig::default()));
+    }
+
+    #[test]
+    fn evenly_split_gene_carries_one_bit() {
+        let agents = agents(&["a", "b"]);
+        let refs: Vec<&AgentInfo> = agents.iter().collect();
+        let genomes = HashMap::from([
+            ("a".to_string(), genome(true)),
+            ("b".to_string(), genome(false)),
+        ]);
+        let metrics = measure(&refs, &genomes, &DistanceWeights::default());
+        assert_eq!(metrics.gene_entropy["src/neural.rs"], 1.0);
+        assert!(metrics.mean_distance > 0.0);
+        assert!(!metrics.is_low(&DiversityConfig::default()));
+    }
+
+    #[test]
+    fn a_single_agent_is_never_low_diversity() {
+        let agents = agents(&["a"]);
+        let refs: Vec<&AgentInfo> = agents.iter().collect();
+        let metrics = measure(&refs, &HashMap::new(), &DistanceWeights::default());
+        assert!(!metrics.is_low(&DiversityConfig::default()));
+    }
+
+    #[test]
+    fn injections_wait_for_the_cooldown() {
+        let config = DiversityConfig::default();
+        let mut state = DiversityState::default();
+        assert!(state.can_inject(1, &config));
+        state.last_injection = Some(10);
+        assert!(!state.can_inject(10 + config.cooldown_ticks - 1, &config));
+        assert!(state.can_inject(10 + config.cooldown_ticks, &config));
+        state.boosted = true;
+        assert_eq!(state.mutation_rate(&config), config.boosted_mutation_rate);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::agent_selection::SelectionMode;
+use crate::diversity::DiversityConfig;
+use crate::ipc::hub::IpcConfig;
+use crate::island::{validate_islands, IslandConfig, MigrationConfig, DEFAULT_ISLAND};
+use crate::novelty::NoveltyConfig;
+use crate::pareto::ParetoConfig;
+use crate::species::SpeciationConfig;
+use crate::tasks::harness::TaskConfig;
+use crate::world::WorldConfig;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Configuration d'une expérience, sauvegardée dans experiment.ron
+/// - master_seed: graine maîtresse dont dérivent tous les flux aléatoires
+/// - nb_agents: taille de la population initiale
+/// - control_api_addr: adresse (localhost) de l'API HTTP de contrôle
+/// - population_cap: plafond d'agents vivants de l'île unique (sans îles déclarées) ;
+///   au-delà, les agents les moins bien classés sont éliminés à chaque tour
+/// - islands: sous-populations (une seule île `main` de nb_agents agents si vide)
+/// - migration: topologie et fréquence des migrations entre îles
+/// - speciation: seuil de compatibilité et poids de la distance entre génomes
+/// - diversity: seuil de convergence et interventions pour préserver la diversité
+/// - selection_mode: classement des migrants, des agents remplacés et des agents éliminés
+///   au-delà du plafond d'une île (aptitude, nouveauté...)
+/// - novelty: descripteurs de comportement et archive de la recherche de nouveauté
+/// - pareto: objectifs du classement multi-objectif (mode de sélection `pareto`)
+/// - tasks: suites de problèmes du harnais de tâches (aucune par défaut)
+/// - ipc: surveillance des heartbeats et demandes reçues sur le canal des agents
+/// - world: monde simulé où les agents se disputent les ressources (inactif par défaut)
+///
+/// Même graine + même template + même config = mêmes génomes et mêmes décisions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentConfig {
+    pub master_seed: u64,
+    pub nb_agents: usize,
+    #[serde(default = "default_control_api_addr")]
+    pub control_api_addr: String,
+    #[serde(default)]
+    pub population_cap: Option<usize>,
+    #[serde(default)]
+    pub islands: Vec<IslandConfig>,
+    #[serde(default)]
+    pub migration: MigrationConfig,
+    #[serde(default)]
+    pub speciation: SpeciationConfig,
+    #[serde(default)]
+    pub diversity: DiversityConfig,
+    #[serde(default)]
+    pub selection_mode: SelectionMode,
+    #[serde(default)]
+    pub novelty: NoveltyConfig,
+    #[serde(default)]
+    pub pareto: ParetoConfig,
+    #[serde(default)]
+    pub tasks: TaskConfig,
+    #[serde(default)]
+    pub ipc: IpcConfig,
+    #[serde(default)]
+    pub world: WorldConfig,
+}
+
+fn default_control_api_addr() -> String {
+    "127.0.0.1:9100".to_string()
+}
+
+impl Default for ExperimentConfig {
+    fn default() -> Self {
+        Self {
+            master_seed: rand::thread_rng().gen(),
+            nb_agents: 3,
+            control_api_addr: default_control_api_addr(),
+            population_cap: None,
+            islands: Vec::new(),
+            migration: MigrationConfig::default(),
+            speciation: SpeciationConfig::default(),
+            diversity: DiversityConfig::default(),
+            selection_mode: SelectionMode::default(),
+            novelty: NoveltyConfig::default(),
+            pareto: ParetoConfig::default(),
+            tasks: TaskConfig::default(),
+            ipc: IpcConfig::default(),
+            world: WorldConfig::default(),
+        }
+    }
+}
+
+impl ExperimentConfig {
+    /// Charge la config ; si elle n'existe pas, en crée une (graine tirée au hasard)
+    /// et l'écrit sur disque pour que l'expérience reste rejouable.
+    pub fn load_or_create(path: &Path) -> std::io::Result<Self> {
+        if path.exists() {
+            let content = fs::read_to_string(path)?;
+            let config: Self = ron::from_str(&content).map_err(std::io::Error::other)?;
+            validate_islands(&config.islands)
+                .and_then(|_| config.tasks.validate())
+                .and_then(|_| config.world.validate())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            return Ok(config);
+        }
+
+        let config = Self::default();
+        let content = ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default())
+            .map_err(std::io::Error::other)?;
+        fs::write(path, content)?;
+        println!(
+            "🎲 Nouvelle expérience (graine {}) : {}",
+            config.master_seed,
+            path.display()
+        );
+        Ok(config)
+    }
+
+    /// Îles de l'expérience (île unique, plafonnée par `population_cap`, si aucune n'est déclarée)
+    pub fn islands(&self) -> Vec<IslandConfig> {
+        if !self.islands.is_empty() {
+            return self.islands.clone();
+        }
+        vec![IslandConfig {
+            name: DEFAULT_ISLAND.to_string(),
+            nb_agents: self.nb_agents,
+            population_cap: self.population_cap,
+            selection: None,
+        }]
+    }
+}
@@ -1,26 +1,50 @@
+use crate::experiment_config::ExperimentConfig;
 use crate::genome::{FileGene, GenomeConfig};
+use crate::seeding::{stream_rng, SeedStream};
 use rand::Rng;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
 
+/// Recombinaison pour le `child_index`-ième enfant d'une génération,
+/// avec le flux aléatoire de cette génération (reproductible)
+pub fn recombine_in_generation(
+    parent1: &GenomeConfig,
+    parent2: &GenomeConfig,
+    experiment: &ExperimentConfig,
+    generation: u64,
+    child_index: u64,
+) -> GenomeConfig {
+    let mut rng = stream_rng(
+        experiment.master_seed,
+        SeedStream::Generation {
+            generation,
+            index: child_index,
+        },
+    );
+    recombine_genomes(parent1, parent2, &mut rng)
+}
+
 /// Effectue une recombinaison génétique entre deux génomes parents
-/// pour créer un nouveau génome enfant
-pub fn recombine_genomes(parent1: &GenomeConfig, parent2: &GenomeConfig) -> GenomeConfig {
-    let mut rng = rand::thread_rng();
+/// pour créer un nouveau génome enfant.
+/// Fichiers et fonctions sont parcourus triés : même `rng` = même enfant.
+pub fn recombine_genomes(
+    parent1: &GenomeConfig,
+    parent2: &GenomeConfig,
+    rng: &mut impl Rng,
+) -> GenomeConfig {
     let mut child_files = Vec::new();
 
     // Identifier tous les fichiers présents dans au moins un des parents
-    let mut all_paths: HashMap<String, bool> = HashMap::new();
-    for file in &parent1.files {
-        all_paths.insert(file.path.clone(), true);
-    }
-    for file in &parent2.files {
-        all_paths.insert(file.path.clone(), true);
-    }
+    let all_paths: BTreeSet<&String> = parent1
+        .files
+        .iter()
+        .chain(parent2.files.iter())
+        .map(|f| &f.path)
+        .collect();
 
     // Pour chaque fichier, hériter des caractéristiques d'un des parents ou faire un mélange
-    for path in all_paths.keys() {
+    for path in all_paths {
         let parent1_file = parent1.files.iter().find(|f| &f.path == path);
         let parent2_file = parent2.files.iter().find(|f| &f.path == path);
 
@@ -34,15 +58,14 @@ pub fn recombine_genomes(parent1: &GenomeConfig, parent2: &GenomeConfig) -> Geno
                 };
                 let mut functions = HashMap::new();
 
-                // Mélanger les fonctions des deux parents
-                let all_functions: HashMap<String, bool> = file1
+                // Mélanger les fonctions des deux parents (ordre trié pour rester reproductible)
+                let all_functions: BTreeSet<&String> = file1
                     .functions
-                    .iter()
-                    .chain(file2.functions.iter())
-                    .map(|(k, v)| (k.clone(), *v))
+                    .keys()
+                    .chain(file2.functions.keys())
                     .collect();
 
-                for (func_name, _) in &all_functions {
+                for func_name in all_functions {
                     let use_parent1 = rng.gen_bool(0.5);
                     let active = if use_parent1 {
                         file1.functions.get(func_name).copied().unwrap_or(false)
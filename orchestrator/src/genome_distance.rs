@@ -0,0 +1,169 @@
+use crate::genome::{FileGene, GenomeConfig};
+use serde::{Deserialize, Serialize};
+
+/// Poids des termes de la distance entre génomes (à la manière de NEAT)
+/// - file: fichiers présents dans les deux génomes mais activés différemment
+/// - function: fonctions présentes dans les deux gènes mais activées différemment
+/// - disjoint: fichiers ou fonctions présents dans un seul des deux génomes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistanceWeights {
+    pub file: f64,
+    pub function: f64,
+    pub disjoint: f64,
+}
+
+impl Default for DistanceWeights {
+    fn default() -> Self {
+        Self {
+            file: 1.0,
+            function: 0.5,
+            disjoint: 1.0,
+        }
+    }
+}
+
+/// Distance entre deux génomes : chaque terme est une distance de Hamming normalisée
+/// (0 : identiques), pondérée par `weights`.
+/// - activation des fichiers communs, rapportée au nombre de fichiers communs
+/// - activation des fonctions communes, rapportée au nombre de fonctions communes
+/// - gènes disjoints (fichiers et fonctions), rapportés au nombre de gènes du plus grand génome
+///
+/// Les gènes de `GenomeConfig` ne portent que leur activation : aucun paramètre
+/// numérique n'est comparé (un terme de paramètres viendra avec de tels gènes).
+pub fn genome_distance(a: &GenomeConfig, b: &GenomeConfig, weights: &DistanceWeights) -> f64 {
+    let mut shared_files = 0usize;
+    let mut file_mismatches = 0usize;
+    let mut shared_functions = 0usize;
+    let mut function_mismatches = 0usize;
+    let mut disjoint = 0usize;
+
+    for file in &a.files {
+        match find_gene(b, &file.path) {
+            Some(other) => {
+                shared_files += 1;
+                if file.active != other.active {
+                    file_mismatches += 1;
+                }
+                for (function, active) in &file.functions {
+                    match other.functions.get(function) {
+                        Some(other_active) => {
+                            shared_functions += 1;
+                            if active != other_active {
+                                function_mismatches += 1;
+                            }
+                        }
+                        None => disjoint += 1,
+                    }
+                }
+                disjoint += other
+                    .functions
+                    .keys()
+                    .filter(|function| !file.functions.contains_key(*function))
+                    .count();
+            }
+            None => disjoint += 1 + file.functions.len(),
+        }
+    }
+    disjoint += b
+        .files
+        .iter()
+        .filter(|file| find_gene(a, &file.path).is_none())
+        .map(|file| 1 + file.functions.len())
+        .sum::<usize>();
+
+    let genes = gene_count(a).max(gene_count(b));
+    weights.file * ratio(file_mismatches, shared_files)
+        + weights.function * ratio(function_mismatches, shared_functions)
+        + weights.disjoint * ratio(disjoint, genes)
+}
+
+fn find_gene<'a>(genome: &'a GenomeConfig, path: &str) -> Option<&'a FileGene> {
+    genome.files.iter().find(|file| file.path == path)
+}
+
+/// Nombre de gènes d'un génome (fichiers et fonctions)
+fn gene_count(genome: &GenomeConfig) -> usize {
+    genome
+        .files
+        .iter()
+        .map(|file| 1 + file.functions.len())
+        .sum()
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn gene(path: &str, active: bool, functions: &[(&str, bool)]) -> FileGene {
+        FileGene {
+            path: path.to_string(),
+            active,
+            functions: functions
+                .iter()
+                .map(|(name, active)| (name.to_string(), *active))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    fn genome(files: Vec<FileGene>) -> GenomeConfig {
+        GenomeConfig { files }
+    }
+
+    #[test]
+    fn identical_genomes_are_at_distance_zero() {
+        let a = genome(vec![
+            gene("src/a.rs", true, &[("run", true), ("step", false)]),
+            gene("src/b.rs", false, &[]),
+        ]);
+        assert_eq!(
+            genome_distance(&a, &a.clone(), &DistanceWeights::default()),
+            0.0
+        );
+    }
+
+    #[test]
+    fn activation_mismatches_are_normalized_and_weighted() {
+        let weights = DistanceWeights {
+            file: 2.0,
+            function: 4.0,
+            disjoint: 1.0,
+        };
+        let a = genome(vec![
+            gene("src/a.rs", true, &[("run", true), ("step", true)]),
+            gene("src/b.rs", true, &[]),
+        ]);
+        let b = genome(vec![
+            gene("src/a.rs", false, &[("run", true), ("step", false)]),
+            gene("src/b.rs", true, &[]),
+        ]);
+        // 1 fichier sur 2 et 1 fonction sur 2 activés différemment
+        assert_eq!(genome_distance(&a, &b, &weights), 2.0 * 0.5 + 4.0 * 0.5);
+    }
+
+    #[test]
+    fn disjoint_genes_use_the_disjoint_weight() {
+        let weights = DistanceWeights {
+            file: 1.0,
+            function: 1.0,
+            disjoint: 3.0,
+        };
+        let a = genome(vec![gene("src/a.rs", true, &[("run", true)])]);
+        let b = genome(vec![
+            gene("src/a.rs", true, &[("run", true)]),
+            gene("src/extra.rs", true, &[("new", true)]),
+        ]);
+        // Fichier et fonction présents dans b seulement : 2 gènes disjoints sur 4
+        let distance = genome_distance(&a, &b, &weights);
+        assert_eq!(distance, 3.0 * 0.5);
+        assert_eq!(distance, genome_distance(&b, &a, &weights));
+    }
+}
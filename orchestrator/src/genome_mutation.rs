@@ -0,0 +1,48 @@
+use crate::genome::GenomeConfig;
+use rand::Rng;
+
+/// Mute un génome : chaque fichier et chaque fonction change d'activation avec la
+/// probabilité `rate`. Comme pour le génome initial, `main.rs` reste actif ainsi
+/// qu'au moins un module de mutation. Fichiers et fonctions sont parcourus triés :
+/// même `rng` = même mutation. Retourne le nombre de gènes modifiés.
+pub fn mutate_genome(genome: &mut GenomeConfig, rate: f64, rng: &mut impl Rng) -> usize {
+    if rate <= 0.0 {
+        return 0;
+    }
+    let rate = rate.min(1.0);
+    let mut mutations = 0;
+
+    genome.files.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in genome.files.iter_mut() {
+        if !file.path.contains("main.rs") && rng.gen_bool(rate) {
+            file.active = !file.active;
+            mutations += 1;
+        }
+        let mut functions: Vec<&String> = file.functions.keys().collect();
+        functions.sort();
+        let flipped: Vec<String> = functions
+            .into_iter()
+            .filter(|_| rng.gen_bool(rate))
+            .cloned()
+            .collect();
+        for function in flipped {
+            if let Some(active) = file.functions.get_mut(&function) {
+                *active = !*active;
+                mutations += 1;
+            }
+        }
+    }
+
+    let is_mutation = |path: &str| path.contains("modules.rs") || path.contains("genome/mutate");
+    if genome.files.iter().any(|f| is_mutation(&f.path))
+        && !genome
+            .files
+            .iter()
+            .any(|f| is_mutation(&f.path) && f.active)
+    {
+        if let Some(file) = genome.files.iter_mut().find(|f| is_mutation(&f.path)) {
+            file.active = true;
+        }
+    }
+    mutations
+}
@@ -0,0 +1,323 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::ipc::hub::IpcHub;
+use crate::ipc::messages::OrchestratorMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// Limites et coûts des messages entre agents
+/// - max_body_bytes: taille maximale d'un message
+/// - max_messages_per_tick: envois (`send` ou `publish`) acceptés par agent et par tour
+/// - mailbox_capacity: messages en attente par agent (les plus anciens sont perdus au-delà)
+/// - message_cost: énergie payée par destinataire
+/// - cost_per_kb: énergie supplémentaire par destinataire et par Ko (entamé)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokerConfig {
+    pub max_body_bytes: usize,
+    pub max_messages_per_tick: u32,
+    pub mailbox_capacity: usize,
+    pub message_cost: i32,
+    pub cost_per_kb: i32,
+}
+
+impl Default for BrokerConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 1024,
+            max_messages_per_tick: 10,
+            mailbox_capacity: 50,
+            message_cost: 1,
+            cost_per_kb: 2,
+        }
+    }
+}
+
+/// Destinataires d'un message : un agent, ou les abonnés d'un sujet (hors émetteur)
+pub enum Recipients {
+    Agent(String),
+    Topic(String),
+}
+
+/// Message en attente dans une boîte aux lettres
+struct Envelope {
+    from: String,
+    topic: Option<String>,
+    body: String,
+}
+
+/// Broker des messages entre agents : boîtes aux lettres par agent et sujets.
+/// Les messages sont délivrés sur le canal IPC à la fin de chaque tour ;
+/// ceux d'un agent non connecté attendent dans sa boîte aux lettres.
+#[derive(Default)]
+pub struct Broker {
+    mailboxes: HashMap<String, VecDeque<Envelope>>,
+    subscriptions: BTreeMap<String, BTreeSet<String>>,
+    sent_this_tick: HashMap<String, u32>,
+}
+
+impl Broker {
+    /// Nouveau tour : remet à zéro les compteurs de débit
+    pub fn start_tick(&mut self) {
+        self.sent_this_tick.clear();
+    }
+
+    pub fn subscribe(&mut self, agent: &str, topic: &str) {
+        self.subscriptions
+            .entry(topic.to_string())
+            .or_default()
+            .insert(agent.to_string());
+    }
+
+    pub fn unsubscribe(&mut self, agent: &str, topic: &str) {
+        if let Some(subscribers) = self.subscriptions.get_mut(topic) {
+            subscribers.remove(agent);
+            if subscribers.is_empty() {
+                self.subscriptions.remove(topic);
+            }
+        }
+    }
+
+    /// Sujets auxquels un agent est abonné
+    pub fn topics(&self, agent: &str) -> Vec<String> {
+        self.subscriptions
+            .iter()
+            .filter(|(_, subscribers)| subscribers.contains(agent))
+            .map(|(topic, _)| topic.clone())
+            .collect()
+    }
+
+    /// Dépose un message d'un agent Running dans les boîtes aux lettres de ses
+    /// destinataires, après contrôle de la taille, du débit et de l'énergie (payée
+    /// par l'émetteur). Retourne le nombre de destinataires ; un refus est compté
+    /// dans les statistiques de l'émetteur.
+    pub fn submit(
+        &mut self,
+        listing: &mut AgentsListing,
+        from: &str,
+        recipients: Recipients,
+        body: String,
+        config: &BrokerConfig,
+    ) -> Result<usize, String> {
+        let result = self.check_and_charge(listing, from, &recipients, &body, config);
+        let Some(sender) = listing.agents.iter_mut().find(|a| a.name == from) else {
+            return result.map(|_| 0);
+        };
+        let names = match result {
+            Ok(names) => names,
+            Err(e) => {
+                sender.messages.rejected += 1;
+                return Err(e);
+            }
+        };
+        *self.sent_this_tick.entry(from.to_string()).or_default() += 1;
+        sender.messages.sent += names.len() as u64;
+        sender.messages.bytes_sent += (body.len() * names.len()) as u64;
+
+        let topic = match recipients {
+            Recipients::Topic(topic) => Some(topic),
+            Recipients::Agent(_) => None,
+        };
+        for name in &names {
+            let mailbox = self.mailboxes.entry(name.clone()).or_default();
+            if mailbox.len() >= config.mailbox_capacity {
+                mailbox.pop_front();
+                if let Some(recipient) = listing.agents.iter_mut().find(|a| &a.name == name) {
+                    recipient.messages.dropped += 1;
+                }
+            }
+            if config.mailbox_capacity > 0 {
+                mailbox.push_back(Envelope {
+                    from: from.to_string(),
+                    topic: topic.clone(),
+                    body: body.clone(),
+                });
+            }
+        }
+        Ok(names.len())
+    }
+
+    /// Contrôles d'un envoi ; en cas d'acceptation, l'énergie est prélevée et les
+    /// noms des destinataires retournés
+    fn check_and_charge(
+        &self,
+        listing: &mut AgentsListing,
+        from: &str,
+        recipients: &Recipients,
+        body: &str,
+        config: &BrokerConfig,
+    ) -> Result<Vec<String>, String> {
+        if body.len() > config.max_body_bytes {
+            return Err(format!(
+                "Message trop long ({} > {} octets)",
+                body.len(),
+                config.max_body_bytes
+            ));
+        }
+        let sent = self.sent_this_tick.get(from).copied().unwrap_or(0);
+        if sent >= config.max_messages_per_tick {
+            return Err(format!("Débit dépassé ({} messages ce tour)", sent));
+        }
+        let names: Vec<String> = match recipients {
+            Recipients::Agent(to) => {
+                if !listing
+                    .agents
+                    .iter()
+                    .any(|a| &a.name == to && a.state().is_alive())
+                {
+                    return Err(format!("Destinataire inconnu : {}", to));
+                }
+                vec![to.clone()]
+            }
+            Recipients::Topic(topic) => self
+                .subscriptions
+                .get(topic)
+                .map(|subscribers| {
+                    subscribers
+                        .iter()
+                        .filter(|name| name.as_str() != from)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let sender = listing
+            .agents
+            .iter_mut()
+            .find(|a| a.name == from && a.state() == AgentState::Running)
+            .ok_or_else(|| format!("Agent {} non Running", from))?;
+        let kb = body.len().div_ceil(1024) as i32;
+        let cost = (config.message_cost + config.cost_per_kb * kb) * names.len() as i32;
+        if sender.energy < cost {
+            return Err(format!(
+                "Énergie insuffisante ({} < {})",
+                sender.energy, cost
+            ));
+        }
+        sender.energy -= cost;
+        sender.messages.energy_spent += cost as i64;
+        Ok(names)
+    }
+
+    /// Délivre les messages en attente aux agents connectés, dans l'ordre d'arrivée
+    pub fn deliver(&mut self, listing: &mut AgentsListing, ipc: &IpcHub) {
+        for (name, mailbox) in self.mailboxes.iter_mut() {
+            let mut delivered = 0;
+            while let Some(envelope) = mailbox.front() {
+                let message = OrchestratorMessage::Message {
+                    from: envelope.from.clone(),
+                    topic: envelope.topic.clone(),
+                    body: envelope.body.clone(),
+                };
+                if !ipc.send(name, &message) {
+                    break;
+                }
+                mailbox.pop_front();
+                delivered += 1;
+            }
+            if let Some(agent) = listing.agents.iter_mut().find(|a| &a.name == name) {
+                agent.messages.received += delivered;
+            }
+        }
+        self.mailboxes.retain(|_, mailbox| !mailbox.is_empty());
+    }
+
+    /// Oublie boîtes aux lettres et abonnements des agents qui ne sont plus vivants
+    pub fn forget_dead(&mut self, listing: &AgentsListing) {
+        let alive = |name: &str| {
+            listing
+                .agents
+                .iter()
+                .any(|a| a.name == name && a.state().is_alive())
+        };
+        self.mailboxes.retain(|name, _| alive(name));
+        for subscribers in self.subscriptions.values_mut() {
+            subscribers.retain(|name| alive(name));
+        }
+        self.subscriptions
+            .retain(|_, subscribers| !subscribers.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bodies(broker: &Broker, agent: &str) -> Vec<String> {
+        broker.mailboxes[agent]
+            .iter()
+            .map(|envelope| envelope.body.clone())
+            .collect()
+    }
+
+    #[test]
+    fn full_mailbox_drops_the_oldest_message() {
+        let mut listing = AgentsListing::with_names(&["a", "b"]);
+        let mut broker = Broker::default();
+        let config = BrokerConfig {
+            mailbox_capacity: 2,
+            ..Default::default()
+        };
+        for body in ["1", "2", "3"] {
+            let to = Recipients::Agent("b".into());
+            broker
+                .submit(&mut listing, "a", to, body.into(), &config)
+                .unwrap();
+        }
+        assert_eq!(bodies(&broker, "b"), vec!["2", "3"]);
+        assert_eq!(listing.agents[1].messages.dropped, 1);
+        assert_eq!(listing.agents[0].messages.sent, 3);
+    }
+
+    #[test]
+    fn topic_reaches_every_subscriber_but_the_publisher() {
+        let mut listing = AgentsListing::with_names(&["a", "b", "c", "d"]);
+        let mut broker = Broker::default();
+        let config = BrokerConfig::default();
+        for agent in ["a", "b", "c"] {
+            broker.subscribe(agent, "signals");
+        }
+        let topic = Recipients::Topic("signals".into());
+        let reached = broker
+            .submit(&mut listing, "a", topic, "hello".into(), &config)
+            .unwrap();
+        assert_eq!(reached, 2);
+        assert_eq!(bodies(&broker, "b"), vec!["hello"]);
+        assert_eq!(bodies(&broker, "c"), vec!["hello"]);
+        assert!(!broker.mailboxes.contains_key("a"));
+        assert!(!broker.mailboxes.contains_key("d"));
+        // Coût payé par destinataire : (1 + 2 × 1 Ko entamé) × 2
+        assert_eq!(listing.agents[0].energy, 1000 - 6);
+    }
+
+    #[test]
+    fn sender_without_enough_energy_is_rejected() {
+        let mut listing = AgentsListing::with_names(&["a", "b"]);
+        listing.agents[0].energy = 2;
+        let mut broker = Broker::default();
+        let to = Recipients::Agent("b".into());
+        let result = broker.submit(&mut listing, "a", to, "x".into(), &BrokerConfig::default());
+        assert!(result.is_err());
+        assert_eq!(listing.agents[0].energy, 2);
+        assert_eq!(listing.agents[0].messages.rejected, 1);
+        assert!(broker.mailboxes.is_empty());
+    }
+
+    #[test]
+    fn rate_limit_resets_each_tick() {
+        let mut listing = AgentsListing::with_names(&["a", "b"]);
+        let mut broker = Broker::default();
+        let config = BrokerConfig {
+            max_messages_per_tick: 1,
+            ..Default::default()
+        };
+        let send = |broker: &mut Broker, listing: &mut AgentsListing| {
+            let to = Recipients::Agent("b".into());
+            broker.submit(listing, "a", to, "x".into(), &config)
+        };
+        assert!(send(&mut broker, &mut listing).is_ok());
+        assert!(send(&mut broker, &mut listing).is_err());
+        broker.start_tick();
+        assert!(send(&mut broker, &mut listing).is_ok());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::audit::audit_log::current_timestamp;
+use crate::ipc::broker::BrokerConfig;
+use crate::ipc::messages::{AgentMessage, OrchestratorMessage};
+use protocol::{AgentChannel, MessageStats};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Lignes de journal conservées en mémoire par canal
+const LOG_LINES: usize = 100;
+/// Délai d'écriture vers un agent : au-delà (agent qui ne lit plus sa socket),
+/// la connexion est coupée plutôt que de bloquer le tour
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Réaction à un agent bloqué (définie dans le protocole partagé, consignée par
+/// l'événement `heartbeat_lost`)
+pub use protocol::HungAction;
+
+/// Canal agent ↔ orchestrateur : une socket Unix par agent (`ipc/<agent>.sock`),
+/// dont le chemin est passé à l'agent (`--ipc <socket>`). La connexion est facultative :
+/// seuls les agents qui ont envoyé un message depuis leur lancement sont surveillés.
+///
+/// Configuration
+/// - heartbeat_timeout_secs: silence au-delà duquel un agent connecté est jugé bloqué
+/// - on_hung: relance ou mort de l'agent bloqué
+/// - reproduce_cost: énergie payée par l'agent pour une demande `reproduce`
+/// - energy_floor: une demande `need_energy` est servie jusqu'à ce niveau d'énergie
+/// - broker: limites et coûts des messages entre agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcConfig {
+    pub heartbeat_timeout_secs: u64,
+    pub on_hung: HungAction,
+    pub reproduce_cost: i32,
+    pub energy_floor: i32,
+    #[serde(default)]
+    pub broker: BrokerConfig,
+}
+
+impl Default for IpcConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_timeout_secs: 30,
+            on_hung: HungAction::Restart,
+            reproduce_cost: 500,
+            energy_floor: 200,
+            broker: BrokerConfig::default(),
+        }
+    }
+}
+
+/// État d'un canal, partagé avec ses threads d'écoute
+/// (`connection` : numéro de la connexion courante, les précédentes ne sont plus lues)
+#[derive(Default)]
+struct ChannelState {
+    connection: u64,
+    writer: Option<UnixStream>,
+    last_seen: Option<u64>,
+    metrics: BTreeMap<String, f64>,
+    logs: VecDeque<String>,
+}
+
+struct Channel {
+    path: PathBuf,
+    closed: AtomicBool,
+    state: Mutex<ChannelState>,
+}
+
+/// Demandes reçues des agents, traitées par le cycle de vie
+type Requests = Arc<Mutex<Vec<(String, AgentMessage)>>>;
+
+/// Canaux ouverts, par nom d'agent
+pub struct IpcHub {
+    dir: PathBuf,
+    channels: Mutex<HashMap<String, Arc<Channel>>>,
+    requests: Requests,
+}
+
+impl IpcHub {
+    /// Sockets créées dans `dir` (chemin absolu : les agents sont lancés par cargo)
+    pub fn new(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: fs::canonicalize(dir)?,
+            channels: Mutex::new(HashMap::new()),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        })
+    }
+
+    /// Ouvre (ou réinitialise avant une relance) le canal d'un agent et retourne le
+    /// chemin de sa socket. Les lignes `log` sont ajoutées à `log_dir/ipc.log`.
+    pub fn open(&self, name: &str, log_dir: &Path) -> io::Result<PathBuf> {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(channel) = channels.get(name) {
+            let mut state = channel.state.lock().unwrap();
+            if let Some(writer) = state.writer.take() {
+                let _ = writer.shutdown(Shutdown::Both);
+            }
+            state.connection += 1;
+            state.last_seen = None;
+            return Ok(channel.path.clone());
+        }
+
+        let path = self.dir.join(format!("{}.sock", name));
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        fs::create_dir_all(log_dir)?;
+        let channel = Arc::new(Channel {
+            path: path.clone(),
+            closed: AtomicBool::new(false),
+            state: Mutex::new(ChannelState::default()),
+        });
+        let accepted = Arc::clone(&channel);
+        let requests = Arc::clone(&self.requests);
+        let (name, log_path) = (name.to_string(), log_dir.join("ipc.log"));
+        channels.insert(name.clone(), channel);
+        std::thread::spawn(move || accept(listener, accepted, requests, name, log_path));
+        Ok(path)
+    }
+
+    /// Ferme le canal d'un agent (connexion coupée, socket supprimée)
+    pub fn close(&self, name: &str) {
+        let Some(channel) = self.channels.lock().unwrap().remove(name) else {
+            return;
+        };
+        channel.closed.store(true, Ordering::SeqCst);
+        // Réveille le thread bloqué sur `accept`
+        let _ = UnixStream::connect(&channel.path);
+        let _ = fs::remove_file(&channel.path);
+        let writer = channel.state.lock().unwrap().writer.take();
+        if let Some(writer) = writer {
+            let _ = writer.shutdown(Shutdown::Both);
+        }
+    }
+
+    /// Ferme les canaux des agents pour lesquels `keep` est faux
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) {
+        let names: Vec<String> = self.channels.lock().unwrap().keys().cloned().collect();
+        for name in names.iter().filter(|name| !keep(name)) {
+            self.close(name);
+        }
+    }
+
+    /// Envoie une commande à un agent connecté. Retourne `false` s'il ne l'est pas.
+    /// Une écriture en échec ou hors délai coupe la connexion : l'agent, devenu
+    /// silencieux, est ensuite traité comme bloqué.
+    pub fn send(&self, name: &str, message: &OrchestratorMessage) -> bool {
+        let Some(channel) = self.channels.lock().unwrap().get(name).cloned() else {
+            return false;
+        };
+        let mut state = channel.state.lock().unwrap();
+        let Some(writer) = state.writer.as_mut() else {
+            return false;
+        };
+        let Ok(mut line) = serde_json::to_string(message) else {
+            return false;
+        };
+        line.push('\n');
+        if writer.write_all(line.as_bytes()).is_err() {
+            if let Some(writer) = state.writer.take() {
+                let _ = writer.shutdown(Shutdown::Both);
+            }
+            return false;
+        }
+        true
+    }
+
+    /// Envoie une commande à tous les agents connectés
+    pub fn broadcast(&self, message: &OrchestratorMessage) {
+        let names: Vec<String> = self.channels.lock().unwrap().keys().cloned().collect();
+        for name in names {
+            self.send(&name, message);
+        }
+    }
+
+    /// Demandes reçues depuis le dernier appel, dans l'ordre d'arrivée
+    pub fn drain_requests(&self) -> Vec<(String, AgentMessage)> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+
+    /// Agents silencieux depuis plus de `timeout_secs` (et durée de leur silence)
+    pub fn silent_agents(&self, now: u64, timeout_secs: u64) -> Vec<(String, u64)> {
+        let channels = self.channels.lock().unwrap();
+        let mut silent: Vec<(String, u64)> = channels
+            .iter()
+            .filter_map(|(name, channel)| {
+                let last_seen = channel.state.lock().unwrap().last_seen?;
+                let silence = now.saturating_sub(last_seen);
+                (silence > timeout_secs).then(|| (name.clone(), silence))
+            })
+            .collect();
+        silent.sort();
+        silent
+    }
+
+    /// Vue du canal d'un agent (`None` s'il n'est pas ouvert)
+    pub fn report(&self, name: &str) -> Option<AgentChannel> {
+        let channel = self.channels.lock().unwrap().get(name).cloned()?;
+        let state = channel.state.lock().unwrap();
+        Some(AgentChannel {
+            connected: state.writer.is_some(),
+            last_seen: state.last_seen,
+            metrics: state.metrics.clone(),
+            logs: state.logs.iter().cloned().collect(),
+            topics: Vec::new(),
+            messages: MessageStats::default(),
+        })
+    }
+}
+
+/// Accepte les connexions de l'agent jusqu'à la fermeture du canal
+/// (une nouvelle connexion remplace la précédente)
+fn accept(
+    listener: UnixListener,
+    channel: Arc<Channel>,
+    requests: Requests,
+    name: String,
+    log_path: PathBuf,
+) {
+    for stream in listener.incoming() {
+        if channel.closed.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(writer) = stream.try_clone() else {
+            continue;
+        };
+        if writer.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+            continue;
+        }
+        let connection = {
+            let mut state = channel.state.lock().unwrap();
+            if let Some(previous) = state.writer.replace(writer) {
+                let _ = previous.shutdown(Shutdown::Both);
+            }
+            state.connection += 1;
+            state.connection
+        };
+        let (channel, requests) = (Arc::clone(&channel), Arc::clone(&requests));
+        let (name, log_path) = (name.clone(), log_path.clone());
+        std::thread::spawn(move || {
+            read_messages(stream, connection, &channel, &requests, &name, &log_path)
+        });
+    }
+}
+
+/// Lit les messages d'une connexion tant qu'elle reste la connexion courante ;
+/// les lignes invalides sont ignorées
+fn read_messages(
+    stream: UnixStream,
+    connection: u64,
+    channel: &Channel,
+    requests: &Requests,
+    name: &str,
+    log_path: &Path,
+) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let Ok(message) = serde_json::from_str::<AgentMessage>(&line) else {
+            continue;
+        };
+        let now = current_timestamp();
+        let mut state = channel.state.lock().unwrap();
+        if state.connection != connection {
+            return;
+        }
+        state.last_seen = Some(now);
+        match message {
+            AgentMessage::Heartbeat => {}
+            AgentMessage::Metrics { values } => state.metrics = values,
+            AgentMessage::Log { level, message } => {
+                let line = format!("{} [{}] {}", now, level, message);
+                if let Err(e) = append_line(log_path, &line) {
+                    eprintln!("⚠️ Journal IPC de {} : {}", name, e);
+                }
+                if state.logs.len() == LOG_LINES {
+                    state.logs.pop_front();
+                }
+                state.logs.push_back(line);
+            }
+            request => requests.lock().unwrap().push((name.to_string(), request)),
+        }
+    }
+    let mut state = channel.state.lock().unwrap();
+    if state.connection == connection {
+        state.writer = None;
+    }
+}
+
+fn append_line(path: &Path, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Dossier de test propre au processus
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ipc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn agent_that_stops_reading_does_not_block_send() {
+        let dir = test_dir("stalled");
+        let hub = IpcHub::new(&dir.join("ipc")).unwrap();
+        let socket = hub.open("agent", &dir.join("logs")).unwrap();
+        // Connexion jamais lue : le tampon du noyau finit par se remplir
+        let client = UnixStream::connect(&socket).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while hub
+            .report("agent")
+            .is_some_and(|channel| !channel.connected)
+        {
+            assert!(Instant::now() < deadline, "connexion jamais acceptée");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let message = OrchestratorMessage::GenomeAvailable {
+            path: PathBuf::from("x".repeat(4096)),
+        };
+        let start = Instant::now();
+        let mut sent = 0;
+        while hub.send("agent", &message) {
+            sent += 1;
+            assert!(start.elapsed() < Duration::from_secs(10), "envoi bloqué");
+        }
+        assert!(sent > 0);
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(!hub.report("agent").unwrap().connected);
+
+        drop(client);
+        hub.close("agent");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::world::{SeenAgent, SeenCell, WorldAction};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Messages d'un agent vers l'orchestrateur (JSON tagué `"type"`, une ligne par message)
+/// - heartbeat: signe de vie, attendu au moins toutes les `heartbeat_timeout_secs`
+/// - metrics: mesures auto-déclarées (remplacent les précédentes)
+/// - log: ligne de journal (`logs/agents/<agent>/ipc.log`)
+/// - reproduce: demande de clonage, payée en énergie par l'agent
+/// - need_energy: demande d'énergie
+/// - send / publish: message à un agent ou aux abonnés d'un sujet (broker, payé en énergie)
+/// - subscribe / unsubscribe: abonnement à un sujet
+/// - act: action dans le monde simulé (`{"type": "act", "action": "consume", "amount": 10}`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentMessage {
+    Heartbeat,
+    Metrics {
+        values: BTreeMap<String, f64>,
+    },
+    Log {
+        #[serde(default = "default_level")]
+        level: String,
+        message: String,
+    },
+    Reproduce,
+    NeedEnergy {
+        amount: i32,
+    },
+    Send {
+        to: String,
+        body: String,
+    },
+    Publish {
+        topic: String,
+        body: String,
+    },
+    Subscribe {
+        topic: String,
+    },
+    Unsubscribe {
+        topic: String,
+    },
+    Act(WorldAction),
+}
+
+fn default_level() -> String {
+    "info".to_string()
+}
+
+/// Commandes de l'orchestrateur vers un agent (même format)
+/// - pause: l'agent va être mis en pause (processus arrêté juste après)
+/// - shutdown: l'orchestrateur s'arrête ou tue l'agent (SIGTERM ensuite)
+/// - genome_available: le code a été resynchronisé avec un nouveau génome (relance au tour suivant)
+/// - message: message d'un autre agent (`topic` absent pour un message direct)
+/// - perception: position, énergie et voisinage de l'agent dans le monde simulé (chaque tour)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrchestratorMessage {
+    Pause,
+    Shutdown,
+    GenomeAvailable {
+        path: PathBuf,
+    },
+    Message {
+        from: String,
+        topic: Option<String>,
+        body: String,
+    },
+    Perception {
+        x: u32,
+        y: u32,
+        energy: i32,
+        cells: Vec<SeenCell>,
+        agents: Vec<SeenAgent>,
+    },
+}
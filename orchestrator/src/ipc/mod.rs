@@ -0,0 +1,3 @@
+pub mod broker;
+pub mod hub;
+pub mod messages;
@@ -0,0 +1,119 @@
+use crate::agent_selection::SelectionCriteria;
+use serde::{Deserialize, Serialize};
+
+/// Île unique utilisée quand l'expérience n'en déclare aucune
+pub const DEFAULT_ISLAND: &str = "main";
+
+/// Sous-population isolée, avec son propre dossier `agents/<name>/`
+/// - name: nom de l'île (lettres, chiffres, `-` et `_`)
+/// - nb_agents: nombre d'agents créés à l'initialisation
+/// - population_cap: nombre maximal d'agents vivants (aucune limite si absent) ;
+///   au-delà (naissances par `reproduce`), les moins bien classés sont éliminés
+/// - selection: critères propres à l'île (sinon ceux, modifiables, de l'orchestrateur)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IslandConfig {
+    pub name: String,
+    pub nb_agents: usize,
+    #[serde(default)]
+    pub population_cap: Option<usize>,
+    #[serde(default)]
+    pub selection: Option<SelectionCriteria>,
+}
+
+/// Île sans agent initial ni critères propres, pour les tests unitaires
+#[cfg(test)]
+impl IslandConfig {
+    pub fn for_test(name: &str, population_cap: Option<usize>) -> Self {
+        IslandConfig {
+            name: name.to_string(),
+            nb_agents: 0,
+            population_cap,
+            selection: None,
+        }
+    }
+}
+
+impl IslandConfig {
+    /// Vrai si l'île ne peut plus accueillir d'agent vivant
+    pub fn is_full(&self, living: usize) -> bool {
+        self.population_cap.is_some_and(|cap| living >= cap)
+    }
+}
+
+/// Îles recevant les migrants d'une île
+/// - ring: l'île suivante (la dernière envoie vers la première)
+/// - fully_connected: toutes les autres îles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationTopology {
+    #[default]
+    Ring,
+    FullyConnected,
+}
+
+impl MigrationTopology {
+    /// Indices des îles de destination de l'île `index` parmi `count`
+    pub fn destinations(self, index: usize, count: usize) -> Vec<usize> {
+        if count < 2 {
+            return Vec::new();
+        }
+        match self {
+            MigrationTopology::Ring => vec![(index + 1) % count],
+            MigrationTopology::FullyConnected => (0..count).filter(|&i| i != index).collect(),
+        }
+    }
+}
+
+/// Migration périodique des meilleurs agents d'une île vers ses voisines
+/// - interval_ticks: tours du cycle de vie entre deux migrations (0 : jamais)
+/// - migrants: nombre d'agents envoyés par île et par destination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationConfig {
+    pub topology: MigrationTopology,
+    pub interval_ticks: u64,
+    pub migrants: usize,
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        Self {
+            topology: MigrationTopology::Ring,
+            interval_ticks: 60, // 5 minutes à un tour toutes les 5 s
+            migrants: 1,
+        }
+    }
+}
+
+impl MigrationConfig {
+    /// Vrai si une migration a lieu à la fin du tour `tick` (compté à partir de 1)
+    pub fn is_due(&self, tick: u64) -> bool {
+        self.interval_ticks > 0 && self.migrants > 0 && tick.is_multiple_of(self.interval_ticks)
+    }
+}
+
+/// Un nom d'île sert de nom de dossier : pas de séparateur ni de `..`
+pub fn is_valid_island_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Vérifie les îles déclarées : noms valides et uniques, population initiale sous le plafond
+pub fn validate_islands(islands: &[IslandConfig]) -> Result<(), String> {
+    for (index, island) in islands.iter().enumerate() {
+        if !is_valid_island_name(&island.name) {
+            return Err(format!("Nom d'île invalide: {:?}", island.name));
+        }
+        if islands[..index].iter().any(|i| i.name == island.name) {
+            return Err(format!("Île déclarée deux fois: {}", island.name));
+        }
+        if let Some(cap) = island.population_cap.filter(|&cap| island.nb_agents > cap) {
+            return Err(format!(
+                "Île {} : {} agents initiaux pour un plafond de {}",
+                island.name, island.nb_agents, cap
+            ));
+        }
+    }
+    Ok(())
+}
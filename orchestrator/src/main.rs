@@ -6,92 +6,229 @@ pub const TEMPLATE_DIR: &str = "agent_template"; // Sous-dossier template des ag
 mod agent_code_analysis;
 mod agent_config;
 mod agent_file_hashes;
+mod agent_lineage;
 mod agent_listing;
 mod agent_needs;
 mod agent_sanitizer;
 mod agent_selection;
+mod agent_state;
 mod agent_structural_code;
 mod agent_validator; // Ajout du module validator à la place de updater
+mod audit;
 mod cargo_commands;
+mod cli;
+mod control;
+mod diversity;
+mod experiment_config;
+#[allow(dead_code)] // Recombinaison pas encore branchée dans le cycle de vie
+mod genetic_recombination;
 mod genome;
+mod genome_distance;
+mod genome_mutation;
 mod genome_sync; // Au lieu de genetic_recombination
+mod ipc;
+mod island;
 mod manage_agents_commands;
 mod monitoring;
 mod notifications;
+mod novelty;
+mod pareto;
 mod pipelines;
 mod project_paths; // Import du module qui gère les chemins
 mod scan_agents; // Ajouter le nouveau module
+mod seeding;
+mod shutdown;
+mod snapshot;
+mod species;
 mod sys_commands;
+mod tasks;
+mod world;
 
+use crate::audit::audit_log::AuditLog;
+use crate::audit::replay;
+use crate::cli::CliCommand;
+use crate::experiment_config::ExperimentConfig;
+use crate::ipc::hub::IpcHub;
+use crate::monitoring::metrics::Metrics;
+use crate::pipelines::initiate::listing::load_agents_listing;
 use crate::pipelines::{initiate_project, LifeManager};
 use crate::project_paths::ProjectPaths;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
-};
-use std::thread;
-use std::time::Duration;
+use crate::shutdown::ShutdownToken;
+use std::sync::{mpsc, Arc, Mutex};
 
 fn main() {
     // Initialisation des chemins via ProjectPaths
     let paths = ProjectPaths::new();
 
-    // Définir le nombre d'agents à créer
-    let nb_agents = 3; // Exemple : 3 agents
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let resume = match cli::parse_args(&args) {
+        Ok(CliCommand::Run) => false,
+        Ok(CliCommand::Resume) => true,
+        Ok(CliCommand::Replay { until }) => {
+            match replay::replay_audit_log(&paths.audit_log_path(), until) {
+                Ok(state) => replay::print_population_state(&state),
+                Err(e) => eprintln!("Erreur lors de la relecture du journal d'audit : {}", e),
+            }
+            return;
+        }
+        Ok(CliCommand::Snapshot { label }) => {
+            if let Some(addr) = running_orchestrator(&paths) {
+                eprintln!(
+                    "Un orchestrateur tourne (API sur {}) : le listing sur disque peut changer \
+                     pendant l'archivage. Demandez le snapshot à l'API (POST /snapshot).",
+                    addr
+                );
+                return;
+            }
+            let result = load_agents_listing(paths.agent_listing_path()).and_then(|listing| {
+                snapshot::population_snapshot::create_snapshot(&paths, &listing, label.as_deref())
+            });
+            if let Err(e) = result {
+                eprintln!("Erreur lors de la création du snapshot : {}", e);
+            }
+            return;
+        }
+        Ok(CliCommand::Restore { archive, workspace }) => {
+            let target = workspace
+                .map(|dir| ProjectPaths::with_workspace(&dir))
+                .unwrap_or(paths);
+            if let Some(addr) = running_orchestrator(&target) {
+                eprintln!(
+                    "Un orchestrateur tourne (API sur {}) : arrêtez-le avant de restaurer \
+                     dans son workspace.",
+                    addr
+                );
+                return;
+            }
+            let result = AuditLog::open(&target.audit_log_path()).and_then(|audit| {
+                snapshot::population_snapshot::restore_snapshot(&archive, &target, &audit)
+            });
+            if let Err(e) = result {
+                eprintln!("Erreur lors de la restauration du snapshot : {}", e);
+            }
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}\n{}", e, cli::usage());
+            return;
+        }
+    };
+
+    // Journal d'audit partagé entre l'initialisation et le cycle de vie
+    let audit = match AuditLog::open(&paths.audit_log_path()) {
+        Ok(audit) => Arc::new(audit),
+        Err(e) => {
+            eprintln!("Erreur lors de l'ouverture du journal d'audit : {}", e);
+            return;
+        }
+    };
 
-    // ÉTAPE 1: Initialisation complète du projet
-    if let Err(e) = initiate_project(&paths, nb_agents) {
-        eprintln!("Erreur lors de l'initialisation du projet : {}", e);
-        return;
+    // Configuration de l'expérience (graine maîtresse, nombre d'agents)
+    let experiment = match ExperimentConfig::load_or_create(&paths.experiment_config_path()) {
+        Ok(experiment) => experiment,
+        Err(e) => {
+            eprintln!(
+                "Erreur lors du chargement de la configuration d'expérience : {}",
+                e
+            );
+            return;
+        }
+    };
+
+    // ÉTAPE 1: Initialisation complète du projet (sauf reprise d'une population existante)
+    if !resume {
+        if let Err(e) = initiate_project(&paths, &experiment, &audit) {
+            eprintln!("Erreur lors de l'initialisation du projet : {}", e);
+            return;
+        }
     }
 
     // Utilisation du helper pour obtenir le chemin du listing
     let listing_path = paths.agent_listing_path();
-    let listing_bytes = match std::fs::read(&listing_path) {
-        Ok(bytes) => bytes,
+    let listing = match load_agents_listing(&listing_path) {
+        Ok(listing) => listing,
         Err(e) => {
             eprintln!("Erreur lors du chargement du listing : {}", e);
             return;
         }
     };
-    let listing: crate::agent_listing::AgentsListing =
-        match bincode::deserialize(&listing_bytes) {
-            Ok(listing) => listing,
-            Err(e) => {
-                eprintln!("Erreur de désérialisation du listing : {}", e);
-                return;
-            }
-        };
 
+    // Canaux IPC des agents (une socket par agent)
+    let ipc = match IpcHub::new(&paths.ipc_dir()) {
+        Ok(ipc) => ipc,
+        Err(e) => {
+            eprintln!("Erreur lors de la création des canaux IPC : {}", e);
+            return;
+        }
+    };
+
+    // Le handler Ctrl+C ne fait qu'annuler le jeton : le cycle de vie termine son tour,
+    // arrête les agents et sauvegarde le listing lui-même (pas de verrou pris ici)
     let listing = Arc::new(Mutex::new(listing));
-    let running = Arc::new(AtomicBool::new(true));
+    let shutdown = ShutdownToken::new();
     {
-        let running = running.clone();
-        let listing_path = listing_path.clone();
-        let listing = listing.clone();
+        let shutdown = shutdown.clone();
         ctrlc::set_handler(move || {
-            println!("\n🛑 Arrêt demandé (Ctrl+C). Sauvegarde de l'état des agents...");
-            // Sauvegarde du listing (sécurisé)
-            if let Ok(listing) = listing.lock() {
-                if let Ok(bytes) = bincode::serialize(&*listing) {
-                    let _ = std::fs::write(&listing_path, bytes);
-                }
-            }
-            running.store(false, Ordering::SeqCst);
+            println!("\n🛑 Arrêt demandé (Ctrl+C). Arrêt des agents et sauvegarde de l'état...");
+            shutdown.cancel();
         })
         .expect("Erreur lors de l'installation du handler Ctrl+C");
     }
 
-    // ÉTAPE 2: Lancement du pipeline de gestion
-    let life_manager = LifeManager::new(listing_path);
-
-    // Boucle principale avec gestion d'arrêt propre
-    while running.load(Ordering::SeqCst) {
-        if let Ok(mut listing) = listing.lock() {
-            life_manager.manage_agents_lifecycle(&mut *listing, &paths);
+    // API HTTP de contrôle : les commandes sont exécutées par le cycle de vie
+    let (control_tx, control_rx) = mpsc::channel();
+    let metrics = Arc::new(Metrics::default());
+    let api_addr = experiment.control_api_addr.clone();
+    // Nouveaux jetons à chaque démarrage : sans eux, l'API refuse toutes les requêtes
+    match control::access::generate_access_tokens(&paths.access_tokens_path()) {
+        Ok(tokens) => {
+            println!(
+                "🔑 Jetons d'accès écrits dans {}",
+                paths.access_tokens_path().display()
+            );
+            let api = control::http_api::spawn_http_api(
+                &api_addr,
+                control_tx,
+                metrics.clone(),
+                tokens,
+                audit.clone(),
+                shutdown.clone(),
+            );
+            if let Err(e) = api {
+                eprintln!("⚠️ API de contrôle indisponible ({}) : {}", api_addr, e);
+            }
         }
-        thread::sleep(Duration::from_secs(1));
+        Err(e) => eprintln!(
+            "⚠️ API de contrôle désactivée, jetons d'accès impossibles à écrire : {}",
+            e
+        ),
     }
 
+    // ÉTAPE 2: Lancement du pipeline de gestion (retourne après l'arrêt propre)
+    let life_manager = LifeManager::new(
+        listing_path,
+        audit,
+        metrics,
+        shutdown,
+        experiment,
+        control_rx,
+        ipc,
+    );
+    life_manager.manage_agents_lifecycle(&listing, &paths);
+
     println!("✅ Arrêt propre terminé.");
 }
+
+/// Adresse de l'API de contrôle du workspace si un orchestrateur y répond
+/// (les commandes hors ligne ne doivent pas modifier une population en cours)
+fn running_orchestrator(paths: &ProjectPaths) -> Option<String> {
+    let config_path = paths.experiment_config_path();
+    let addr = if config_path.exists() {
+        ExperimentConfig::load_or_create(&config_path)
+            .map(|experiment| experiment.control_api_addr)
+            .unwrap_or_default()
+    } else {
+        ExperimentConfig::default().control_api_addr
+    };
+    control::http_api::is_serving(&addr).then_some(addr)
+}
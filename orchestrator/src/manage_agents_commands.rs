@@ -1,56 +1,126 @@
+use crate::cargo_commands::BuildOutcome;
 use crate::{cargo_commands, sys_commands};
+use std::fs::{self, File, OpenOptions};
 use std::path::Path;
-use std::process::Child;
+use std::process::{Child, Stdio};
+use std::time::{Duration, Instant};
 
-pub fn run_agent(agent_path: &Path) -> std::io::Result<Child> {
+/// Lance un agent en lui transmettant sa graine (`--seed`) pour un comportement reproductible
+/// et, s'il est ouvert, le chemin de la socket de son canal IPC (`--ipc`).
+/// Ses sorties sont ajoutées à `log_dir/stdout.log` et `log_dir/stderr.log`.
+pub fn run_agent(
+    agent_path: &Path,
+    seed: u64,
+    log_dir: &Path,
+    ipc_socket: Option<&Path>,
+) -> std::io::Result<Child> {
     let manifest_path = format!("{}/Cargo.toml", agent_path.display());
-    cargo_commands::run(&manifest_path)
+    fs::create_dir_all(log_dir)?;
+    let stdout = append_log(&log_dir.join("stdout.log"))?;
+    let stderr = append_log(&log_dir.join("stderr.log"))?;
+    let mut args = vec!["--seed".to_string(), seed.to_string()];
+    if let Some(socket) = ipc_socket {
+        args.extend(["--ipc".to_string(), socket.display().to_string()]);
+    }
+    cargo_commands::run(
+        &manifest_path,
+        &args,
+        Stdio::from(stdout),
+        Stdio::from(stderr),
+    )
 }
 
-pub fn kill_agent(is_running: &mut bool, child: &mut Child) -> std::io::Result<()> {
-    let result = sys_commands::kill_process(child);
-    if result.is_ok() {
-        *is_running = false;
-    }
-    result
+/// Lance un agent en mode tâches (`--seed <graine> --tasks`) pour le harnais :
+/// stdin et stdout sont reliés à l'orchestrateur, stderr est ajouté à `log_dir/stderr.log`.
+pub fn run_agent_tasks(agent_path: &Path, seed: u64, log_dir: &Path) -> std::io::Result<Child> {
+    let manifest_path = format!("{}/Cargo.toml", agent_path.display());
+    fs::create_dir_all(log_dir)?;
+    let stderr = append_log(&log_dir.join("stderr.log"))?;
+    cargo_commands::run_piped(
+        &manifest_path,
+        &[
+            "--seed".to_string(),
+            seed.to_string(),
+            "--tasks".to_string(),
+        ],
+        Stdio::from(stderr),
+    )
+}
+
+fn append_log(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+pub fn kill_agent(child: &mut Child) -> std::io::Result<()> {
+    sys_commands::kill_process(child)
+}
+
+/// Résultat d'une tentative de (re)lancement d'agent
+pub enum LaunchOutcome {
+    Launched(Child),
+    BuildFailed,
+    BuildTimedOut,
 }
 
+/// Recompile (dans la limite de `build_timeout`) et relance un agent.
 pub fn reload_agent(
     agent_path: &Path,
-    is_running: &mut bool,
+    seed: u64,
     old_process: Option<&mut Child>,
-) -> std::io::Result<Option<Child>> {
+    build_timeout: Duration,
+    log_dir: &Path,
+    ipc_socket: Option<&Path>,
+) -> std::io::Result<LaunchOutcome> {
     // Kill l'ancien si existe
     if let Some(child) = old_process {
         let _ = child.kill();
-        *is_running = false;
     }
 
     // Build
     let manifest_path = format!("{}/Cargo.toml", agent_path.display());
-    if cargo_commands::build(&manifest_path)?.status.success() {
+    match cargo_commands::build(&manifest_path, build_timeout)? {
         // Run
-        match cargo_commands::run(&manifest_path) {
-            Ok(child) => {
-                *is_running = true;
-                Ok(Some(child))
-            }
-            Err(e) => {
-                *is_running = false;
-                Err(e)
-            }
+        BuildOutcome::Success => {
+            run_agent(agent_path, seed, log_dir, ipc_socket).map(LaunchOutcome::Launched)
         }
-    } else {
-        *is_running = false;
-        Ok(None)
+        BuildOutcome::Failed => Ok(LaunchOutcome::BuildFailed),
+        BuildOutcome::TimedOut => Ok(LaunchOutcome::BuildTimedOut),
     }
 }
 
-pub fn run_all_agents(agent_paths: &[std::path::PathBuf]) -> Vec<std::io::Result<Child>> {
-    agent_paths.iter().map(|p| run_agent(p)).collect()
+/// Vérifie si un agent a crashé en vérifiant son état système
+/// Retourne `Some(code)` si le processus est terminé, `None` s'il tourne encore
+pub fn check_agent_exit(child: &mut Child) -> Option<i32> {
+    sys_commands::check_process_status(child).unwrap_or(Some(-1))
 }
 
-/// Vérifie si un agent a crashé en vérifiant son état système
-pub fn check_agent_crashed(is_running: bool, child: &mut Child) -> bool {
-    is_running && sys_commands::check_process_status(child).map_or(true, |status| status.is_some())
+/// Attend jusqu'à `grace` que les processus se terminent d'eux-mêmes.
+/// Retourne ceux qui tournent encore.
+pub fn wait_for_exit(mut children: Vec<(String, Child)>, grace: Duration) -> Vec<(String, Child)> {
+    let deadline = Instant::now() + grace;
+    while !children.is_empty() && Instant::now() < deadline {
+        children.retain_mut(|(_, child)| matches!(child.try_wait(), Ok(None)));
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    children
+}
+
+/// Arrête proprement un ensemble d'agents :
+/// SIGTERM à tous, attente jusqu'à `grace`, puis SIGKILL pour les récalcitrants.
+/// Retourne les noms des agents qui ont dû être tués de force.
+pub fn terminate_agents(children: Vec<(String, Child)>, grace: Duration) -> Vec<String> {
+    for (name, child) in &children {
+        if let Err(e) = sys_commands::terminate_process(child) {
+            eprintln!("⚠️ SIGTERM impossible pour {} : {}", name, e);
+        }
+    }
+
+    wait_for_exit(children, grace)
+        .into_iter()
+        .map(|(name, mut child)| {
+            let _ = sys_commands::kill_process(&mut child);
+            let _ = child.wait();
+            name
+        })
+        .collect()
 }
@@ -0,0 +1,251 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::monitoring::resource_monitor::{ResourceMonitor, ResourceUsage};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Métriques agrégées du cycle de vie, exposées au format texte Prometheus (`/metrics`).
+/// Les compteurs sont incrémentés au fil du tour, les jauges de population
+/// recalculées à la fin de chaque tour.
+#[derive(Default)]
+pub struct Metrics {
+    builds_started: AtomicU64,
+    builds_failed: AtomicU64,
+    builds_timed_out: AtomicU64,
+    ticks: AtomicU64,
+    transitions: Mutex<HashMap<AgentState, u64>>,
+    population: Mutex<PopulationGauges>,
+    resources: ResourceMonitor,
+}
+
+/// Jauges calculées à la fin d'un tour
+#[derive(Default)]
+struct PopulationGauges {
+    states: HashMap<AgentState, usize>,
+    alive: usize,
+    energy: Option<Stats>,
+    fitness_by_generation: BTreeMap<u32, Stats>,
+    resources: ResourceUsage,
+    tick_duration: Duration,
+}
+
+/// Moyenne / min / max d'une série de valeurs
+#[derive(Clone, Copy)]
+struct Stats {
+    mean: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Stats {
+    fn of(values: impl Iterator<Item = f64>) -> Option<Self> {
+        let (count, sum, min, max) = values.fold(
+            (0usize, 0.0, f64::INFINITY, f64::NEG_INFINITY),
+            |(count, sum, min, max), v| (count + 1, sum + v, min.min(v), max.max(v)),
+        );
+        (count > 0).then(|| Stats {
+            mean: sum / count as f64,
+            min,
+            max,
+        })
+    }
+}
+
+impl Metrics {
+    /// Surveillance des processus des agents, partagée par la sélection et les jauges
+    pub fn resources(&self) -> &ResourceMonitor {
+        &self.resources
+    }
+
+    pub fn build_started(&self) {
+        self.builds_started.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn build_failed(&self) {
+        self.builds_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn build_timed_out(&self) {
+        self.builds_timed_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Compte une transition d'état (crashs, morts, quarantaines...)
+    pub fn transition(&self, to: AgentState) {
+        *self.transitions.lock().unwrap().entry(to).or_default() += 1;
+    }
+
+    /// Recalcule les jauges de population à partir du listing
+    pub fn observe_population(&self, listing: &AgentsListing, resources: ResourceUsage) {
+        let mut states = HashMap::new();
+        for agent in &listing.agents {
+            *states.entry(agent.state()).or_default() += 1;
+        }
+        let alive: Vec<_> = listing
+            .agents
+            .iter()
+            .filter(|a| a.state().is_alive())
+            .collect();
+
+        let mut fitness_by_generation: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+        for agent in &alive {
+            fitness_by_generation
+                .entry(agent.lineage.generation)
+                .or_default()
+                .push(agent.fitness());
+        }
+
+        let mut population = self.population.lock().unwrap();
+        population.states = states;
+        population.alive = alive.len();
+        population.energy = Stats::of(alive.iter().map(|a| a.energy as f64));
+        population.fitness_by_generation = fitness_by_generation
+            .into_iter()
+            .filter_map(|(generation, values)| {
+                Stats::of(values.into_iter()).map(|s| (generation, s))
+            })
+            .collect();
+        population.resources = resources;
+    }
+
+    /// Enregistre la durée du dernier tour
+    pub fn observe_tick(&self, duration: Duration) {
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+        self.population.lock().unwrap().tick_duration = duration;
+    }
+
+    /// Rendu au format d'exposition texte Prometheus
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let counter = |out: &mut String, name: &str, help: &str, value: &AtomicU64| {
+            header(out, name, help, "counter");
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        };
+
+        counter(
+            &mut out,
+            "evolve_builds_started_total",
+            "Compilations d'agents lancées",
+            &self.builds_started,
+        );
+        counter(
+            &mut out,
+            "evolve_builds_failed_total",
+            "Compilations d'agents échouées",
+            &self.builds_failed,
+        );
+        counter(
+            &mut out,
+            "evolve_builds_timed_out_total",
+            "Compilations d'agents interrompues (délai dépassé)",
+            &self.builds_timed_out,
+        );
+        counter(
+            &mut out,
+            "evolve_lifecycle_ticks_total",
+            "Tours du cycle de vie exécutés",
+            &self.ticks,
+        );
+
+        let transitions = self.transitions.lock().unwrap();
+        header(
+            &mut out,
+            "evolve_state_transitions_total",
+            "Transitions d'état par état d'arrivée",
+            "counter",
+        );
+        for state in AgentState::ALL {
+            let count = transitions.get(&state).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "evolve_state_transitions_total{{to=\"{state}\"}} {count}"
+            );
+        }
+        drop(transitions);
+
+        let population = self.population.lock().unwrap();
+        header(
+            &mut out,
+            "evolve_agents",
+            "Nombre d'agents par état",
+            "gauge",
+        );
+        for state in AgentState::ALL {
+            let count = population.states.get(&state).copied().unwrap_or(0);
+            let _ = writeln!(out, "evolve_agents{{state=\"{state}\"}} {count}");
+        }
+        gauge(
+            &mut out,
+            "evolve_agents_alive",
+            "Agents vivants (ni en quarantaine, ni morts, ni archivés)",
+            population.alive as f64,
+        );
+
+        if let Some(energy) = population.energy {
+            header(
+                &mut out,
+                "evolve_agent_energy",
+                "Énergie des agents vivants",
+                "gauge",
+            );
+            write_stats(&mut out, "evolve_agent_energy", "", energy);
+        }
+
+        header(
+            &mut out,
+            "evolve_generation_fitness",
+            "Aptitude des agents vivants par génération",
+            "gauge",
+        );
+        for (generation, fitness) in &population.fitness_by_generation {
+            let labels = format!("generation=\"{generation}\",");
+            write_stats(&mut out, "evolve_generation_fitness", &labels, *fitness);
+        }
+
+        gauge(
+            &mut out,
+            "evolve_agent_processes",
+            "Processus d'agents surveillés",
+            population.resources.processes as f64,
+        );
+        gauge(
+            &mut out,
+            "evolve_agent_memory_megabytes",
+            "Mémoire totale des processus d'agents (Mo)",
+            population.resources.memory_mb as f64,
+        );
+        gauge(
+            &mut out,
+            "evolve_agent_cpu_percent",
+            "CPU total des processus d'agents (%)",
+            population.resources.cpu_percent,
+        );
+        gauge(
+            &mut out,
+            "evolve_lifecycle_tick_duration_seconds",
+            "Durée du dernier tour du cycle de vie",
+            population.tick_duration.as_secs_f64(),
+        );
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: f64) {
+    header(out, name, help, "gauge");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+/// Écrit une série mean/min/max ; `labels` se termine par une virgule s'il n'est pas vide
+fn write_stats(out: &mut String, name: &str, labels: &str, stats: Stats) {
+    for (stat, value) in [("mean", stats.mean), ("min", stats.min), ("max", stats.max)] {
+        let _ = writeln!(out, "{name}{{{labels}stat=\"{stat}\"}} {value}");
+    }
+}
@@ -1,17 +1,125 @@
+use std::collections::HashMap;
 use std::process::Child;
-use sysinfo::{ProcessExt, System, SystemExt};
-
-/// Surveillance pure des ressources système, sans dépendance à la config
-/// Retourne true si le process dépasse une des limites données
-pub fn check_resource_usage(process: &Child, memory_limit_mb: u64, cpu_limit_percent: u8) -> bool {
-    let sys = System::new_all();
-    let pid = sysinfo::Pid::from(process.id() as usize);
-
-    if let Some(sys_process) = sys.process(pid) {
-        let memory_mb = sys_process.memory() / 1024 / 1024;
-        let cpu_percent = sys_process.cpu_usage() as u8;
-        memory_mb > memory_limit_mb || cpu_percent > cpu_limit_percent
-    } else {
-        false
+use std::sync::Mutex;
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+/// Consommation cumulée d'un ensemble de processus
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResourceUsage {
+    pub processes: usize,
+    pub memory_mb: u64,
+    pub cpu_percent: f64,
+}
+
+impl ResourceUsage {
+    /// Somme de plusieurs mesures
+    pub fn total<'a>(usages: impl Iterator<Item = &'a ResourceUsage>) -> Self {
+        usages.fold(ResourceUsage::default(), |mut acc, u| {
+            acc.processes += u.processes;
+            acc.memory_mb += u.memory_mb;
+            acc.cpu_percent += u.cpu_percent;
+            acc
+        })
+    }
+}
+
+/// Surveillance des processus des agents, conservée d'un tour à l'autre.
+/// sysinfo calcule le CPU d'un processus entre deux rafraîchissements : un `System`
+/// neuf le mesure toujours à 0. Seuls les processus suivis sont rafraîchis.
+#[derive(Default)]
+pub struct ResourceMonitor {
+    state: Mutex<MonitorState>,
+}
+
+#[derive(Default)]
+struct MonitorState {
+    system: System,
+    /// Dernière mesure de chaque processus suivi, par pid
+    last: HashMap<u32, ResourceUsage>,
+}
+
+impl ResourceMonitor {
+    /// Rafraîchit les processus nommés et retourne leur mémoire/CPU depuis la mesure
+    /// précédente (ceux déjà terminés sont ignorés et oubliés)
+    pub fn sample<'a>(
+        &self,
+        processes: impl Iterator<Item = (&'a String, &'a Child)>,
+    ) -> Vec<(String, ResourceUsage)> {
+        let mut state = self.state.lock().unwrap();
+        let mut last = HashMap::new();
+        let usages = processes
+            .filter_map(|(name, process)| {
+                let pid = Pid::from_u32(process.id());
+                if !state.system.refresh_process(pid) {
+                    return None;
+                }
+                let p = state.system.process(pid)?;
+                let usage = ResourceUsage {
+                    processes: 1,
+                    memory_mb: p.memory() / 1024 / 1024,
+                    cpu_percent: p.cpu_usage() as f64,
+                };
+                last.insert(process.id(), usage);
+                Some((name.clone(), usage))
+            })
+            .collect();
+        state.last = last;
+        usages
+    }
+
+    /// Retourne true si la dernière mesure du process dépasse une des limites données
+    /// (un process pas encore mesuré n'en dépasse aucune)
+    pub fn exceeds_limits(
+        &self,
+        process: &Child,
+        memory_limit_mb: u64,
+        cpu_limit_percent: u8,
+    ) -> bool {
+        let state = self.state.lock().unwrap();
+        state.last.get(&process.id()).is_some_and(|usage| {
+            usage.memory_mb > memory_limit_mb || usage.cpu_percent > cpu_limit_percent as f64
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn a_busy_process_is_measured_after_two_samples() {
+        let mut child = Command::new("sh")
+            .args(["-c", "while :; do :; done"])
+            .spawn()
+            .unwrap();
+        let name = "busy".to_string();
+        let monitor = ResourceMonitor::default();
+
+        // sysinfo attend que le process ait déjà consommé du CPU à la première mesure
+        thread::sleep(Duration::from_millis(200));
+        monitor.sample([(&name, &child)].into_iter());
+        thread::sleep(Duration::from_millis(500));
+        let usages = monitor.sample([(&name, &child)].into_iter());
+        let over_limit = monitor.exceeds_limits(&child, u64::MAX, 0);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(usages.len(), 1);
+        let (sampled, usage) = &usages[0];
+        assert_eq!(sampled, "busy");
+        assert_eq!(usage.processes, 1);
+        assert!(
+            usage.cpu_percent > 0.0,
+            "CPU mesuré : {}",
+            usage.cpu_percent
+        );
+        assert!(over_limit);
+
+        // Terminé : le process n'est plus mesuré
+        assert!(monitor.sample([(&name, &child)].into_iter()).is_empty());
+        assert!(!monitor.exceeds_limits(&child, u64::MAX, 0));
     }
 }
@@ -0,0 +1,285 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::audit::audit_log::AuditEvent;
+use crate::control::agent_detail;
+use crate::genome::GenomeConfig;
+use crate::project_paths::ProjectPaths;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Descripteur de comportement : caractéristiques nommées (une caractéristique absente vaut 0)
+pub type BehaviourDescriptor = BTreeMap<String, f64>;
+
+/// Lignes de stdout.log prises en compte pour décrire le comportement
+const STDOUT_TRACE_LINES: usize = 200;
+
+/// Origine du descripteur de comportement d'un agent
+/// - active_modules: fichiers et fonctions actifs de son génome
+/// - stdout_trace: dernières lignes de sa sortie standard (volume et premiers mots)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviourSource {
+    #[default]
+    ActiveModules,
+    StdoutTrace,
+}
+
+/// Recherche de nouveauté
+/// - behaviour: origine des descripteurs de comportement
+/// - k: nombre de plus proches voisins (population vivante et archive)
+/// - archive_threshold: nouveauté au-delà de laquelle un comportement est archivé
+/// - archive_max: taille maximale de l'archive (les plus anciens sont oubliés)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoveltyConfig {
+    pub behaviour: BehaviourSource,
+    pub k: usize,
+    pub archive_threshold: f64,
+    pub archive_max: usize,
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        Self {
+            behaviour: BehaviourSource::ActiveModules,
+            k: 15,
+            archive_threshold: 1.0,
+            archive_max: 500,
+        }
+    }
+}
+
+/// Comportement conservé dans l'archive de nouveauté (sauvegardée avec le listing)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedBehaviour {
+    pub agent: String,
+    pub generation: u32,
+    pub novelty: f64,
+    pub descriptor: BehaviourDescriptor,
+}
+
+/// Descripteur de comportement d'un agent (aucun sans génome ou sans sortie)
+pub fn describe(
+    agent: &AgentInfo,
+    genome: Option<&GenomeConfig>,
+    source: BehaviourSource,
+    paths: &ProjectPaths,
+) -> Option<BehaviourDescriptor> {
+    match source {
+        BehaviourSource::ActiveModules => genome.map(active_modules),
+        BehaviourSource::StdoutTrace => {
+            let lines = agent_detail::agent_logs(paths, &agent.name, STDOUT_TRACE_LINES).stdout;
+            (!lines.is_empty()).then(|| stdout_trace(&lines))
+        }
+    }
+}
+
+/// Un axe par fichier et par fonction du génome (1 si actif)
+fn active_modules(genome: &GenomeConfig) -> BehaviourDescriptor {
+    let mut descriptor = BehaviourDescriptor::new();
+    for file in &genome.files {
+        descriptor.insert(file.path.clone(), flag(file.active));
+        for (function, active) in &file.functions {
+            descriptor.insert(format!("{}::{}", file.path, function), flag(*active));
+        }
+    }
+    descriptor
+}
+
+/// Volume de sortie (log2 du nombre de lignes) et part des lignes commençant par chaque mot
+fn stdout_trace(lines: &[String]) -> BehaviourDescriptor {
+    let mut descriptor = BehaviourDescriptor::new();
+    descriptor.insert("lines".into(), (1.0 + lines.len() as f64).log2());
+    let share = 1.0 / lines.len() as f64;
+    for line in lines {
+        let word = line.split_whitespace().next().unwrap_or("");
+        *descriptor.entry(format!("word:{}", word)).or_default() += share;
+    }
+    descriptor
+}
+
+fn flag(active: bool) -> f64 {
+    if active {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Distance euclidienne entre deux descripteurs
+pub fn behaviour_distance(a: &BehaviourDescriptor, b: &BehaviourDescriptor) -> f64 {
+    let only_b = b
+        .iter()
+        .filter(|(key, _)| !a.contains_key(*key))
+        .map(|(_, value)| value * value);
+    a.iter()
+        .map(|(key, value)| {
+            let diff = value - b.get(key).copied().unwrap_or(0.0);
+            diff * diff
+        })
+        .chain(only_b)
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Nouveauté : distance moyenne aux `k` plus proches voisins (0 sans voisin)
+pub fn novelty_score<'a>(
+    descriptor: &BehaviourDescriptor,
+    neighbours: impl Iterator<Item = &'a BehaviourDescriptor>,
+    k: usize,
+) -> f64 {
+    let mut distances: Vec<f64> = neighbours
+        .map(|other| behaviour_distance(descriptor, other))
+        .collect();
+    distances.sort_by(f64::total_cmp);
+    distances.truncate(k.max(1));
+    if distances.is_empty() {
+        0.0
+    } else {
+        distances.iter().sum::<f64>() / distances.len() as f64
+    }
+}
+
+/// Calcule la nouveauté de chaque agent vivant face aux autres vivants et à l'archive,
+/// puis archive les comportements assez nouveaux (une fois par agent).
+/// Les scores sont conservés dans le listing (`AgentInfo::novelty`) ;
+/// retourne les événements d'archivage.
+pub fn update_novelty(
+    listing: &mut AgentsListing,
+    genomes: &HashMap<String, GenomeConfig>,
+    config: &NoveltyConfig,
+    paths: &ProjectPaths,
+) -> Vec<AuditEvent> {
+    let descriptors: HashMap<String, BehaviourDescriptor> = listing
+        .agents
+        .iter()
+        .filter(|agent| agent.state().is_alive())
+        .filter_map(|agent| {
+            let descriptor = describe(agent, genomes.get(&agent.name), config.behaviour, paths)?;
+            Some((agent.name.clone(), descriptor))
+        })
+        .collect();
+
+    // Un agent déjà archivé n'est pas comparé à son propre comportement
+    let archive = &listing.novelty_archive;
+    let scores: HashMap<&str, f64> = descriptors
+        .iter()
+        .map(|(name, descriptor)| {
+            let living = descriptors
+                .iter()
+                .filter(|(other, _)| *other != name)
+                .map(|(_, other)| other);
+            let archived = archive
+                .iter()
+                .filter(|entry| &entry.agent != name)
+                .map(|entry| &entry.descriptor);
+            let score = novelty_score(descriptor, living.chain(archived), config.k);
+            (name.as_str(), score)
+        })
+        .collect();
+
+    let mut candidates = Vec::new();
+    for agent in listing.agents.iter_mut() {
+        if !agent.state().is_alive() {
+            continue;
+        }
+        agent.novelty = scores.get(agent.name.as_str()).copied();
+        let Some(novelty) = agent.novelty else {
+            continue;
+        };
+        let archived = archive.iter().any(|entry| entry.agent == agent.name);
+        if novelty > config.archive_threshold && !archived {
+            candidates.push(ArchivedBehaviour {
+                agent: agent.name.clone(),
+                generation: agent.lineage.generation,
+                novelty,
+                descriptor: descriptors[&agent.name].clone(),
+            });
+        }
+    }
+
+    let mut events = Vec::new();
+    for entry in candidates {
+        let archive = &mut listing.novelty_archive;
+        archive.push(entry.clone());
+        let overflow = archive.len().saturating_sub(config.archive_max);
+        archive.drain(..overflow);
+        events.push(AuditEvent::NoveltyArchived {
+            agent: entry.agent,
+            novelty: entry.novelty,
+            archive_size: archive.len(),
+        });
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(values: &[(&str, f64)]) -> BehaviourDescriptor {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn no_neighbour_gives_zero_novelty() {
+        let agent = descriptor(&[("x", 1.0)]);
+        assert_eq!(novelty_score(&agent, std::iter::empty(), 15), 0.0);
+    }
+
+    #[test]
+    fn k_larger_than_neighbours_averages_all_of_them() {
+        let agent = descriptor(&[("x", 0.0)]);
+        let neighbours = [descriptor(&[("x", 1.0)]), descriptor(&[("x", 3.0)])];
+        assert_eq!(novelty_score(&agent, neighbours.iter(), 15), 2.0);
+        // k = 1 : seul le plus proche compte
+        assert_eq!(novelty_score(&agent, neighbours.iter(), 1), 1.0);
+    }
+
+    #[test]
+    fn identical_descriptors_are_not_novel() {
+        let agent = descriptor(&[("x", 1.0), ("y", 2.0)]);
+        let neighbours = [agent.clone(), agent.clone()];
+        assert_eq!(behaviour_distance(&agent, &neighbours[0]), 0.0);
+        assert_eq!(novelty_score(&agent, neighbours.iter(), 2), 0.0);
+    }
+
+    #[test]
+    fn missing_axes_count_as_zero() {
+        let a = descriptor(&[("x", 3.0)]);
+        let b = descriptor(&[("y", 4.0)]);
+        assert_eq!(behaviour_distance(&a, &b), 5.0);
+        assert_eq!(behaviour_distance(&b, &a), 5.0);
+    }
+
+    #[test]
+    fn novel_behaviours_enter_an_empty_archive_once() {
+        let mut listing = AgentsListing::for_test();
+        let mut genomes = HashMap::new();
+        for (name, active) in [("a", true), ("b", false)] {
+            listing.agents.push(AgentInfo::for_test(name));
+            let genome = GenomeConfig {
+                files: vec![crate::genome::FileGene {
+                    path: "src/a.rs".to_string(),
+                    active,
+                    functions: HashMap::new(),
+                }],
+            };
+            genomes.insert(name.to_string(), genome);
+        }
+        let config = NoveltyConfig {
+            archive_threshold: 0.5,
+            ..NoveltyConfig::default()
+        };
+        let paths = ProjectPaths::new();
+
+        let events = update_novelty(&mut listing, &genomes, &config, &paths);
+        assert_eq!(events.len(), 2);
+        assert_eq!(listing.agents[0].novelty, Some(1.0));
+        assert_eq!(listing.novelty_archive.len(), 2);
+
+        // Déjà archivés : pas de second archivage
+        assert!(update_novelty(&mut listing, &genomes, &config, &paths).is_empty());
+    }
+}
@@ -0,0 +1,374 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::agent_state::AgentState;
+use protocol::{ParetoFront, ParetoMember};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Objectifs de la sélection multi-objectif
+/// - fitness, energy, novelty, task_score: à maximiser
+/// - memory_use: dernière mémoire mesurée (Mo), à minimiser
+/// - code_size: taille des sources Rust (octets), à minimiser
+/// - unwraps: appels `unwrap()` dans le code (pénalité du mode par score), à minimiser
+/// - crash_rate: crashs par heure de vie (au moins une heure comptée), à minimiser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    Fitness,
+    Energy,
+    Novelty,
+    TaskScore,
+    MemoryUse,
+    CodeSize,
+    Unwraps,
+    CrashRate,
+}
+
+impl Objective {
+    /// Nom de l'objectif dans les fronts exportés
+    pub fn name(self) -> &'static str {
+        match self {
+            Objective::Fitness => "fitness",
+            Objective::Energy => "energy",
+            Objective::Novelty => "novelty",
+            Objective::TaskScore => "task_score",
+            Objective::MemoryUse => "memory_use",
+            Objective::CodeSize => "code_size",
+            Objective::Unwraps => "unwraps",
+            Objective::CrashRate => "crash_rate",
+        }
+    }
+
+    fn maximize(self) -> bool {
+        matches!(
+            self,
+            Objective::Fitness | Objective::Energy | Objective::Novelty | Objective::TaskScore
+        )
+    }
+}
+
+/// Sélection multi-objectif (NSGA-II, mode de sélection `pareto`)
+/// - objectives: objectifs comparés par le tri non dominé
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParetoConfig {
+    pub objectives: Vec<Objective>,
+}
+
+impl Default for ParetoConfig {
+    fn default() -> Self {
+        Self {
+            objectives: vec![
+                Objective::Fitness,
+                Objective::MemoryUse,
+                Objective::CodeSize,
+                Objective::Unwraps,
+                Objective::CrashRate,
+            ],
+        }
+    }
+}
+
+/// Rang d'un agent : front de Pareto (1 : non dominé) et distance d'encombrement
+/// dans ce front (infinie aux extrémités)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParetoRank {
+    pub front: u32,
+    pub crowding: f64,
+}
+
+impl ParetoRank {
+    /// Valeur de classement : le front d'abord, l'encombrement départage (bonus dans [0, 0.5])
+    pub fn selection_value(&self) -> f64 {
+        let spread = if self.crowding.is_infinite() {
+            1.0
+        } else {
+            self.crowding / (1.0 + self.crowding)
+        };
+        -(self.front as f64) + 0.5 * spread
+    }
+}
+
+/// Classe les agents vivants (rang conservé dans `AgentInfo::pareto`) et retourne
+/// le front de Pareto de chaque génération (tri non dominé au sein de la génération).
+/// `memory_mb` : dernière mesure de mémoire de chaque agent en cours d'exécution.
+pub fn rank_population(
+    listing: &mut AgentsListing,
+    config: &ParetoConfig,
+    memory_mb: &HashMap<String, u64>,
+    now: u64,
+) -> BTreeMap<u32, ParetoFront> {
+    let objectives = &config.objectives;
+    let living: Vec<usize> = (0..listing.agents.len())
+        .filter(|&i| listing.agents[i].state().is_alive())
+        .collect();
+    let values: Vec<Vec<f64>> = living
+        .iter()
+        .map(|&i| {
+            let agent = &listing.agents[i];
+            let memory = memory_mb.get(&agent.name).copied().unwrap_or(0);
+            objectives
+                .iter()
+                .map(|objective| objective_value(listing, agent, *objective, memory, now))
+                .collect()
+        })
+        .collect();
+    let costs: Vec<Vec<f64>> = values.iter().map(|v| to_costs(v, objectives)).collect();
+
+    for agent in listing.agents.iter_mut() {
+        agent.pareto = None;
+    }
+    for (front_index, front) in non_dominated_sort(&costs).iter().enumerate() {
+        for (member, crowding) in front.iter().zip(crowding_distances(front, &costs)) {
+            listing.agents[living[*member]].pareto = Some(ParetoRank {
+                front: front_index as u32 + 1,
+                crowding,
+            });
+        }
+    }
+
+    let mut by_generation: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    for (member, &i) in living.iter().enumerate() {
+        let generation = listing.agents[i].lineage.generation;
+        by_generation.entry(generation).or_default().push(member);
+    }
+    by_generation
+        .into_iter()
+        .map(|(generation, members)| {
+            let generation_costs: Vec<Vec<f64>> =
+                members.iter().map(|&m| costs[m].clone()).collect();
+            let first = non_dominated_sort(&generation_costs)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let mut front: Vec<ParetoMember> = first
+                .into_iter()
+                .map(|local| {
+                    let member = members[local];
+                    ParetoMember {
+                        agent: listing.agents[living[member]].name.clone(),
+                        objectives: objectives
+                            .iter()
+                            .zip(&values[member])
+                            .map(|(objective, value)| (objective.name().to_string(), *value))
+                            .collect(),
+                    }
+                })
+                .collect();
+            front.sort_by(|a, b| a.agent.cmp(&b.agent));
+            (
+                generation,
+                ParetoFront {
+                    generation,
+                    members: front,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Valeur brute d'un objectif pour un agent
+fn objective_value(
+    listing: &AgentsListing,
+    agent: &AgentInfo,
+    objective: Objective,
+    memory_mb: u64,
+    now: u64,
+) -> f64 {
+    match objective {
+        Objective::Fitness => listing.shared_fitness(agent),
+        Objective::Energy => agent.energy as f64,
+        Objective::Novelty => agent.novelty.unwrap_or(0.0),
+        Objective::TaskScore => agent.task_score.unwrap_or(0.0),
+        Objective::MemoryUse => memory_mb as f64,
+        Objective::CodeSize => source_size(&agent.path) as f64,
+        Objective::Unwraps => agent
+            .file_metrics
+            .values()
+            .map(|metrics| metrics.unwrap_count as f64)
+            .sum(),
+        Objective::CrashRate => {
+            let crashes = agent
+                .state_history()
+                .iter()
+                .filter(|transition| transition.to == AgentState::Crashed)
+                .count();
+            let born = agent
+                .state_history()
+                .first()
+                .map_or(now, |transition| transition.timestamp);
+            let hours = (now.saturating_sub(born) as f64 / 3600.0).max(1.0);
+            crashes as f64 / hours
+        }
+    }
+}
+
+/// Valeurs à minimiser (les objectifs à maximiser sont inversés)
+fn to_costs(values: &[f64], objectives: &[Objective]) -> Vec<f64> {
+    values
+        .iter()
+        .zip(objectives)
+        .map(|(value, objective)| if objective.maximize() { -value } else { *value })
+        .collect()
+}
+
+/// Vrai si `a` est au moins aussi bon que `b` sur chaque objectif et meilleur sur l'un d'eux
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// Tri non dominé rapide : indices de chaque front, du meilleur au moins bon
+fn non_dominated_sort(costs: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); costs.len()];
+    let mut domination_count = vec![0usize; costs.len()];
+    let mut fronts: Vec<Vec<usize>> = vec![Vec::new()];
+    for p in 0..costs.len() {
+        for q in 0..costs.len() {
+            if dominates(&costs[p], &costs[q]) {
+                dominated[p].push(q);
+            } else if dominates(&costs[q], &costs[p]) {
+                domination_count[p] += 1;
+            }
+        }
+        if domination_count[p] == 0 {
+            fronts[0].push(p);
+        }
+    }
+
+    let mut current = 0;
+    while !fronts[current].is_empty() {
+        let mut next = Vec::new();
+        for &p in &fronts[current] {
+            for &q in &dominated[p] {
+                domination_count[q] -= 1;
+                if domination_count[q] == 0 {
+                    next.push(q);
+                }
+            }
+        }
+        fronts.push(next);
+        current += 1;
+    }
+    fronts.pop();
+    fronts
+}
+
+/// Distance d'encombrement de chaque membre d'un front (dans l'ordre du front) :
+/// somme, par objectif, de l'écart normalisé entre ses deux voisins
+fn crowding_distances(front: &[usize], costs: &[Vec<f64>]) -> Vec<f64> {
+    let mut distances = vec![0.0; front.len()];
+    if front.len() <= 2 {
+        return vec![f64::INFINITY; front.len()];
+    }
+    let columns = (0..costs[front[0]].len()).map(|objective| {
+        front
+            .iter()
+            .map(|&member| costs[member][objective])
+            .collect::<Vec<f64>>()
+    });
+    for column in columns {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|a, b| column[*a].total_cmp(&column[*b]));
+        let last = order.len() - 1;
+        let (min, max) = (column[order[0]], column[order[last]]);
+        distances[order[0]] = f64::INFINITY;
+        distances[order[last]] = f64::INFINITY;
+        if max > min {
+            for w in 1..last {
+                distances[order[w]] += (column[order[w + 1]] - column[order[w - 1]]) / (max - min);
+            }
+        }
+    }
+    distances
+}
+
+/// Taille (octets) des sources Rust d'un dossier, hors `target/`
+fn source_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                if entry.file_name() == "target" {
+                    0
+                } else {
+                    source_size(&path)
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dominated_points_fall_into_later_fronts() {
+        // Coûts à minimiser : (0,0) domine (1,1) qui domine (2,2)
+        let costs = vec![vec![2.0, 2.0], vec![0.0, 0.0], vec![1.0, 1.0]];
+        assert!(dominates(&costs[1], &costs[2]));
+        assert!(!dominates(&costs[2], &costs[1]));
+        assert_eq!(non_dominated_sort(&costs), vec![vec![1], vec![2], vec![0]]);
+    }
+
+    #[test]
+    fn trade_offs_and_equal_points_share_the_first_front() {
+        let costs = vec![
+            vec![0.0, 3.0],
+            vec![3.0, 0.0],
+            vec![1.0, 1.0],
+            vec![1.0, 1.0],
+        ];
+        // Des points égaux ne se dominent pas
+        assert!(!dominates(&costs[2], &costs[3]));
+        let fronts = non_dominated_sort(&costs);
+        assert_eq!(fronts.len(), 1);
+        let mut first = fronts[0].clone();
+        first.sort();
+        assert_eq!(first, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn extremes_of_a_front_have_infinite_crowding() {
+        let costs = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 2.0],
+            vec![3.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+        let front = vec![0, 1, 2, 3];
+        let crowding = crowding_distances(&front, &costs);
+        assert!(crowding[0].is_infinite());
+        assert!(crowding[3].is_infinite());
+        assert!(crowding[1].is_finite() && crowding[1] > 0.0);
+        assert!(crowding[2].is_finite() && crowding[2] > 0.0);
+    }
+
+    #[test]
+    fn small_fronts_are_entirely_at_infinite_crowding() {
+        let costs = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        let crowding = crowding_distances(&[0, 1], &costs);
+        assert!(crowding.iter().all(|distance| distance.is_infinite()));
+    }
+
+    #[test]
+    fn better_front_always_ranks_above_crowding() {
+        let first = ParetoRank {
+            front: 1,
+            crowding: 0.0,
+        };
+        let second = ParetoRank {
+            front: 2,
+            crowding: f64::INFINITY,
+        };
+        assert!(first.selection_value() > second.selection_value());
+    }
+}
@@ -1,27 +1,29 @@
 use crate::agent_file_hashes::AgentFileHashes;
+use crate::agent_lineage::AgentLineage;
 use crate::agent_listing::AgentInfo;
 use crate::agent_sanitizer;
 use crate::agent_validator;
+use crate::audit::audit_log::current_timestamp;
 use crate::scan_agents::AgentScanResult;
 use std::path::PathBuf;
 
+/// Vérifications du code d'un nouvel agent, consignées par l'événement d'audit `Validity`.
+/// Elles ne changent pas son état : le premier tour du cycle de vie met en quarantaine
+/// un agent non sûr et tue un agent qui ne compile pas.
+pub struct CodeChecks {
+    pub is_safe: bool,
+    pub is_valid: bool,
+}
+
 pub fn build_agent_info(
     agent_id: String,
     short_uuid: String,
     agent_dir: PathBuf,
+    island: String,
     scan_result: &AgentScanResult,
-) -> AgentInfo {
-    AgentInfo {
-        id: agent_id,
-        name: short_uuid,
-        path: agent_dir.clone(),
-        active: true,
-        code_hash: scan_result.code_hash.clone(),
-        file_hashes: AgentFileHashes {
-            code_hash: scan_result.code_hash.clone(),
-            file_hashes: scan_result.file_hashes.clone(),
-        },
-        file_metrics: scan_result.file_metrics.clone(),
+    lineage: AgentLineage,
+) -> (AgentInfo, CodeChecks) {
+    let checks = CodeChecks {
         is_safe: agent_sanitizer::is_code_safe(
             &scan_result
                 .files
@@ -31,13 +33,14 @@ pub fn build_agent_info(
             &agent_dir.to_string_lossy(),
         ),
         is_valid: agent_validator::is_code_valid(&agent_dir),
-        is_running: false,
-        last_modified: std::time::SystemTime::now()
-            .duration_since(std::time::SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs(),
-        energy: 1000,
-        last_crash: None,
-        crash_count: 0,
-    }
+    };
+    let mut agent = AgentInfo::new(agent_id, short_uuid, agent_dir, island, lineage);
+    agent.code_hash = scan_result.code_hash.clone();
+    agent.file_hashes = AgentFileHashes {
+        code_hash: scan_result.code_hash.clone(),
+        file_hashes: scan_result.file_hashes.clone(),
+    };
+    agent.file_metrics = scan_result.file_metrics.clone();
+    agent.last_modified = current_timestamp();
+    (agent, checks)
 }
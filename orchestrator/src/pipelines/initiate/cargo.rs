@@ -8,3 +8,14 @@ pub fn update_cargo_toml(agent_dir: &Path, agent_id: &str) -> std::io::Result<()
     fs::write(&cargo_path, cargo_content)?;
     Ok(())
 }
+
+/// Renomme le package d'un agent copié depuis un autre agent
+pub fn rename_cargo_package(agent_dir: &Path, old_id: &str, new_id: &str) -> std::io::Result<()> {
+    let cargo_path = agent_dir.join("Cargo.toml");
+    let cargo_content = fs::read_to_string(&cargo_path)?.replace(
+        &format!("name = \"{}\"", old_id),
+        &format!("name = \"{}\"", new_id),
+    );
+    fs::write(&cargo_path, cargo_content)?;
+    Ok(())
+}
@@ -2,12 +2,20 @@ use std::fs;
 use std::path::Path;
 
 pub fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
+    copy_dir_except(src, dst, &[])
+}
+
+/// Copie un dossier en ignorant les entrées dont le nom figure dans `skip` (ex: `target`)
+pub fn copy_dir_except(src: &Path, dst: &Path, skip: &[&str]) -> std::io::Result<()> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
         let entry = entry?;
+        if skip.iter().any(|name| entry.file_name() == *name) {
+            continue;
+        }
         let ty = entry.file_type()?;
         if ty.is_dir() {
-            copy_dir_all(&entry.path(), &dst.join(entry.file_name()))?;
+            copy_dir_except(&entry.path(), &dst.join(entry.file_name()), skip)?;
         } else {
             fs::copy(&entry.path(), &dst.join(entry.file_name()))?;
         }
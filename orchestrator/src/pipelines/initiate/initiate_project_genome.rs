@@ -1,16 +1,22 @@
 use crate::genome::{FileGene, GenomeConfig};
 use std::collections::HashMap;
 
+/// Génère le génome initial d'un agent.
+/// Les fichiers sont parcourus par chemin trié : avec un `rng` seedé,
+/// le résultat ne dépend pas de l'ordre (aléatoire) du scan.
 pub fn generate_initial_genome(
     scan_files: &[(String, String)],
     rng: &mut impl rand::Rng,
 ) -> GenomeConfig {
+    let mut paths: Vec<&String> = scan_files.iter().map(|(path, _)| path).collect();
+    paths.sort();
+
     let mutation_exists = scan_files
         .iter()
         .any(|(path, _)| path.contains("modules.rs") || path.contains("genome/mutate"));
 
     let mut files = Vec::new();
-    for (path, _) in scan_files {
+    for path in paths {
         let is_main = path.contains("main.rs");
         let is_mutation = path.contains("modules.rs") || path.contains("genome/mutate");
         let active = is_main || is_mutation || rng.gen_bool(0.7);
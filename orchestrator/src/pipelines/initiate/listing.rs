@@ -1,11 +1,26 @@
 use crate::agent_listing::AgentsListing;
 use std::fs;
+use std::io::Write;
 use std::path::Path;
 
+/// Sauvegarde atomique du listing : écriture dans un fichier temporaire puis renommage,
+/// pour ne jamais laisser un listing tronqué en cas d'arrêt pendant l'écriture
 pub fn save_agents_listing<P: AsRef<Path>>(
     listing_path: P,
     listing: &AgentsListing,
 ) -> std::io::Result<()> {
-    let listing_bytes = bincode::serialize(listing).unwrap();
-    fs::write(listing_path, listing_bytes)
+    let listing_path = listing_path.as_ref();
+    let listing_bytes = bincode::serialize(listing).map_err(std::io::Error::other)?;
+    let tmp_path = listing_path.with_extension("bin.tmp");
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&listing_bytes)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, listing_path)
+}
+
+pub fn load_agents_listing<P: AsRef<Path>>(listing_path: P) -> std::io::Result<AgentsListing> {
+    let listing_bytes = fs::read(listing_path)?;
+    bincode::deserialize(&listing_bytes).map_err(std::io::Error::other)
 }
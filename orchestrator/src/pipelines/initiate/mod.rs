@@ -4,6 +4,7 @@ pub mod copy;
 pub mod initiate_project_genome;
 pub mod listing;
 pub mod log;
+pub mod template_agent;
 
 // Permet d'accéder à pipelines::* depuis initiate::*
 pub use initiate_project_genome::generate_initial_genome;
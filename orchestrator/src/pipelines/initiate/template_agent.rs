@@ -0,0 +1,129 @@
+use crate::agent_lineage::AgentLineage;
+use crate::agent_listing::{generate_agent_id, generate_short_uuid, AgentInfo};
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use crate::genome_sync;
+use crate::pipelines::initiate::agent_info::build_agent_info;
+use crate::pipelines::initiate::cargo::update_cargo_toml;
+use crate::pipelines::initiate::copy::copy_dir_all;
+use crate::pipelines::initiate::generate_initial_genome;
+use crate::project_paths::ProjectPaths;
+use crate::scan_agents::RustScanner;
+use crate::seeding::{derive_seed, SeedStream};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs;
+
+/// Crée un agent de génération 0 à partir du template, dans `island` :
+/// copie du template, génome initial tiré avec le flux de l'agent `index`,
+/// code synchronisé avec le génome, puis scan.
+/// Les erreurs sont signalées sans interrompre l'appelant (None : agent non créé).
+pub fn create_template_agent(
+    paths: &ProjectPaths,
+    scanner: &RustScanner,
+    template_files: &[(String, String)],
+    island: &str,
+    index: u64,
+    master_seed: u64,
+    audit: &AuditLog,
+) -> Option<AgentInfo> {
+    // Génération de l'UUID pour l'agent
+    let agent_id = generate_agent_id(master_seed, index);
+    let short_uuid = generate_short_uuid(&agent_id);
+    let agent_dir = paths.agent_dir(island, &short_uuid);
+    if agent_dir.exists() {
+        eprintln!("⚠️ Agent déjà existant : {}", short_uuid);
+        return None;
+    }
+
+    // Copier le template
+    if let Err(e) = copy_dir_all(&paths.template_dir, &agent_dir) {
+        eprintln!("❌ Échec copie template pour {} : {}", short_uuid, e);
+        return None; // Gestion douce des erreurs
+    }
+
+    // Mettre à jour le Cargo.toml
+    if let Err(e) = update_cargo_toml(&agent_dir, &agent_id) {
+        eprintln!("❌ Erreur update Cargo.toml pour {} : {}", short_uuid, e);
+        return None;
+    }
+
+    // Générer le génome initial avec le flux aléatoire propre à cet agent
+    let seed = derive_seed(master_seed, SeedStream::Agent { index });
+    let mut rng = StdRng::seed_from_u64(seed);
+    let initial_genome = generate_initial_genome(template_files, &mut rng);
+
+    // Laisser genome_sync s'occuper de la synchronisation complète
+    if let Err(e) = genome_sync::sync_code_with_genome(&initial_genome, &agent_dir) {
+        eprintln!(
+            "❌ Échec synchronisation génome pour {} : {}",
+            short_uuid, e
+        );
+    }
+
+    // Sauvegarder le génome dans le répertoire de l'agent
+    let agent_genome_path = agent_dir.join("genome.bin");
+    if let Ok(bytes) = bincode::serialize(&initial_genome) {
+        let _ = std::fs::write(&agent_genome_path, bytes);
+    }
+
+    // Sauvegarder une copie du génome dans le répertoire de l'orchestrateur
+    let orchestrator_genome_path = paths.agent_genome_path(island, &short_uuid);
+    let genome_bytes = match bincode::serialize(&initial_genome) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Erreur de sérialisation du génome: {}", e);
+            return None;
+        }
+    };
+    if let Err(e) = fs::write(orchestrator_genome_path, genome_bytes) {
+        eprintln!("Erreur d'écriture du génome orchestrateur: {}", e);
+        return None;
+    }
+
+    // Scanner l'agent APRÈS synchronisation pour obtenir le hash final
+    let scan_result = match scanner.scan_agent(&agent_dir, false) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("❌ Échec scan pour {} : {}", short_uuid, e);
+            return None; // Gestion douce des erreurs
+        }
+    };
+
+    // Créer AgentInfo avec le scan résultant
+    let lineage = AgentLineage {
+        index,
+        generation: 0,
+        parents: Vec::new(),
+        seed,
+    };
+    let (agent_info, checks) = build_agent_info(
+        agent_id,
+        short_uuid.clone(),
+        agent_dir.clone(),
+        island.to_string(),
+        &scan_result,
+        lineage,
+    );
+
+    // Hook d'event création
+    crate::notifications::notifier::notify_killed(
+        &agent_info,
+        "Agent créé (hook event)".to_string(),
+    );
+    audit.record(AuditEvent::AgentCreated {
+        agent: agent_info.name.clone(),
+        id: agent_info.id.clone(),
+        path: agent_info.path.clone(),
+        energy: agent_info.energy,
+        seed,
+        generation: agent_info.lineage.generation,
+        island: agent_info.island.clone(),
+    });
+    audit.record(AuditEvent::Validity {
+        agent: agent_info.name.clone(),
+        is_safe: checks.is_safe,
+        is_valid: checks.is_valid,
+    });
+
+    Some(agent_info)
+}
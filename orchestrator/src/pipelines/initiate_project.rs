@@ -2,24 +2,25 @@
 //récupère le contenu de agent_template/ pour créer les agents
 //crée le fichier genome.bin dans agents/ pour chaque agent avec les fichiers réellement utilisés par l'agent, structure du fichier dans genome.rs
 
-use crate::genome_sync;
-use crate::pipelines::initiate::agent_info::build_agent_info;
-use crate::pipelines::initiate::cargo::update_cargo_toml;
-use crate::pipelines::initiate::copy::copy_dir_all;
-use crate::pipelines::initiate::generate_initial_genome;
+use crate::agent_listing::AgentsListing;
+use crate::audit::audit_log::AuditLog;
+use crate::experiment_config::ExperimentConfig;
 use crate::pipelines::initiate::listing::save_agents_listing;
 use crate::pipelines::initiate::log::write_initialization_log;
+use crate::pipelines::initiate::template_agent::create_template_agent;
 use crate::project_paths::ProjectPaths;
 use crate::scan_agents::RustScanner;
-use crate::{
-    agent_listing::{generate_agent_id, generate_short_uuid, AgentsListing},
-    manage_agents_commands,
-};
+use crate::tasks::coevolution::CoevolutionState;
+use crate::world::WorldState;
 use rayon::prelude::*;
 use std::fs;
 use std::sync::Arc;
 
-pub fn initiate_project(paths: &ProjectPaths, nb_agents: usize) -> std::io::Result<()> {
+pub fn initiate_project(
+    paths: &ProjectPaths,
+    experiment: &ExperimentConfig,
+    audit: &AuditLog,
+) -> std::io::Result<()> {
     // 1. Créer le dossier agents/ s'il n'existe pas
     fs::create_dir_all(&paths.agents_dir)?;
 
@@ -32,7 +33,12 @@ pub fn initiate_project(paths: &ProjectPaths, nb_agents: usize) -> std::io::Resu
     }
 
     // Créer le listing des agents
-    let listing = AgentsListing { agents: Vec::new() };
+    let listing = AgentsListing {
+        agents: Vec::new(),
+        novelty_archive: Vec::new(),
+        world: WorldState::default(),
+        coevolution: CoevolutionState::default(),
+    };
     let initialization_log = Vec::new(); // Log pour récapitulatif
 
     // Scanne d'abord le template pour connaître sa structure
@@ -48,95 +54,40 @@ pub fn initiate_project(paths: &ProjectPaths, nb_agents: usize) -> std::io::Resu
         }
     };
 
-    // 3. Créer les agents initiaux avec leur génome (parallélisé)
-    let agent_indices: Vec<_> = (1..=nb_agents).collect();
+    // 3. Créer les agents initiaux de chaque île avec leur génome (parallélisé).
+    // Les index sont numérotés à la suite d'une île à l'autre (une graine par agent)
+    let islands = experiment.islands();
+    for island in &islands {
+        fs::create_dir_all(paths.island_dir(&island.name))?;
+    }
+    let agent_indices: Vec<(u64, &str)> = islands
+        .iter()
+        .flat_map(|island| std::iter::repeat_n(island.name.as_str(), island.nb_agents))
+        .enumerate()
+        .map(|(i, island)| (i as u64 + 1, island))
+        .collect();
+    let template_files: Vec<(String, String)> = template_scan_result
+        .files
+        .iter()
+        .map(|(p, c)| (p.clone(), c.clone()))
+        .collect();
     let listing = Arc::new(parking_lot::Mutex::new(listing));
     let initialization_log = Arc::new(parking_lot::Mutex::new(initialization_log));
 
     agent_indices.par_chunks(10).for_each(|chunk| {
-        chunk.iter().for_each(|_i| {
-            // Génération de l'UUID pour l'agent
-            let agent_id = generate_agent_id();
-            let short_uuid = generate_short_uuid(&agent_id);
-            let agent_dir = paths.agents_dir.join(&short_uuid);
-            if agent_dir.exists() {
-                eprintln!("⚠️ Agent déjà existant : {}", short_uuid);
-                return;
-            }
-
-            // Copier le template
-            if let Err(e) = copy_dir_all(&paths.template_dir, &agent_dir) {
-                eprintln!("❌ Échec copie template pour {} : {}", short_uuid, e);
-                return; // Gestion douce des erreurs
-            }
-
-            // Mettre à jour le Cargo.toml
-            if let Err(e) = update_cargo_toml(&agent_dir, &agent_id) {
-                eprintln!("❌ Erreur update Cargo.toml pour {} : {}", short_uuid, e);
-                return;
-            }
-
-            // Générer le génome initial via la fonction utilitaire
-            let mut rng = rand::thread_rng();
-            let initial_genome = generate_initial_genome(
-                &template_scan_result
-                    .files
-                    .iter()
-                    .map(|(p, c)| (p.clone(), c.clone()))
-                    .collect::<Vec<_>>(),
-                &mut rng,
-            );
-
-            // Laisser genome_sync s'occuper de la synchronisation complète
-            if let Err(e) = genome_sync::sync_code_with_genome(&initial_genome, &agent_dir) {
-                eprintln!(
-                    "❌ Échec synchronisation génome pour {} : {}",
-                    short_uuid, e
-                );
-            }
-
-            // Sauvegarder le génome dans le répertoire de l'agent
-            let agent_genome_path = agent_dir.join("genome.bin");
-            if let Ok(bytes) = bincode::serialize(&initial_genome) {
-                let _ = std::fs::write(&agent_genome_path, bytes);
-            }
-
-            // Sauvegarder une copie du génome dans le répertoire de l'orchestrateur
-            let orchestrator_genome_path = paths.agent_genome_path(&short_uuid);
-            let genome_bytes = match bincode::serialize(&initial_genome) {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    eprintln!("Erreur de sérialisation du génome: {}", e);
-                    return;
-                }
-            };
-            if let Err(e) = fs::write(orchestrator_genome_path, genome_bytes) {
-                eprintln!("Erreur d'écriture du génome orchestrateur: {}", e);
+        chunk.iter().for_each(|&(index, island)| {
+            let Some(agent_info) = create_template_agent(
+                paths,
+                &scanner,
+                &template_files,
+                island,
+                index,
+                experiment.master_seed,
+                audit,
+            ) else {
                 return;
-            }
-
-            // Scanner l'agent APRÈS synchronisation pour obtenir le hash final
-            let scan_result = match scanner.scan_agent(&agent_dir, false) {
-                Ok(result) => result,
-                Err(e) => {
-                    eprintln!("❌ Échec scan pour {} : {}", short_uuid, e);
-                    return; // Gestion douce des erreurs
-                }
             };
-
-            // Créer AgentInfo avec le scan résultant
-            let agent_info = build_agent_info(
-                agent_id,
-                short_uuid.clone(),
-                agent_dir.clone(),
-                &scan_result,
-            );
-
-            // Hook d'event création
-            crate::notifications::notifier::notify_killed(
-                &agent_info,
-                "Agent créé (hook event)".to_string(),
-            );
+            let short_uuid = agent_info.name.clone();
 
             // Ajouter l'agent au listing (thread-safe)
             listing.lock().agents.push(agent_info);
@@ -146,7 +97,9 @@ pub fn initiate_project(paths: &ProjectPaths, nb_agents: usize) -> std::io::Resu
         });
     });
 
-    let listing = Arc::try_unwrap(listing).unwrap().into_inner();
+    let mut listing = Arc::try_unwrap(listing).unwrap().into_inner();
+    // Ordre du listing indépendant de l'ordre d'exécution parallèle
+    listing.agents.sort_by_key(|agent| agent.lineage.index);
     let initialization_log = Arc::try_unwrap(initialization_log).unwrap().into_inner();
 
     // 4. Sauvegarder le listing
@@ -157,11 +110,12 @@ pub fn initiate_project(paths: &ProjectPaths, nb_agents: usize) -> std::io::Resu
     let log_path = paths.workspace_dir.join("initialization_log.txt");
     write_initialization_log(&log_path, &initialization_log)?;
 
-    // 6. Lancer tous les agents initiaux en parallèle
-    let agent_paths: Vec<_> = listing.agents.iter().map(|a| a.path.clone()).collect();
-    let children = manage_agents_commands::run_all_agents(&agent_paths);
-    let success_count = children.into_iter().filter(|r| r.is_ok()).count();
-    println!("✅ {} agents lancés", success_count);
+    // 6. Les agents restent à l'état Created : le cycle de vie les compile et les lance
+    println!(
+        "✅ {} agents initialisés sur {} île(s)",
+        listing.agents.len(),
+        islands.len()
+    );
 
     Ok(())
 }
@@ -1,161 +1,825 @@
 use crate::agent_config::AgentConfig;
-use crate::agent_listing::AgentsListing;
-use crate::agent_selection::SelectionCriteria;
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::agent_selection::{SelectionCriteria, SelectionMode};
+use crate::agent_state::AgentState;
 use crate::agent_structural_code::AgentStructuralCode;
 use crate::agent_validator;
-use crate::manage_agents_commands;
+use crate::audit::audit_log::{current_timestamp, AuditEvent, AuditLog};
+use crate::control::agent_detail;
+use crate::control::command::{
+    operator_event, AgentSummary, ControlCommand, ControlError, ControlRequest, ControlResult,
+    SelectionParams,
+};
+use crate::diversity::{self, DiversityState};
+use crate::experiment_config::ExperimentConfig;
+use crate::ipc::broker::Broker;
+use crate::ipc::hub::IpcHub;
+use crate::ipc::messages::OrchestratorMessage;
+use crate::island::IslandConfig;
+use crate::manage_agents_commands::{self, LaunchOutcome};
+use crate::monitoring::metrics::Metrics;
+use crate::monitoring::resource_monitor::ResourceUsage;
 use crate::notifications::notifier;
+use crate::novelty;
+use crate::pareto;
+use crate::pipelines::initiate::listing::save_agents_listing;
+use crate::pipelines::life_cycle::energy_changes::recording_energy_changes;
+use crate::pipelines::life_cycle::generation_summary::{
+    changed_summaries, summarize_generations, GenerationStats,
+};
+use crate::pipelines::life_cycle::operator_actions::{self, stop_process};
+use crate::pipelines::life_cycle::state_transition::{
+    apply_transition, handle_failure, recording_transitions,
+};
 use crate::pipelines::life_cycle::{
-    agent_safety, agent_scan_update, genome_sync, natural_selection,
+    agent_clone, agent_safety, agent_scan_update, genome_sync, natural_selection,
 };
 use crate::project_paths::ProjectPaths;
 use crate::scan_agents::RustScanner;
+use crate::shutdown::ShutdownToken;
+use crate::snapshot::population_snapshot;
+use crate::species::{self, SpeciesTracker};
+use protocol::{CoevolutionSummary, ParetoFront, ResourceSample};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
+use std::process::Child;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Intervalle entre deux tours du cycle de vie
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+/// Mesures de ressources conservées par agent (30 minutes à un tour toutes les 5 s)
+const RESOURCE_HISTORY_LEN: usize = 360;
 
 pub struct LifeManager {
-    listing_path: PathBuf,
+    pub(super) listing_path: PathBuf,
+    pub(super) audit: Arc<AuditLog>,
+    pub(super) metrics: Arc<Metrics>,
+    shutdown: ShutdownToken,
+    pub(super) config: AgentConfig,
+    // Modifiables par l'opérateur pendant l'exécution (pris en compte au tour suivant)
+    criteria: Mutex<SelectionCriteria>,
+    pub(super) experiment: ExperimentConfig,
+    // Îles de l'expérience et critères propres à certaines d'entre elles
+    pub(super) islands: Vec<IslandConfig>,
+    island_criteria: HashMap<String, SelectionCriteria>,
+    // Commandes de l'API de contrôle, traitées entre deux tours
+    control: Mutex<Receiver<ControlRequest>>,
+    // Suivi de la population d'un tour à l'autre
+    pub(super) records: Mutex<PopulationRecords>,
+    // Processus des agents Running, possédés par le LifeManager pour pouvoir les arrêter
+    pub(super) processes: Mutex<HashMap<String, Child>>,
+    // Canaux IPC des agents lancés (heartbeats, mesures, demandes)
+    pub(super) ipc: IpcHub,
+    // Boîtes aux lettres et sujets des messages entre agents
+    pub(super) broker: Mutex<Broker>,
+}
+
+/// Suivi de la population d'un tour à l'autre (un seul verrou, jamais gardé pendant
+/// un appel qui le reprend, comme `LifeManager::mutation_rate`)
+/// - generations / pareto_fronts: derniers résumés et fronts journalisés
+///   (seuls les changements sont émis)
+/// - species: espèces de la population (génomes proches), mises à jour à chaque tour
+/// - coevolution: résumés des générations de problèmes coévolués depuis le lancement
+/// - diversity: mesures de diversité et interventions en cours (taux de mutation relevé...)
+/// - resource_history: dernières mesures de ressources de chaque agent
+#[derive(Default)]
+pub(super) struct PopulationRecords {
+    pub generations: BTreeMap<u32, GenerationStats>,
+    pub species: SpeciesTracker,
+    pub pareto_fronts: BTreeMap<u32, ParetoFront>,
+    pub coevolution: Vec<CoevolutionSummary>,
+    pub diversity: DiversityState,
+    pub resource_history: HashMap<String, VecDeque<ResourceSample>>,
+}
+
+/// Données partagées par tous les agents pendant un tour du cycle de vie
+struct TickContext<'a> {
+    scanner: &'a RustScanner,
+    config: &'a AgentConfig,
+    criteria: &'a SelectionCriteria,
+    island_criteria: &'a HashMap<String, SelectionCriteria>,
+    paths: &'a ProjectPaths,
+    audit: &'a AuditLog,
+    metrics: &'a Metrics,
+    ipc: &'a IpcHub,
+    world_enabled: bool,
+    selection_mode: SelectionMode,
+}
+
+impl TickContext<'_> {
+    /// Critères de sélection d'une île (ceux de l'orchestrateur si elle n'en a pas de propres)
+    fn criteria_for(&self, island: &str) -> &SelectionCriteria {
+        self.island_criteria.get(island).unwrap_or(self.criteria)
+    }
 }
 
 impl LifeManager {
-    pub fn new(listing_path: PathBuf) -> Self {
-        Self { listing_path }
+    pub fn new(
+        listing_path: PathBuf,
+        audit: Arc<AuditLog>,
+        metrics: Arc<Metrics>,
+        shutdown: ShutdownToken,
+        experiment: ExperimentConfig,
+        control: Receiver<ControlRequest>,
+        ipc: IpcHub,
+    ) -> Self {
+        let islands = experiment.islands();
+        let island_criteria = islands
+            .iter()
+            .filter_map(|island| Some((island.name.clone(), island.selection.clone()?)))
+            .collect();
+        Self {
+            listing_path,
+            audit,
+            metrics,
+            shutdown,
+            config: AgentConfig::default(),
+            criteria: Mutex::new(SelectionCriteria::default()),
+            experiment,
+            islands,
+            island_criteria,
+            control: Mutex::new(control),
+            records: Mutex::new(PopulationRecords::default()),
+            processes: Mutex::new(HashMap::new()),
+            ipc,
+            broker: Mutex::new(Broker::default()),
+        }
     }
 
-    pub fn manage_agents_lifecycle(&self, listing: &mut AgentsListing, paths: &ProjectPaths) {
-        let config = AgentConfig::default();
-        let criteria = SelectionCriteria::default();
-        let processes = Arc::new(Mutex::new(HashMap::new()));
-        let agent_file_hashes = Arc::new(Mutex::new(HashMap::new()));
-        let agent_file_metrics = Arc::new(Mutex::new(HashMap::new()));
+    /// Boucle du cycle de vie jusqu'à l'annulation du jeton d'arrêt.
+    /// Le listing n'est verrouillé que pendant un tour ou une commande de contrôle.
+    /// À l'arrêt, les processus des agents sont terminés et le listing sauvegardé.
+    pub fn manage_agents_lifecycle(&self, listing: &Mutex<AgentsListing>, paths: &ProjectPaths) {
+        let agent_file_hashes = Mutex::new(HashMap::new());
+        let agent_file_metrics = Mutex::new(HashMap::new());
 
         // Prépare les logs
-        std::fs::create_dir_all(&config.log_dir).expect("Failed to create logs dir");
+        std::fs::create_dir_all(&self.config.log_dir).expect("Failed to create logs dir");
+
+        let mut tick = 0u64;
+        while !self.shutdown.is_cancelled() {
+            let tick_start = Instant::now();
+            tick += 1;
+            {
+                // Chaque étape consigne les variations d'énergie qu'elle a provoquées
+                let mut listing = listing.lock().unwrap();
+                let audit = &*self.audit;
+                recording_energy_changes(&mut listing, audit, "tick", |listing| {
+                    self.run_tick(listing, paths, &agent_file_hashes, &agent_file_metrics)
+                });
+                recording_energy_changes(&mut listing, audit, "agent_channels", |listing| {
+                    self.handle_agent_channels(listing, paths)
+                });
+                if self.experiment.world.enabled {
+                    recording_energy_changes(&mut listing, audit, "world", |listing| {
+                        self.step_world(listing, tick)
+                    });
+                }
+                if self.experiment.migration.is_due(tick) {
+                    recording_energy_changes(&mut listing, audit, "migration", |listing| {
+                        self.migrate(listing, paths)
+                    });
+                }
+                if self.experiment.tasks.is_due(tick) {
+                    recording_energy_changes(&mut listing, audit, "tasks", |listing| {
+                        self.evaluate_tasks(listing, paths, tick)
+                    });
+                }
+                recording_energy_changes(&mut listing, audit, "diversity", |listing| {
+                    self.preserve_diversity(listing, paths, tick)
+                });
+            }
+            self.metrics.observe_tick(tick_start.elapsed());
 
-        loop {
-            let scanner = RustScanner::new(paths.workspace_dir.clone());
+            if !self.wait_next_tick(listing, paths) {
+                break;
+            }
+        }
 
-            // Pooling : traite les agents par chunks de 10
-            for agent_chunk in listing.agents.chunks_mut(10) {
-                agent_chunk.par_iter_mut().for_each(|agent| {
-                    if !agent.active {
-                        return;
-                    }
-                    // Ajout : skip si l'agent est en backoff suite à crash
-                    if agent.has_recent_crash(config.backoff_delay.as_secs()) {
-                        return;
-                    }
+        let mut listing = listing.lock().unwrap();
+        self.shutdown_agents(&mut listing);
+    }
 
-                    // Utilise des verrous pour accéder aux variables partagées
-                    let mut hashes = agent_file_hashes.lock().unwrap();
-                    let mut metrics = agent_file_metrics.lock().unwrap();
-                    let mut procs = processes.lock().unwrap();
+    /// Un tour complet du cycle de vie sur tous les agents
+    fn run_tick(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        agent_file_hashes: &Mutex<HashMap<String, HashMap<String, String>>>,
+        agent_file_metrics: &Mutex<HashMap<String, HashMap<String, AgentStructuralCode>>>,
+    ) {
+        let audit = &*self.audit;
+        let scanner = RustScanner::new(paths.workspace_dir.clone());
+        let criteria = self.criteria.lock().unwrap().clone();
+        let ctx = TickContext {
+            scanner: &scanner,
+            config: &self.config,
+            criteria: &criteria,
+            island_criteria: &self.island_criteria,
+            paths,
+            audit,
+            metrics: &self.metrics,
+            ipc: &self.ipc,
+            world_enabled: self.experiment.world.enabled,
+            selection_mode: self.experiment.selection_mode,
+        };
 
-                    // 1. Scan, détection des fichiers modifiés et mise à jour de l'état de l'agent après scan
-                    let scan_update = match agent_scan_update::scan_and_update_agent(
-                        agent,
-                        &scanner,
-                        &mut *hashes,
-                        &mut *metrics,
-                    ) {
-                        Some(res) => res,
-                        None => return,
+        // Pooling : traite les agents par chunks de 10
+        for agent_chunk in listing.agents.chunks_mut(10) {
+            agent_chunk.par_iter_mut().for_each(|agent| {
+                // Utilise des verrous pour accéder aux variables partagées
+                let mut hashes = agent_file_hashes.lock().unwrap();
+                let mut metrics = agent_file_metrics.lock().unwrap();
+                let mut procs = self.processes.lock().unwrap();
+
+                recording_transitions(agent, audit, &self.metrics, |agent| {
+                    process_agent(agent, &ctx, &mut hashes, &mut metrics, &mut procs)
+                });
+            });
+        }
+
+        // 5. Monitoring des processus : seuls les agents Running gardent un processus
+        //    (et un canal IPC)
+        let mut procs = self.processes.lock().unwrap();
+        procs.retain(|name, child| {
+            let running = listing
+                .agents
+                .iter()
+                .any(|a| &a.name == name && a.state() == AgentState::Running);
+            if !running {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+            running
+        });
+        self.ipc.retain(|name| procs.contains_key(name));
+        drop(procs);
+
+        // 6. Archivage des agents morts ou en quarantaine depuis assez longtemps
+        let now = current_timestamp();
+        for agent in listing.agents.iter_mut() {
+            let expired = now >= agent.state_since() + self.config.archive_after.as_secs();
+            if matches!(agent.state(), AgentState::Dead | AgentState::Quarantined) && expired {
+                recording_transitions(agent, audit, &self.metrics, |agent| {
+                    apply_transition(agent, AgentState::Archived, "Délai de conservation écoulé")
+                });
+            }
+        }
+
+        // 7. Espèces : rattachement des agents vivants (conservé dans le listing)
+        let genomes = species::living_genomes(listing, paths);
+        let species_events = self.records.lock().unwrap().species.speciate(
+            listing,
+            &genomes,
+            &self.experiment.speciation,
+        );
+        for event in species_events {
+            audit.record(event);
+        }
+
+        // 8. Nouveauté des comportements (score dans le listing, archive des plus nouveaux)
+        for event in novelty::update_novelty(listing, &genomes, &self.experiment.novelty, paths) {
+            audit.record(event);
+        }
+
+        // 9. Rangs de Pareto (mémoire : dernière mesure du tour précédent)
+        let memory_mb: HashMap<String, u64> = self
+            .records
+            .lock()
+            .unwrap()
+            .resource_history
+            .iter()
+            .filter_map(|(name, samples)| Some((name.clone(), samples.back()?.memory_mb)))
+            .collect();
+        let fronts = pareto::rank_population(listing, &self.experiment.pareto, &memory_mb, now);
+        {
+            let previous = &mut self.records.lock().unwrap().pareto_fronts;
+            for (generation, front) in &fronts {
+                if previous.get(generation) != Some(front) {
+                    audit.record(AuditEvent::ParetoFront {
+                        generation: *generation,
+                        members: front.members.clone(),
+                    });
+                }
+            }
+            *previous = fronts;
+        }
+
+        // 10. Élagage des îles au-delà de leur plafond (les moins bien classés meurent)
+        self.cull_islands(listing);
+
+        // 11. Diversité de chaque génération et de la population (changements seulement)
+        let weights = &self.experiment.speciation.weights;
+        let measures = diversity::measure_population(listing, &genomes, weights);
+        {
+            let state = &mut self.records.lock().unwrap().diversity;
+            for (generation, metrics) in &measures {
+                if state.metrics.get(generation) != Some(metrics) {
+                    audit.record(metrics.event(*generation));
+                }
+            }
+            state.metrics = measures;
+        }
+
+        // 12. Sauvegarde de l'état
+        self.persist_listing(listing);
+
+        // 13. Métriques de population et de ressources (historique par agent)
+        let usages = self
+            .metrics
+            .resources()
+            .sample(self.processes.lock().unwrap().iter());
+        let total = ResourceUsage::total(usages.iter().map(|(_, usage)| usage));
+        self.metrics.observe_population(listing, total);
+        self.record_resource_samples(usages, now);
+
+        // 14. Résumés de génération (pour les abonnés au journal d'événements)
+        let summaries = summarize_generations(listing);
+        let previous = &mut self.records.lock().unwrap().generations;
+        for event in changed_summaries(previous, &summaries) {
+            audit.record(event);
+        }
+        *previous = summaries;
+    }
+
+    /// Ajoute une mesure à l'historique de ressources de chaque agent en cours d'exécution
+    fn record_resource_samples(&self, usages: Vec<(String, ResourceUsage)>, timestamp: u64) {
+        let history = &mut self.records.lock().unwrap().resource_history;
+        for (name, usage) in usages {
+            let samples = history.entry(name).or_default();
+            if samples.len() == RESOURCE_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(ResourceSample {
+                timestamp,
+                memory_mb: usage.memory_mb,
+                cpu_percent: usage.cpu_percent,
+            });
+        }
+    }
+
+    /// Attend le prochain tour en exécutant les commandes de contrôle reçues.
+    /// Retourne `false` si l'arrêt a été demandé pendant l'attente.
+    fn wait_next_tick(&self, listing: &Mutex<AgentsListing>, paths: &ProjectPaths) -> bool {
+        let deadline = Instant::now() + TICK_INTERVAL;
+        let control = self.control.lock().unwrap();
+        while !self.shutdown.is_cancelled() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return true;
+            }
+            match control.recv_timeout(remaining.min(Duration::from_millis(100))) {
+                Ok(request) => {
+                    let command = request
+                        .command
+                        .is_mutating()
+                        .then(|| request.command.clone());
+                    let result = {
+                        let mut listing = listing.lock().unwrap();
+                        let operator = &request.operator;
+                        let reason = request.command.name();
+                        recording_energy_changes(&mut listing, &self.audit, &reason, |listing| {
+                            self.handle_control(request.command, operator, listing, paths)
+                        })
                     };
-                    let changed_files = scan_update.changed_files;
-                    let scan_files = scan_update.scan_files;
-                    let code_hash = scan_update.scan_result_code_hash;
+                    // Actions opérateur tracées avec leur auteur, réussies ou non
+                    if let Some(command) = command {
+                        self.audit
+                            .record(operator_event(&request.operator, &command, &result));
+                    }
+                    // L'appelant a pu abandonner (délai dépassé) : rien à faire
+                    let _ = request.reply.send(result);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                // API de contrôle absente : simple attente
+                Err(RecvTimeoutError::Disconnected) => return self.shutdown.sleep(remaining),
+            }
+        }
+        false
+    }
 
-                    // Vérifie la sécurité, gère l'état de l'agent si non sûr et met à jour les métriques
-                    if !agent_safety::check_and_handle_agent_safety_and_metrics(
+    /// Exécute une commande de contrôle sur le listing verrouillé (`operator` : auteur de la
+    /// commande, tracé par les dérogations auditées)
+    fn handle_control(
+        &self,
+        command: ControlCommand,
+        operator: &str,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+    ) -> ControlResult {
+        match command {
+            ControlCommand::ListAgents => {
+                let agents: Vec<AgentSummary> = listing.agents.iter().map(Into::into).collect();
+                json(&agents)
+            }
+            ControlCommand::GetAgent { name } => json(find_agent(listing, &name)?),
+            ControlCommand::GetGenome { name } => {
+                let agent = find_agent(listing, &name)?;
+                json(&agent_detail::genome_view(agent, paths)?)
+            }
+            ControlCommand::GetSourceDiff { name } => {
+                let agent = find_agent(listing, &name)?;
+                json(&agent_detail::source_diff(agent, listing, paths))
+            }
+            ControlCommand::GetLogs { name, lines } => {
+                find_agent(listing, &name)?;
+                json(&agent_detail::agent_logs(paths, &name, lines))
+            }
+            ControlCommand::GetResourceHistory { name } => {
+                find_agent(listing, &name)?;
+                let records = self.records.lock().unwrap();
+                let samples: Vec<ResourceSample> = records
+                    .resource_history
+                    .get(&name)
+                    .map(|samples| samples.iter().copied().collect())
+                    .unwrap_or_default();
+                json(&samples)
+            }
+            ControlCommand::GetAgentEvents { name, limit } => {
+                find_agent(listing, &name)?;
+                json(&agent_detail::agent_events(paths, &name, limit)?)
+            }
+            ControlCommand::GetAgentChannel { name } => {
+                let agent = find_agent(listing, &name)?;
+                let mut channel = self.ipc.report(&name).ok_or_else(|| {
+                    ControlError::not_found(format!("Aucun canal IPC ouvert pour {}", name))
+                })?;
+                channel.topics = self.broker.lock().unwrap().topics(&name);
+                channel.messages = agent.messages.clone();
+                json(&channel)
+            }
+            ControlCommand::Kill { name, .. } => {
+                self.operator_action(listing, &name, |agent, procs| {
+                    operator_actions::kill(
                         agent,
-                        &scan_files,
-                        &changed_files,
-                    ) {
-                        return;
-                    }
+                        procs,
+                        &self.ipc,
+                        self.config.shutdown_grace,
+                        &self.audit,
+                    )
+                })
+            }
+            ControlCommand::Pause { name } => {
+                self.operator_action(listing, &name, |agent, procs| {
+                    operator_actions::pause(agent, procs, &self.ipc)
+                })
+            }
+            ControlCommand::Resume { name } => {
+                self.operator_action(listing, &name, |agent, _| operator_actions::resume(agent))
+            }
+            ControlCommand::Revive { name, .. } => {
+                let agent = find_agent(listing, &name)?;
+                if !agent.state().is_alive() {
+                    self.check_island_capacity(listing, &agent.island)?;
+                }
+                self.operator_action(listing, &name, |agent, _| {
+                    operator_actions::revive(agent, operator, &self.audit)
+                })
+            }
+            ControlCommand::Clone { name } => {
+                let parent = find_agent(listing, &name)?;
+                self.check_island_capacity(listing, &parent.island)?;
+                let clone = agent_clone::clone_agent(
+                    parent,
+                    &parent.island,
+                    listing.next_index(),
+                    self.mutation_rate(),
+                    self.experiment.master_seed,
+                    paths,
+                    &self.audit,
+                )?;
+                let summary = AgentSummary::from(&clone);
+                listing.agents.push(clone);
+                self.persist_listing(listing);
+                json(&summary)
+            }
+            ControlCommand::GetSelectionParams => {
+                json(&SelectionParams::from(&*self.criteria.lock().unwrap()))
+            }
+            ControlCommand::SetSelectionParams { params } => {
+                if params.max_score == 0 {
+                    return Err(ControlError::bad_request("max_score doit être positif"));
+                }
+                *self.criteria.lock().unwrap() = params.clone().into();
+                println!("⚙️ Critères de sélection modifiés : {:?}", params);
+                json(&params)
+            }
+            ControlCommand::Snapshot { label } => {
+                let archive =
+                    population_snapshot::create_snapshot(paths, listing, label.as_deref())
+                        .map_err(|e| ControlError::internal(format!("Snapshot : {}", e)))?;
+                Ok(serde_json::json!({ "archive": archive }))
+            }
+            ControlCommand::ListGenerations => {
+                let records = self.records.lock().unwrap();
+                let summaries: Vec<_> = records
+                    .generations
+                    .iter()
+                    .map(|(generation, stats)| stats.summary(*generation))
+                    .collect();
+                json(&summaries)
+            }
+            ControlCommand::ListSpecies => json(&self.records.lock().unwrap().species.summaries()),
+            ControlCommand::GetWorld => json(&listing.world.view()),
+            ControlCommand::ListParetoFronts => {
+                let records = self.records.lock().unwrap();
+                json(&records.pareto_fronts.values().collect::<Vec<_>>())
+            }
+            ControlCommand::ListCoevolution => json(&self.records.lock().unwrap().coevolution),
+            ControlCommand::GetConfig => Ok(serde_json::json!({
+                "experiment": self.experiment,
+                "agent_config": self.config,
+                "selection_criteria": self.criteria.lock().unwrap().clone(),
+            })),
+            // Consignée par l'API de contrôle, jamais transmise au cycle de vie
+            ControlCommand::ReportAccessDenied { .. } => Err(ControlError::bad_request(
+                "report_access_denied est traitée par l'API de contrôle",
+            )),
+        }
+    }
 
-                    // Calcule les métriques totales pour l'agent à partir du cache
-                    let total_metrics = agent.file_metrics.values().fold(
-                        AgentStructuralCode::default(),
-                        |mut acc, m| {
-                            acc.unwrap_count += m.unwrap_count;
-                            acc
-                        },
-                    );
-
-                    // Validation & Compilation (toujours sur l'agent complet)
-                    let is_valid = agent_validator::is_code_valid(&agent.path);
-                    agent.is_valid = is_valid;
-                    if !is_valid {
-                        agent.active = false;
-                        notifier::notify_disabled(agent, "Agent invalide (ne compile pas)".into());
-                        return;
-                    }
+    /// Refuse d'ajouter un agent vivant à une île ayant atteint son plafond
+    fn check_island_capacity(
+        &self,
+        listing: &AgentsListing,
+        island: &str,
+    ) -> Result<(), ControlError> {
+        let living = listing.living_count(island);
+        match self.islands.iter().find(|config| config.name == island) {
+            Some(config) if config.is_full(living) => Err(ControlError::conflict(format!(
+                "Île {} pleine ({} agents vivants)",
+                island, living
+            ))),
+            _ => Ok(()),
+        }
+    }
 
-                    // Gestion du code_hash (toujours sur l'agent complet)
-                    if code_hash != agent.file_hashes.code_hash {
-                        // 3. Kill & Restart
-                        let mut old_process = procs.remove(&agent.name);
-                        match manage_agents_commands::reload_agent(
-                            &agent.path,
-                            &mut agent.is_running,
-                            old_process.as_mut(),
-                        ) {
-                            Ok(Some(child)) => {
-                                procs.insert(agent.name.clone(), child);
-                                agent.file_hashes.code_hash = code_hash;
-                            }
-                            _ => {
-                                eprintln!("❌ Échec relance {}", agent.name);
-                            }
-                        }
-                    }
+    /// Taux de mutation courant des clones et migrants
+    pub(super) fn mutation_rate(&self) -> f64 {
+        let config = &self.experiment.diversity;
+        self.records.lock().unwrap().diversity.mutation_rate(config)
+    }
+
+    /// Sauvegarde le listing ; une erreur est signalée sans interrompre le cycle de vie
+    pub(super) fn persist_listing(&self, listing: &AgentsListing) {
+        if let Err(e) = save_agents_listing(&self.listing_path, listing) {
+            eprintln!("⚠️ Erreur sauvegarde: {}", e);
+        }
+    }
+
+    /// Arrête le processus d'un agent, ferme son canal IPC et le fait passer Dead
+    /// (transition et mort journalisées)
+    pub(super) fn kill_agent(&self, listing: &mut AgentsListing, name: &str, reason: String) {
+        let Some(agent) = listing.agents.iter_mut().find(|a| a.name == name) else {
+            return;
+        };
+        stop_process(&mut self.processes.lock().unwrap(), name);
+        self.ipc.close(name);
+        recording_transitions(agent, &self.audit, &self.metrics, |agent| {
+            if apply_transition(agent, AgentState::Dead, &reason) {
+                self.audit.record(AuditEvent::Killed {
+                    agent: name.to_string(),
+                    reason,
+                });
+            }
+        });
+    }
+
+    /// Île vivante la moins peuplée ayant encore de la place
+    pub(super) fn island_with_room(&self, listing: &AgentsListing) -> Option<String> {
+        self.islands
+            .iter()
+            .map(|island| (island, listing.living_count(&island.name)))
+            .filter(|(island, living)| !island.is_full(*living))
+            .min_by_key(|(_, living)| *living)
+            .map(|(island, _)| island.name.clone())
+    }
+
+    /// Applique une action opérateur sur un agent, journalise ses transitions
+    /// et sauvegarde le listing
+    fn operator_action(
+        &self,
+        listing: &mut AgentsListing,
+        name: &str,
+        action: impl FnOnce(&mut AgentInfo, &mut HashMap<String, Child>) -> Result<(), ControlError>,
+    ) -> ControlResult {
+        let agent = listing
+            .agents
+            .iter_mut()
+            .find(|a| a.name == name)
+            .ok_or_else(|| ControlError::not_found(format!("Agent inconnu : {}", name)))?;
+        recording_transitions(agent, &self.audit, &self.metrics, |agent| {
+            action(agent, &mut self.processes.lock().unwrap())
+        })?;
+        let summary = AgentSummary::from(&*agent);
+
+        self.persist_listing(listing);
+        json(&summary)
+    }
+
+    /// Arrêt propre : SIGTERM puis SIGKILL après le délai de grâce,
+    /// agents Running remis en Building (relancés à la reprise), listing sauvegardé
+    fn shutdown_agents(&self, listing: &mut AgentsListing) {
+        let children: Vec<(String, Child)> = self.processes.lock().unwrap().drain().collect();
+        println!("🛑 Arrêt de {} processus d'agents...", children.len());
+        self.ipc.broadcast(&OrchestratorMessage::Shutdown);
+        for name in manage_agents_commands::terminate_agents(children, self.config.shutdown_grace) {
+            eprintln!("⚠️ Agent {} tué de force (SIGKILL)", name);
+        }
+        self.ipc.retain(|_| false);
 
-                    // Sélection naturelle
-                    natural_selection::process_natural_selection(
+        for agent in listing.agents.iter_mut() {
+            if agent.state() == AgentState::Running {
+                recording_transitions(agent, &self.audit, &self.metrics, |agent| {
+                    apply_transition(
                         agent,
-                        &mut procs,
-                        &total_metrics,
-                        &config,
-                        &criteria,
-                    );
-
-                    // Synchronisation avec le génome
-                    genome_sync::sync_agent_with_genome(agent, paths);
-
-                    // Hook d'event mort
-                    if !agent.active {
-                        crate::notifications::notifier::notify_killed(
-                            agent,
-                            "Agent désactivé (hook event)".to_string(),
-                        );
-                    }
+                        AgentState::Building,
+                        "Arrêt de l'orchestrateur, relance à la reprise",
+                    )
                 });
             }
+        }
 
-            // 5. Monitoring des processus
-            processes.lock().unwrap().retain(|name, child| {
-                match child.try_wait() {
-                    Ok(None) => true, // Toujours en vie
-                    _ => {
-                        if let Some(agent) = listing.agents.iter_mut().find(|a| &a.name == name) {
-                            agent.is_running = false;
-                        }
-                        false
-                    }
-                }
-            });
+        match save_agents_listing(&self.listing_path, listing) {
+            Ok(()) => println!("💾 Listing sauvegardé : {}", self.listing_path.display()),
+            Err(e) => eprintln!("⚠️ Erreur sauvegarde finale: {}", e),
+        }
+    }
+}
 
-            // 6. Sauvegarde de l'état
-            if let Err(e) = bincode::serialize(&listing).and_then(|bytes| {
-                std::fs::write(&self.listing_path, bytes).map_err(bincode::Error::from)
-            }) {
-                eprintln!("⚠️ Erreur sauvegarde: {}", e);
+/// Traite un agent pour un tour du cycle de vie, en fonction de son état
+fn process_agent(
+    agent: &mut AgentInfo,
+    ctx: &TickContext,
+    hashes: &mut HashMap<String, HashMap<String, String>>,
+    metrics: &mut HashMap<String, HashMap<String, AgentStructuralCode>>,
+    procs: &mut HashMap<String, Child>,
+) {
+    // 0. Dispatch sur l'état courant
+    match agent.state() {
+        // États terminaux ou figés : rien à faire
+        AgentState::Paused | AgentState::Quarantined | AgentState::Dead | AgentState::Archived => {
+            return
+        }
+        AgentState::Crashed => {
+            apply_transition(agent, AgentState::Backoff, "Attente avant relance");
+            return;
+        }
+        AgentState::Backoff => {
+            if agent.is_backing_off(ctx.config.backoff_delay.as_secs()) {
+                return;
             }
+            apply_transition(agent, AgentState::Building, "Fin du backoff");
+        }
+        AgentState::Created | AgentState::Building | AgentState::Running => {}
+    }
+
+    // 1. Scan, détection des fichiers modifiés et mise à jour de l'état de l'agent après scan
+    let scan_update =
+        match agent_scan_update::scan_and_update_agent(agent, ctx.scanner, hashes, metrics) {
+            Some(res) => res,
+            None => return,
+        };
+    let changed_files = scan_update.changed_files;
+    let scan_files = scan_update.scan_files;
+    let code_hash = scan_update.scan_result_code_hash;
+    if !changed_files.is_empty() {
+        ctx.audit.record(AuditEvent::ScanResult {
+            agent: agent.name.clone(),
+            code_hash: code_hash.clone(),
+            changed_files: changed_files.clone(),
+        });
+    }
+
+    // Vérifie la sécurité, gère l'état de l'agent si non sûr et met à jour les métriques
+    if !agent_safety::check_and_handle_agent_safety_and_metrics(
+        agent,
+        &scan_files,
+        &changed_files,
+        ctx.audit,
+    ) {
+        stop_process(procs, &agent.name);
+        return;
+    }
+
+    // Calcule les métriques totales pour l'agent à partir du cache
+    let total_metrics =
+        agent
+            .file_metrics
+            .values()
+            .fold(AgentStructuralCode::default(), |mut acc, m| {
+                acc.unwrap_count += m.unwrap_count;
+                acc
+            });
 
-            std::thread::sleep(std::time::Duration::from_secs(5));
+    // Validation & Compilation (toujours sur l'agent complet) : un agent invalide meurt
+    if !agent_validator::is_code_valid(&agent.path) {
+        ctx.audit.record(AuditEvent::Validity {
+            agent: agent.name.clone(),
+            is_safe: true,
+            is_valid: false,
+        });
+        let reason = "Agent invalide (ne compile pas)";
+        stop_process(procs, &agent.name);
+        apply_transition(agent, AgentState::Dead, reason);
+        notifier::notify_disabled(agent, reason.into());
+        ctx.audit.record(AuditEvent::Killed {
+            agent: agent.name.clone(),
+            reason: reason.into(),
+        });
+        return;
+    }
+
+    // 3. (Re)lancement si l'agent n'a pas de processus ou si son code a changé
+    let code_changed = code_hash != agent.file_hashes.code_hash;
+    let has_process = procs.contains_key(&agent.name);
+    if agent.state() != AgentState::Running || code_changed || !has_process {
+        if agent.state() != AgentState::Building {
+            let reason = match agent.state() {
+                AgentState::Created => "Première compilation",
+                _ => "Code modifié, rechargement",
+            };
+            apply_transition(agent, AgentState::Building, reason);
+        }
+
+        let mut old_process = procs.remove(&agent.name);
+        let log_dir = ctx.paths.agent_logs_dir(&agent.name);
+        let ipc_socket = ctx
+            .ipc
+            .open(&agent.name, &log_dir)
+            .map_err(|e| eprintln!("⚠️ Canal IPC indisponible pour {} : {}", agent.name, e))
+            .ok();
+        ctx.metrics.build_started();
+        match manage_agents_commands::reload_agent(
+            &agent.path,
+            agent.lineage.seed,
+            old_process.as_mut(),
+            ctx.config.build_timeout,
+            &log_dir,
+            ipc_socket.as_deref(),
+        ) {
+            Ok(LaunchOutcome::Launched(child)) => {
+                procs.insert(agent.name.clone(), child);
+                agent.file_hashes.code_hash = code_hash;
+                apply_transition(agent, AgentState::Running, "Processus lancé");
+            }
+            Ok(LaunchOutcome::BuildFailed) => {
+                eprintln!("❌ Échec compilation {}", agent.name);
+                ctx.metrics.build_failed();
+                handle_failure(agent, ctx.config, ctx.audit, "Échec de compilation");
+                return;
+            }
+            Ok(LaunchOutcome::BuildTimedOut) => {
+                eprintln!("⏱️ Compilation trop longue {}", agent.name);
+                ctx.metrics.build_timed_out();
+                handle_failure(agent, ctx.config, ctx.audit, "Délai de compilation dépassé");
+                return;
+            }
+            Err(e) => {
+                eprintln!("❌ Échec relance {} : {}", agent.name, e);
+                handle_failure(agent, ctx.config, ctx.audit, "Échec de lancement");
+                return;
+            }
         }
     }
+
+    // Sélection naturelle
+    natural_selection::process_natural_selection(
+        agent,
+        procs,
+        &total_metrics,
+        ctx.config,
+        ctx.criteria_for(&agent.island),
+        ctx.audit,
+        ctx.metrics.resources(),
+        !ctx.world_enabled,
+        ctx.selection_mode,
+    );
+
+    // Synchronisation avec le génome (l'agent est prévenu avant sa relance)
+    if genome_sync::sync_agent_with_genome(agent, ctx.paths) {
+        let path = ctx.paths.agent_genome_path(&agent.island, &agent.name);
+        ctx.ipc
+            .send(&agent.name, &OrchestratorMessage::GenomeAvailable { path });
+    }
+
+    // Hook d'event mort
+    if !agent.state().is_alive() {
+        notifier::notify_killed(agent, format!("Agent {} (hook event)", agent.state()));
+    }
+}
+
+fn find_agent<'a>(listing: &'a AgentsListing, name: &str) -> Result<&'a AgentInfo, ControlError> {
+    listing
+        .agents
+        .iter()
+        .find(|a| a.name == name)
+        .ok_or_else(|| ControlError::not_found(format!("Agent inconnu : {}", name)))
+}
+
+fn json<T: serde::Serialize + ?Sized>(value: &T) -> ControlResult {
+    serde_json::to_value(value).map_err(|e| ControlError::internal(e.to_string()))
 }
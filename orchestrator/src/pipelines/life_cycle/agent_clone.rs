@@ -0,0 +1,143 @@
+use crate::agent_lineage::AgentLineage;
+use crate::agent_listing::{generate_agent_id, generate_short_uuid, AgentInfo};
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use crate::control::command::ControlError;
+use crate::genome::GenomeConfig;
+use crate::genome_mutation::mutate_genome;
+use crate::genome_sync;
+use crate::pipelines::initiate::agent_info::build_agent_info;
+use crate::pipelines::initiate::cargo::rename_cargo_package;
+use crate::pipelines::initiate::copy::copy_dir_except;
+use crate::project_paths::ProjectPaths;
+use crate::scan_agents::RustScanner;
+use crate::seeding::{derive_seed, stream_rng, SeedStream};
+use std::fs;
+
+/// Clone un agent à la demande de l'opérateur : copie de son code (sans `target/`)
+/// et de son génome, nouvel identifiant, génération suivante.
+/// `island` : île de la copie (celle du parent, ou la destination d'un migrant).
+/// `index` : prochain index libre du flux de création (dérivation de la graine).
+/// `mutation_rate` : probabilité de mutation de chaque gène de la copie (0 : copie exacte).
+/// L'agent créé est à l'état Created : le cycle de vie le compile au prochain tour.
+pub fn clone_agent(
+    parent: &AgentInfo,
+    island: &str,
+    index: u64,
+    mutation_rate: f64,
+    master_seed: u64,
+    paths: &ProjectPaths,
+    audit: &AuditLog,
+) -> Result<AgentInfo, ControlError> {
+    let agent_id = generate_agent_id(master_seed, index);
+    let short_uuid = generate_short_uuid(&agent_id);
+    let agent_dir = paths.agent_dir(island, &short_uuid);
+    if agent_dir.exists() {
+        return Err(ControlError::conflict(format!(
+            "Agent déjà existant : {}",
+            short_uuid
+        )));
+    }
+
+    let copy = fs::create_dir_all(paths.island_dir(island))
+        .and_then(|_| copy_dir_except(&parent.path, &agent_dir, &["target"]))
+        .and_then(|_| rename_cargo_package(&agent_dir, &parent.id, &agent_id))
+        .and_then(|_| {
+            copy_genome(
+                parent,
+                &paths.agent_genome_path(island, &short_uuid),
+                mutation_rate,
+                stream_rng(
+                    master_seed,
+                    SeedStream::Generation {
+                        generation: parent.lineage.generation as u64 + 1,
+                        index,
+                    },
+                ),
+                paths,
+            )
+        });
+    if let Err(e) = copy {
+        // Pas d'agent à moitié créé dans agents/
+        let _ = fs::remove_dir_all(&agent_dir);
+        return Err(ControlError::internal(format!(
+            "Clonage de {} impossible : {}",
+            parent.name, e
+        )));
+    }
+
+    let scanner = RustScanner::new(paths.workspace_dir.clone());
+    let scan_result = match scanner.scan_agent(&agent_dir, false) {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&agent_dir);
+            return Err(ControlError::internal(format!(
+                "Échec scan pour {} : {}",
+                short_uuid, e
+            )));
+        }
+    };
+
+    let seed = derive_seed(master_seed, SeedStream::Agent { index });
+    let lineage = AgentLineage {
+        index,
+        generation: parent.lineage.generation + 1,
+        parents: vec![parent.name.clone()],
+        seed,
+    };
+    let (agent_info, checks) = build_agent_info(
+        agent_id,
+        short_uuid,
+        agent_dir,
+        island.to_string(),
+        &scan_result,
+        lineage,
+    );
+
+    audit.record(AuditEvent::AgentCreated {
+        agent: agent_info.name.clone(),
+        id: agent_info.id.clone(),
+        path: agent_info.path.clone(),
+        energy: agent_info.energy,
+        seed,
+        generation: agent_info.lineage.generation,
+        island: agent_info.island.clone(),
+    });
+    audit.record(AuditEvent::Validity {
+        agent: agent_info.name.clone(),
+        is_safe: checks.is_safe,
+        is_valid: checks.is_valid,
+    });
+    println!("🧬 Agent {} cloné depuis {}", agent_info.name, parent.name);
+    Ok(agent_info)
+}
+
+/// Copie le génome du parent, muté avec `mutation_rate` ; le code de la copie
+/// est alors resynchronisé avec son nouveau génome
+fn copy_genome(
+    parent: &AgentInfo,
+    target: &std::path::Path,
+    mutation_rate: f64,
+    mut rng: rand::rngs::StdRng,
+    paths: &ProjectPaths,
+) -> std::io::Result<()> {
+    let source = paths.agent_genome_path(&parent.island, &parent.name);
+    if mutation_rate <= 0.0 {
+        return fs::copy(source, target).map(|_| ());
+    }
+    let mut genome: GenomeConfig =
+        bincode::deserialize(&fs::read(source)?).map_err(std::io::Error::other)?;
+    let mutations = mutate_genome(&mut genome, mutation_rate, &mut rng);
+    fs::write(
+        target,
+        bincode::serialize(&genome).map_err(std::io::Error::other)?,
+    )?;
+    if mutations > 0 {
+        let agent_dir = target.parent().unwrap_or(target);
+        genome_sync::sync_code_with_genome(&genome, agent_dir)?;
+        println!(
+            "🧬 {} gène(s) muté(s) dans la copie de {}",
+            mutations, parent.name
+        );
+    }
+    Ok(())
+}
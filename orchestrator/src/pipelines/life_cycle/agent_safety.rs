@@ -1,18 +1,33 @@
 use crate::agent_code_analysis::analyze_structural_code;
 use crate::agent_listing::AgentInfo;
 use crate::agent_sanitizer;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::{AuditEvent, AuditLog};
 use crate::notifications::notifier;
+use crate::pipelines::life_cycle::state_transition::apply_transition;
 
 pub fn check_and_handle_agent_safety_and_metrics(
     agent: &mut AgentInfo,
     scan_files: &Vec<(String, String)>,
     changed_files: &[String],
+    audit: &AuditLog,
 ) -> bool {
-    agent.is_safe = agent_sanitizer::is_code_safe(scan_files, &agent.path.to_string_lossy());
-    if !agent.is_safe {
-        agent.active = false;
-        agent.is_valid = false;
+    if !agent_sanitizer::is_code_safe(scan_files, &agent.path.to_string_lossy()) {
+        audit.record(AuditEvent::Validity {
+            agent: agent.name.clone(),
+            is_safe: false,
+            is_valid: false,
+        });
+        apply_transition(
+            agent,
+            AgentState::Quarantined,
+            "Agent non sûr après nettoyage",
+        );
         notifier::notify_disabled(agent, "Agent non sûr après nettoyage".into());
+        audit.record(AuditEvent::Killed {
+            agent: agent.name.clone(),
+            reason: "Agent non sûr après nettoyage".into(),
+        });
         return false;
     }
 
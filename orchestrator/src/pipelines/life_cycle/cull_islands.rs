@@ -0,0 +1,19 @@
+use crate::agent_listing::AgentsListing;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::pipelines::life_cycle::culling;
+
+impl LifeManager {
+    /// Élimine les agents les moins bien classés des îles au-delà de leur plafond
+    pub(in crate::pipelines) fn cull_islands(&self, listing: &mut AgentsListing) {
+        let mode = self.experiment.selection_mode;
+        for island in &self.islands {
+            for name in culling::surplus_agents(listing, island, mode) {
+                let reason = format!(
+                    "Île {} au-delà de son plafond : agent le moins bien classé",
+                    island.name
+                );
+                self.kill_agent(listing, &name, reason);
+            }
+        }
+    }
+}
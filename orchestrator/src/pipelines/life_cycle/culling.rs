@@ -0,0 +1,162 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::agent_selection::SelectionMode;
+use crate::agent_state::AgentState;
+use crate::island::IslandConfig;
+
+/// Agents à éliminer d'une île au-delà de son plafond (naissances par `reproduce`) :
+/// les moins bien classés selon `mode` d'abord, à égalité les plus récents.
+/// L'aptitude est partagée au sein de l'espèce : une espèce nouvelle et peu peuplée
+/// n'est pas écrasée par une espèce dominante ; en recherche de nouveauté, les
+/// comportements les plus banals disparaissent les premiers.
+/// Les agents mis en pause par l'opérateur ne sont jamais éliminés.
+pub fn surplus_agents(
+    listing: &AgentsListing,
+    island: &IslandConfig,
+    mode: SelectionMode,
+) -> Vec<String> {
+    let Some(cap) = island.population_cap else {
+        return Vec::new();
+    };
+    let surplus = listing.living_count(&island.name).saturating_sub(cap);
+    if surplus == 0 {
+        return Vec::new();
+    }
+    let mut candidates: Vec<&AgentInfo> = listing
+        .island_agents(&island.name)
+        .filter(|agent| agent.state().is_alive() && agent.state() != AgentState::Paused)
+        .collect();
+    candidates.sort_by(|a, b| {
+        listing
+            .selection_value(a, mode)
+            .total_cmp(&listing.selection_value(b, mode))
+            .then(b.lineage.index.cmp(&a.lineage.index))
+    });
+    candidates
+        .into_iter()
+        .take(surplus)
+        .map(|agent| agent.name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::island::DEFAULT_ISLAND;
+    use crate::pareto::ParetoRank;
+
+    #[test]
+    fn no_culling_without_cap_or_under_cap() {
+        let listing = AgentsListing::with_agents(&[
+            ("a", DEFAULT_ISLAND, 10, 1),
+            ("b", DEFAULT_ISLAND, 20, 1),
+        ]);
+        assert!(surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, None),
+            SelectionMode::Fitness
+        )
+        .is_empty());
+        assert!(surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(2)),
+            SelectionMode::Fitness
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn small_species_is_protected_by_fitness_sharing() {
+        // Espèce 1 : 4 agents à 1000 (aptitude partagée 250) ; espèce 2 : 1 agent à 400
+        let listing = AgentsListing::with_agents(&[
+            ("a", DEFAULT_ISLAND, 1000, 1),
+            ("b", DEFAULT_ISLAND, 1000, 1),
+            ("c", DEFAULT_ISLAND, 1000, 1),
+            ("d", DEFAULT_ISLAND, 1000, 1),
+            ("newcomer", DEFAULT_ISLAND, 400, 2),
+        ]);
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(4)),
+            SelectionMode::Fitness,
+        );
+        // À aptitude égale, le plus récent de l'espèce dominante est éliminé
+        assert_eq!(culled, vec!["d".to_string()]);
+    }
+
+    #[test]
+    fn paused_and_dead_agents_are_never_culled() {
+        let mut listing = AgentsListing::with_agents(&[
+            ("a", DEFAULT_ISLAND, 1, 1),
+            ("b", DEFAULT_ISLAND, 500, 2),
+            ("c", DEFAULT_ISLAND, 900, 3),
+        ]);
+        listing.agents[0]
+            .transition(AgentState::Paused, "pause")
+            .unwrap();
+        listing.agents[2]
+            .transition(AgentState::Dead, "mort")
+            .unwrap();
+        // Vivants : a (en pause) et b ; plafond 1 : seul b peut être éliminé
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(1)),
+            SelectionMode::Fitness,
+        );
+        assert_eq!(culled, vec!["b"]);
+    }
+
+    #[test]
+    fn novelty_mode_culls_the_least_novel_agents() {
+        let mut listing = AgentsListing::with_agents(&[
+            ("fit", DEFAULT_ISLAND, 5000, 1),
+            ("novel", DEFAULT_ISLAND, 10, 2),
+            ("dull", DEFAULT_ISLAND, 10, 3),
+        ]);
+        listing.agents[0].novelty = Some(0.1);
+        listing.agents[1].novelty = Some(3.0);
+        listing.agents[2].novelty = Some(0.5);
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(2)),
+            SelectionMode::Novelty,
+        );
+        assert_eq!(culled, vec!["fit"]);
+
+        let combined = SelectionMode::Combined {
+            novelty_weight: 1000.0,
+        };
+        // fit : 5000 + 100 ; novel : 10 + 3000 ; dull : 10 + 500
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(2)),
+            combined,
+        );
+        assert_eq!(culled, vec!["dull"]);
+    }
+
+    #[test]
+    fn pareto_mode_culls_the_worst_front_then_the_most_crowded() {
+        let mut listing = AgentsListing::with_agents(&[
+            ("a", DEFAULT_ISLAND, 0, 1),
+            ("b", DEFAULT_ISLAND, 0, 1),
+            ("c", DEFAULT_ISLAND, 0, 1),
+            ("d", DEFAULT_ISLAND, 0, 1),
+        ]);
+        let ranks = [(1, f64::INFINITY), (1, 0.2), (1, 2.0), (2, f64::INFINITY)];
+        for (agent, (front, crowding)) in listing.agents.iter_mut().zip(ranks) {
+            agent.pareto = Some(ParetoRank { front, crowding });
+        }
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(3)),
+            SelectionMode::Pareto,
+        );
+        assert_eq!(culled, vec!["d"]);
+        let culled = surplus_agents(
+            &listing,
+            &IslandConfig::for_test(DEFAULT_ISLAND, Some(2)),
+            SelectionMode::Pareto,
+        );
+        assert_eq!(culled, vec!["d", "b"]);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::agent_listing::AgentsListing;
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use std::collections::HashMap;
+
+/// Exécute `change` sur le listing, puis consigne dans le journal d'audit l'énergie
+/// résultante de chaque agent dont l'énergie a varié (`reason` : étape ou commande).
+/// Un agent créé pendant `change` n'est pas concerné : son événement de création
+/// porte déjà son énergie.
+pub fn recording_energy_changes<R>(
+    listing: &mut AgentsListing,
+    audit: &AuditLog,
+    reason: &str,
+    change: impl FnOnce(&mut AgentsListing) -> R,
+) -> R {
+    let before: HashMap<String, i32> = listing
+        .agents
+        .iter()
+        .map(|agent| (agent.name.clone(), agent.energy))
+        .collect();
+    let result = change(listing);
+    for agent in &listing.agents {
+        let Some(&previous) = before.get(&agent.name) else {
+            continue;
+        };
+        if agent.energy != previous {
+            audit.record(AuditEvent::EnergyChanged {
+                agent: agent.name.clone(),
+                delta: agent.energy - previous,
+                energy: agent.energy,
+                reason: reason.to_string(),
+            });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_listing::AgentInfo;
+    use crate::audit::replay::{replay_audit_log, ReplayPoint};
+    use crate::ipc::broker::{Broker, BrokerConfig, Recipients};
+    use crate::world::{self, WorldAction, WorldConfig};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn replay_matches_the_listing_after_world_and_broker_activity() {
+        let dir = std::env::temp_dir().join(format!("energy-changes-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("audit.jsonl");
+        let audit = AuditLog::open(&path).unwrap();
+
+        let mut listing = AgentsListing::for_test();
+        for name in ["a", "b"] {
+            let agent = AgentInfo::for_test(name);
+            audit.record(AuditEvent::AgentCreated {
+                agent: agent.name.clone(),
+                id: agent.id.clone(),
+                path: agent.path.clone(),
+                energy: agent.energy,
+                seed: 0,
+                generation: 0,
+                island: agent.island.clone(),
+            });
+            listing.agents.push(agent);
+        }
+
+        let config = WorldConfig {
+            enabled: true,
+            width: 2,
+            height: 2,
+            fertility: 1.0,
+            ..Default::default()
+        };
+        recording_energy_changes(&mut listing, &audit, "world", |listing| {
+            world::step(listing, &config, &mut StdRng::seed_from_u64(1));
+        });
+        recording_energy_changes(&mut listing, &audit, "agent_channels", |listing| {
+            let consume = WorldAction::Consume { amount: 500 };
+            world::act(listing, "a", consume, &config).unwrap();
+            let step = WorldAction::Move { dx: 1, dy: 0 };
+            world::act(listing, "b", step, &config).unwrap();
+            let mut broker = Broker::default();
+            let to = Recipients::Agent("b".into());
+            let body = "x".repeat(1000);
+            broker
+                .submit(listing, "a", to, body, &BrokerConfig::default())
+                .unwrap();
+            // Coût d'une reproduction payé par le parent
+            listing.agents[1].energy -= 100;
+        });
+
+        let state = replay_audit_log(&path, ReplayPoint::End).unwrap();
+        for agent in &listing.agents {
+            assert_ne!(agent.energy, crate::agent_listing::INITIAL_ENERGY);
+            assert_eq!(state.agents[&agent.name].energy, agent.energy);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
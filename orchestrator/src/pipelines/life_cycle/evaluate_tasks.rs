@@ -0,0 +1,117 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::AuditEvent;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::project_paths::ProjectPaths;
+use crate::seeding::{stream_rng, SeedStream};
+use crate::tasks::harness::{self, TaskRound};
+use protocol::CoevolutionSummary;
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+impl LifeManager {
+    /// Évaluation des agents Running par le harnais de tâches (en parallèle, mêmes
+    /// problèmes pour tous) : le score donne une aptitude et un gain d'énergie.
+    /// En coévolution, les problèmes sont ceux de la population de problèmes (plus un
+    /// échantillon du panthéon), qui passe ensuite à la génération suivante.
+    pub(in crate::pipelines) fn evaluate_tasks(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        tick: u64,
+    ) {
+        let config = &self.experiment.tasks;
+        let mut rng = stream_rng(
+            self.experiment.master_seed,
+            SeedStream::Tasks { round: tick },
+        );
+        let coevolution = config
+            .coevolution
+            .enabled
+            .then(|| listing.coevolution.prepare(config, &mut rng));
+        let round = match &coevolution {
+            Some(coevolution) => &coevolution.round,
+            None => &TaskRound::prepare(config, &mut rng),
+        };
+        let running: Vec<(String, PathBuf, u64)> = listing
+            .agents
+            .iter()
+            .filter(|agent| agent.state() == AgentState::Running)
+            .map(|agent| (agent.name.clone(), agent.path.clone(), agent.lineage.seed))
+            .collect();
+        let results: Vec<_> = running
+            .par_iter()
+            .map(|(name, path, seed)| {
+                let log_dir = paths.agent_logs_dir(name);
+                let result = harness::evaluate(round, path, *seed, &log_dir, config.time_budget());
+                (name, result)
+            })
+            .collect();
+
+        let mut problem_scores = Vec::new();
+        for (name, result) in results {
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("⚠️ Évaluation de {} impossible : {}", name, e);
+                    continue;
+                }
+            };
+            problem_scores.push(result.problem_scores.clone());
+            let Some(agent) = listing.agents.iter_mut().find(|a| &a.name == name) else {
+                continue;
+            };
+            agent.energy += (result.score * config.energy_income).round() as i32;
+            agent.task_score = Some(result.score);
+            agent.task_fitness = result.score * config.fitness_weight;
+            println!(
+                "🧪 Tâches {} : {:.0} % ({} réponses)",
+                name,
+                result.score * 100.0,
+                result.answered
+            );
+            self.audit.record(AuditEvent::TaskEvaluated {
+                agent: name.clone(),
+                score: result.score,
+                suite_scores: result.suite_scores,
+                answered: result.answered,
+                timed_out: result.timed_out,
+                energy: agent.energy,
+            });
+        }
+
+        if let Some(round) = coevolution {
+            let summary =
+                listing
+                    .coevolution
+                    .advance(&config.coevolution, round, &problem_scores, &mut rng);
+            if let Some(summary) = summary {
+                self.record_coevolution(summary);
+            }
+        }
+
+        self.persist_listing(listing);
+    }
+
+    /// Journalise une génération de problèmes coévolués
+    fn record_coevolution(&self, summary: CoevolutionSummary) {
+        println!(
+            "⚔️ Coévolution génération {} : échec moyen {:.0} %, difficulté {:.2}, panthéon {}",
+            summary.generation,
+            summary.mean_failure * 100.0,
+            summary.mean_difficulty,
+            summary.hall_of_fame_size
+        );
+        self.audit.record(AuditEvent::CoevolutionGeneration {
+            generation: summary.generation,
+            challenges: summary.challenges,
+            mean_failure: summary.mean_failure,
+            best_failure: summary.best_failure,
+            mean_difficulty: summary.mean_difficulty,
+            hall_of_fame_size: summary.hall_of_fame_size,
+            hall_of_fame_failure: summary.hall_of_fame_failure,
+            mean_agent_score: summary.mean_agent_score,
+        });
+        self.records.lock().unwrap().coevolution.push(summary);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::AuditEvent;
+use protocol::GenerationSummary;
+use std::collections::{BTreeMap, HashSet};
+
+/// Statistiques d'une génération à la fin d'un tour
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenerationStats {
+    pub alive: usize,
+    pub dead: usize,
+    pub mean_fitness: f64,
+    pub max_fitness: f64,
+    pub diversity: f64,
+}
+
+impl GenerationStats {
+    pub fn summary(&self, generation: u32) -> GenerationSummary {
+        GenerationSummary {
+            generation,
+            alive: self.alive,
+            dead: self.dead,
+            mean_fitness: self.mean_fitness,
+            max_fitness: self.max_fitness,
+            diversity: self.diversity,
+        }
+    }
+}
+
+/// Calcule les statistiques de chaque génération (agents archivés exclus).
+/// L'aptitude et la diversité (part d'empreintes de code distinctes)
+/// ne sont calculées que sur les agents vivants.
+pub fn summarize_generations(listing: &AgentsListing) -> BTreeMap<u32, GenerationStats> {
+    let mut fitness: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+    let mut genotypes: BTreeMap<u32, HashSet<&str>> = BTreeMap::new();
+    let mut summaries: BTreeMap<u32, GenerationStats> = BTreeMap::new();
+
+    for agent in &listing.agents {
+        if agent.state() == AgentState::Archived {
+            continue;
+        }
+        let generation = agent.lineage.generation;
+        let stats = summaries.entry(generation).or_default();
+        if agent.state().is_alive() {
+            stats.alive += 1;
+            fitness.entry(generation).or_default().push(agent.fitness());
+            genotypes
+                .entry(generation)
+                .or_default()
+                .insert(&agent.file_hashes.code_hash);
+        } else {
+            stats.dead += 1;
+        }
+    }
+
+    for (generation, values) in fitness {
+        let stats = summaries.entry(generation).or_default();
+        stats.mean_fitness = values.iter().sum::<f64>() / values.len() as f64;
+        stats.max_fitness = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        stats.diversity = genotypes[&generation].len() as f64 / values.len() as f64;
+    }
+    summaries
+}
+
+/// Événements d'audit pour les générations dont le résumé a changé depuis `previous`
+pub fn changed_summaries(
+    previous: &BTreeMap<u32, GenerationStats>,
+    current: &BTreeMap<u32, GenerationStats>,
+) -> Vec<AuditEvent> {
+    current
+        .iter()
+        .filter(|(generation, stats)| previous.get(generation) != Some(stats))
+        .map(|(generation, stats)| AuditEvent::GenerationSummary {
+            generation: *generation,
+            alive: stats.alive,
+            dead: stats.dead,
+            mean_fitness: stats.mean_fitness,
+            max_fitness: stats.max_fitness,
+            diversity: stats.diversity,
+        })
+        .collect()
+}
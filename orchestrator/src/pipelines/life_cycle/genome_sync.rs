@@ -3,8 +3,9 @@ use crate::genome::GenomeConfig;
 use crate::genome_sync;
 use crate::project_paths::ProjectPaths;
 
-pub fn sync_agent_with_genome(agent: &mut AgentInfo, paths: &ProjectPaths) {
-    let genome_path = paths.agent_genome_path(&agent.name);
+/// Retourne `true` si le code a été modifié pour suivre le génome (relance au tour suivant)
+pub fn sync_agent_with_genome(agent: &mut AgentInfo, paths: &ProjectPaths) -> bool {
+    let genome_path = paths.agent_genome_path(&agent.island, &agent.name);
     if let Ok(genome_bytes) = std::fs::read(&genome_path) {
         if let Ok(genome) = bincode::deserialize::<GenomeConfig>(&genome_bytes) {
             if let Ok(true) = genome_sync::sync_code_with_genome(&genome, &agent.path) {
@@ -13,7 +14,9 @@ pub fn sync_agent_with_genome(agent: &mut AgentInfo, paths: &ProjectPaths) {
                     agent.name
                 );
                 agent.file_hashes.code_hash = "".to_string();
+                return true;
             }
         }
     }
+    false
 }
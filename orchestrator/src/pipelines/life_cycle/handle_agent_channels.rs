@@ -0,0 +1,151 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::{current_timestamp, AuditEvent};
+use crate::ipc::broker::Recipients;
+use crate::ipc::hub::HungAction;
+use crate::ipc::messages::AgentMessage;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::pipelines::life_cycle::operator_actions::stop_process;
+use crate::pipelines::life_cycle::state_transition::{handle_failure, recording_transitions};
+use crate::project_paths::ProjectPaths;
+use crate::world;
+
+impl LifeManager {
+    /// Canaux IPC : les agents bloqués (connectés puis silencieux au-delà du délai)
+    /// sont relancés ou tués, les demandes et messages reçus depuis le tour précédent
+    /// traités, puis les messages en attente délivrés
+    pub(in crate::pipelines) fn handle_agent_channels(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+    ) {
+        let config = &self.experiment.ipc;
+        let mut changed = false;
+        for (name, silent_secs) in self
+            .ipc
+            .silent_agents(current_timestamp(), config.heartbeat_timeout_secs)
+        {
+            if !listing
+                .agents
+                .iter()
+                .any(|a| a.name == name && a.state() == AgentState::Running)
+            {
+                continue;
+            }
+            let reason = format!("Agent bloqué : aucun message depuis {} s", silent_secs);
+            eprintln!("💤 {} : {}", name, reason);
+            self.audit.record(AuditEvent::HeartbeatLost {
+                agent: name.clone(),
+                silent_secs,
+                action: config.on_hung,
+            });
+            match config.on_hung {
+                HungAction::Restart => {
+                    stop_process(&mut self.processes.lock().unwrap(), &name);
+                    self.ipc.close(&name);
+                    if let Some(agent) = listing.agents.iter_mut().find(|a| a.name == name) {
+                        recording_transitions(agent, &self.audit, &self.metrics, |agent| {
+                            handle_failure(agent, &self.config, &self.audit, &reason)
+                        });
+                    }
+                }
+                HungAction::Kill => self.kill_agent(listing, &name, reason),
+            }
+            changed = true;
+        }
+
+        let mut broker = self.broker.lock().unwrap();
+        broker.start_tick();
+        for (name, request) in self.ipc.drain_requests() {
+            let recipients = match &request {
+                AgentMessage::Send { to, body } => Some((Recipients::Agent(to.clone()), body)),
+                AgentMessage::Publish { topic, body } => {
+                    Some((Recipients::Topic(topic.clone()), body))
+                }
+                AgentMessage::Subscribe { topic } => {
+                    broker.subscribe(&name, topic);
+                    None
+                }
+                AgentMessage::Unsubscribe { topic } => {
+                    broker.unsubscribe(&name, topic);
+                    None
+                }
+                AgentMessage::Act(action) => {
+                    match world::act(listing, &name, action.clone(), &self.experiment.world) {
+                        Ok(Some(event)) => self.audit.record(event),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("🌍 Action de {} refusée : {}", name, e),
+                    }
+                    changed = true;
+                    None
+                }
+                _ => None,
+            };
+            if let Some((recipients, body)) = recipients {
+                let result =
+                    broker.submit(listing, &name, recipients, body.clone(), &config.broker);
+                if let Err(e) = result {
+                    eprintln!("✉️ Message de {} refusé : {}", name, e);
+                }
+                changed = true;
+                continue;
+            }
+
+            let (kind, result) = match request {
+                AgentMessage::Reproduce => ("reproduce", self.reproduce(listing, paths, &name)),
+                AgentMessage::NeedEnergy { amount } => (
+                    "need_energy",
+                    grant_energy(listing, &name, amount, config.energy_floor),
+                ),
+                _ => continue,
+            };
+            let Some(agent) = listing.agents.iter().find(|a| a.name == name) else {
+                continue;
+            };
+            let (granted, detail) = match result {
+                Ok(detail) => (true, detail),
+                Err(detail) => (false, detail),
+            };
+            println!("📨 Demande {} de {} : {}", kind, name, detail);
+            self.audit.record(AuditEvent::AgentRequest {
+                agent: name.clone(),
+                request: kind.to_string(),
+                granted,
+                detail,
+                energy: agent.energy,
+            });
+            changed = true;
+        }
+
+        broker.forget_dead(listing);
+        broker.deliver(listing, &self.ipc);
+        drop(broker);
+
+        if changed {
+            self.persist_listing(listing);
+        }
+    }
+}
+
+/// Demande `need_energy` : énergie accordée à un agent Running sans dépasser `floor`
+fn grant_energy(
+    listing: &mut AgentsListing,
+    name: &str,
+    amount: i32,
+    floor: i32,
+) -> Result<String, String> {
+    let agent = listing
+        .agents
+        .iter_mut()
+        .find(|a| a.name == name && a.state() == AgentState::Running)
+        .ok_or_else(|| format!("Agent {} non Running", name))?;
+    if amount <= 0 {
+        return Err(format!("Quantité invalide : {}", amount));
+    }
+    let granted = amount.min(floor - agent.energy);
+    if granted <= 0 {
+        return Err(format!("Énergie suffisante ({} ≥ {})", agent.energy, floor));
+    }
+    agent.energy += granted;
+    Ok(format!("{} d'énergie accordée", granted))
+}
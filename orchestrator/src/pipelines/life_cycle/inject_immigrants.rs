@@ -0,0 +1,45 @@
+use crate::agent_listing::AgentsListing;
+use crate::pipelines::initiate::template_agent::create_template_agent;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::project_paths::ProjectPaths;
+use crate::scan_agents::RustScanner;
+
+impl LifeManager {
+    /// Crée jusqu'à `count` agents de génération 0 depuis le template
+    pub(in crate::pipelines) fn inject_immigrants(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        count: usize,
+    ) -> Vec<String> {
+        let scanner = RustScanner::new(paths.workspace_dir.clone());
+        let template_files: Vec<(String, String)> = match scanner.scan_template(&paths.template_dir)
+        {
+            Ok(result) => result.files.into_iter().collect(),
+            Err(e) => {
+                eprintln!("❌ Échec scan du template: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut names = Vec::new();
+        for _ in 0..count {
+            let Some(island) = self.island_with_room(listing) else {
+                break;
+            };
+            if let Some(agent) = create_template_agent(
+                paths,
+                &scanner,
+                &template_files,
+                &island,
+                listing.next_index(),
+                self.experiment.master_seed,
+                &self.audit,
+            ) {
+                names.push(agent.name.clone());
+                listing.agents.push(agent);
+            }
+        }
+        names
+    }
+}
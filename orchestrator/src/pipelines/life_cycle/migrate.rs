@@ -0,0 +1,75 @@
+use crate::agent_listing::AgentsListing;
+use crate::audit::audit_log::AuditEvent;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::pipelines::life_cycle::{agent_clone, migration};
+use crate::project_paths::ProjectPaths;
+
+impl LifeManager {
+    /// Migration entre îles : chaque migrant est copié (code et génome) dans l'île
+    /// de destination. Une île pleine remplace son agent le moins apte si le migrant
+    /// fait mieux ; sinon le migrant n'est pas accueilli.
+    /// Les agents sont comparés selon le mode de sélection de l'expérience
+    /// (aptitude partagée au sein de chaque espèce, nouveauté ou les deux).
+    pub(in crate::pipelines) fn migrate(&self, listing: &mut AgentsListing, paths: &ProjectPaths) {
+        let mode = self.experiment.selection_mode;
+        let plan =
+            migration::plan_migrations(listing, &self.islands, &self.experiment.migration, mode);
+        for planned in plan {
+            let Some(migrant) = listing.agents.iter().find(|a| a.name == planned.agent) else {
+                continue;
+            };
+            let value = listing.selection_value(migrant, mode);
+
+            let destination = self.islands.iter().find(|i| i.name == planned.to_island);
+            if destination.is_some_and(|island| island.is_full(listing.living_count(&island.name)))
+            {
+                let weakest = migration::weakest_agent(listing, &planned.to_island, mode)
+                    .filter(|weakest| listing.selection_value(weakest, mode) < value)
+                    .map(|weakest| weakest.name.clone());
+                let Some(weakest) = weakest else {
+                    println!(
+                        "🏝️ Île {} pleine, migrant {} refusé",
+                        planned.to_island, planned.agent
+                    );
+                    continue;
+                };
+                // Fait place au migrant : l'agent remplacé est arrêté et passe à l'état Dead
+                let reason = format!("Remplacé par un migrant de l'île {}", planned.from_island);
+                self.kill_agent(listing, &weakest, reason);
+            }
+
+            let Some(migrant) = listing.agents.iter().find(|a| a.name == planned.agent) else {
+                continue;
+            };
+            match agent_clone::clone_agent(
+                migrant,
+                &planned.to_island,
+                listing.next_index(),
+                self.mutation_rate(),
+                self.experiment.master_seed,
+                paths,
+                &self.audit,
+            ) {
+                Ok(copy) => {
+                    println!(
+                        "🏝️ Migration de {} : {} → {} ({})",
+                        planned.agent, planned.from_island, planned.to_island, copy.name
+                    );
+                    self.audit.record(AuditEvent::Migrated {
+                        agent: copy.name.clone(),
+                        source: planned.agent.clone(),
+                        from_island: planned.from_island.clone(),
+                        to_island: planned.to_island.clone(),
+                    });
+                    listing.agents.push(copy);
+                }
+                Err(e) => eprintln!(
+                    "⚠️ Migration de {} impossible : {}",
+                    planned.agent, e.message
+                ),
+            }
+        }
+
+        self.persist_listing(listing);
+    }
+}
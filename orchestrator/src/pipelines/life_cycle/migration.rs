@@ -0,0 +1,155 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::agent_selection::SelectionMode;
+use crate::agent_state::AgentState;
+use crate::island::{IslandConfig, MigrationConfig};
+
+/// Copie prévue d'un agent vers une autre île
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub agent: String,
+    pub from_island: String,
+    pub to_island: String,
+}
+
+/// Migrations d'un tour : les `migrants` meilleurs agents Running de chaque île
+/// (classement `mode` décroissant, puis ordre de création) vers chacune de ses destinations.
+/// Le plan est établi avant toute copie : un migrant arrivé n'est pas renvoyé plus loin.
+pub fn plan_migrations(
+    listing: &AgentsListing,
+    islands: &[IslandConfig],
+    config: &MigrationConfig,
+    mode: SelectionMode,
+) -> Vec<Migration> {
+    let mut plan = Vec::new();
+    for (index, island) in islands.iter().enumerate() {
+        let mut candidates: Vec<&AgentInfo> = listing
+            .island_agents(&island.name)
+            .filter(|agent| agent.state() == AgentState::Running)
+            .collect();
+        candidates.sort_by(|a, b| {
+            listing
+                .selection_value(b, mode)
+                .total_cmp(&listing.selection_value(a, mode))
+                .then(a.lineage.index.cmp(&b.lineage.index))
+        });
+
+        for destination in config.topology.destinations(index, islands.len()) {
+            for agent in candidates.iter().take(config.migrants) {
+                plan.push(Migration {
+                    agent: agent.name.clone(),
+                    from_island: island.name.clone(),
+                    to_island: islands[destination].name.clone(),
+                });
+            }
+        }
+    }
+    plan
+}
+
+/// Agent vivant le moins bien classé (selon `mode`) d'une île, remplacé par un migrant quand l'île est pleine.
+/// Les agents mis en pause par l'opérateur ne sont jamais remplacés.
+pub fn weakest_agent<'a>(
+    listing: &'a AgentsListing,
+    island: &str,
+    mode: SelectionMode,
+) -> Option<&'a AgentInfo> {
+    listing
+        .agents
+        .iter()
+        .filter(|agent| agent.island == island)
+        .filter(|agent| agent.state().is_alive() && agent.state() != AgentState::Paused)
+        .min_by(|a, b| {
+            listing
+                .selection_value(a, mode)
+                .total_cmp(&listing.selection_value(b, mode))
+                .then(b.lineage.index.cmp(&a.lineage.index))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::island::MigrationTopology;
+
+    fn routes(plan: &[Migration]) -> Vec<(&str, &str)> {
+        plan.iter()
+            .map(|m| (m.agent.as_str(), m.to_island.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn ring_sends_the_best_running_agent_to_the_next_island() {
+        let mut listing = AgentsListing::with_agents(&[
+            ("a1", "a", 100, 0),
+            ("a2", "a", 900, 1),
+            ("b1", "b", 500, 2),
+            ("c1", "c", 700, 3),
+        ]);
+        // Le meilleur de c est en pause : c n'envoie personne
+        listing.agents[3]
+            .transition(AgentState::Paused, "pause")
+            .unwrap();
+        let islands = [
+            IslandConfig::for_test("a", None),
+            IslandConfig::for_test("b", None),
+            IslandConfig::for_test("c", None),
+        ];
+        let config = MigrationConfig::default();
+        let plan = plan_migrations(&listing, &islands, &config, SelectionMode::Fitness);
+        assert_eq!(routes(&plan), vec![("a2", "b"), ("b1", "c")]);
+    }
+
+    #[test]
+    fn fully_connected_sends_migrants_to_every_other_island() {
+        let listing = AgentsListing::with_agents(&[
+            ("a1", "a", 100, 0),
+            ("a2", "a", 900, 1),
+            ("a3", "a", 500, 2),
+        ]);
+        let islands = [
+            IslandConfig::for_test("a", None),
+            IslandConfig::for_test("b", None),
+            IslandConfig::for_test("c", None),
+        ];
+        let config = MigrationConfig {
+            topology: MigrationTopology::FullyConnected,
+            interval_ticks: 1,
+            migrants: 2,
+        };
+        let plan = plan_migrations(&listing, &islands, &config, SelectionMode::Fitness);
+        assert_eq!(
+            routes(&plan),
+            vec![("a2", "b"), ("a3", "b"), ("a2", "c"), ("a3", "c")]
+        );
+    }
+
+    #[test]
+    fn single_island_never_migrates() {
+        let listing = AgentsListing::with_agents(&[("a1", "a", 100, 0)]);
+        let config = MigrationConfig::default();
+        let plan = plan_migrations(
+            &listing,
+            &[IslandConfig::for_test("a", None)],
+            &config,
+            SelectionMode::Fitness,
+        );
+        assert!(plan.is_empty());
+        assert!(config.is_due(60) && !config.is_due(59));
+    }
+
+    #[test]
+    fn weakest_agent_skips_paused_and_prefers_the_newest_on_ties() {
+        let mut listing = AgentsListing::with_agents(&[
+            ("old", "a", 200, 0),
+            ("new", "a", 200, 1),
+            ("paused", "a", 1, 2),
+            ("other", "b", 1, 3),
+        ]);
+        listing.agents[2]
+            .transition(AgentState::Paused, "pause")
+            .unwrap();
+        let weakest = weakest_agent(&listing, "a", SelectionMode::Fitness).unwrap();
+        assert_eq!(weakest.name, "new");
+        assert!(weakest_agent(&listing, "empty", SelectionMode::Fitness).is_none());
+    }
+}
@@ -1,4 +1,20 @@
+pub mod agent_clone;
 pub mod agent_safety;
 pub mod agent_scan_update;
+mod cull_islands;
+pub mod culling;
+pub mod energy_changes;
+mod evaluate_tasks;
+pub mod generation_summary;
 pub mod genome_sync;
+mod handle_agent_channels;
+mod inject_immigrants;
+mod migrate;
+pub mod migration;
 pub mod natural_selection;
+pub mod operator_actions;
+mod preserve_diversity;
+mod reproduce;
+mod reseed_from_archive;
+pub mod state_transition;
+mod step_world;
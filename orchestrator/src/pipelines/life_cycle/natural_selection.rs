@@ -1,17 +1,24 @@
 use crate::agent_config::AgentConfig;
 use crate::agent_listing::AgentInfo;
-use crate::agent_selection::SelectionCriteria;
+use crate::agent_selection::{SelectionCriteria, SelectionMode};
 use crate::agent_structural_code::AgentStructuralCode;
+use crate::audit::audit_log::AuditLog;
+use crate::monitoring::resource_monitor::ResourceMonitor;
 use crate::pipelines::selection_life;
 use std::collections::HashMap;
 use std::process::Child;
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_natural_selection(
     agent: &mut AgentInfo,
     processes: &mut HashMap<String, Child>,
     total_metrics: &AgentStructuralCode,
     config: &AgentConfig,
     criteria: &SelectionCriteria,
+    audit: &AuditLog,
+    resources: &ResourceMonitor,
+    code_energy_loss: bool,
+    mode: SelectionMode,
 ) {
     if let Some(process) = processes.get_mut(&agent.name) {
         let survived = selection_life::process_natural_selection(
@@ -20,13 +27,17 @@ pub fn process_natural_selection(
             total_metrics,
             config,
             criteria,
+            audit,
+            resources,
+            code_energy_loss,
+            mode,
         );
 
         // Si l'agent ne survit pas, le supprimer de la liste des processus
         if !survived {
             processes.remove(&agent.name);
         } else {
-            // L'agent survit : on réinitialise son compteur de crashs
+            // L'agent survit en Running : on réinitialise son compteur de crashs
             agent.reset_crash_count();
         }
     }
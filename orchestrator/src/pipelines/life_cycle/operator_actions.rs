@@ -0,0 +1,167 @@
+use crate::agent_listing::AgentInfo;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use crate::control::command::ControlError;
+use crate::ipc::hub::IpcHub;
+use crate::ipc::messages::OrchestratorMessage;
+use crate::manage_agents_commands;
+use std::collections::HashMap;
+use std::process::Child;
+use std::time::Duration;
+
+/// Énergie minimale rendue à un agent mort relancé par l'opérateur
+const REVIVE_ENERGY: i32 = 1000;
+
+/// Arrête et retire le processus d'un agent s'il existe
+pub fn stop_process(procs: &mut HashMap<String, Child>, name: &str) {
+    if let Some(mut child) = procs.remove(name) {
+        let _ = manage_agents_commands::kill_agent(&mut child);
+        let _ = child.wait();
+    }
+}
+
+/// Tue un agent à la demande de l'opérateur : l'agent est prévenu (`shutdown`) et a
+/// `grace` pour s'arrêter seul, puis son processus reçoit SIGTERM et enfin SIGKILL
+/// (comme à l'arrêt de l'orchestrateur). L'agent passe Dead.
+pub fn kill(
+    agent: &mut AgentInfo,
+    procs: &mut HashMap<String, Child>,
+    ipc: &IpcHub,
+    grace: Duration,
+    audit: &AuditLog,
+) -> Result<(), ControlError> {
+    let reason = "Tué par l'opérateur";
+    agent
+        .transition(AgentState::Dead, reason)
+        .map_err(|e| ControlError::conflict(e.to_string()))?;
+    ipc.send(&agent.name, &OrchestratorMessage::Shutdown);
+    if let Some(child) = procs.remove(&agent.name) {
+        let running =
+            manage_agents_commands::wait_for_exit(vec![(agent.name.clone(), child)], grace);
+        for name in manage_agents_commands::terminate_agents(running, grace) {
+            eprintln!("⚠️ Agent {} tué de force (SIGKILL)", name);
+        }
+    }
+    audit.record(AuditEvent::Killed {
+        agent: agent.name.clone(),
+        reason: reason.into(),
+    });
+    Ok(())
+}
+
+/// Met un agent en pause : agent prévenu (`pause`), processus arrêté, plus traité par le cycle de vie
+pub fn pause(
+    agent: &mut AgentInfo,
+    procs: &mut HashMap<String, Child>,
+    ipc: &IpcHub,
+) -> Result<(), ControlError> {
+    agent
+        .transition(AgentState::Paused, "Mis en pause par l'opérateur")
+        .map_err(|e| ControlError::conflict(e.to_string()))?;
+    ipc.send(&agent.name, &OrchestratorMessage::Pause);
+    stop_process(procs, &agent.name);
+    Ok(())
+}
+
+/// Reprend un agent en pause : recompilé et relancé au prochain tour
+pub fn resume(agent: &mut AgentInfo) -> Result<(), ControlError> {
+    if agent.state() != AgentState::Paused {
+        return Err(ControlError::conflict(format!(
+            "Agent {} non en pause (état {})",
+            agent.name,
+            agent.state()
+        )));
+    }
+    agent
+        .transition(AgentState::Building, "Repris par l'opérateur")
+        .map_err(|e| ControlError::conflict(e.to_string()))
+}
+
+/// Relance un agent mort, en pause, en quarantaine ou en attente : recompilé au prochain tour.
+/// Dead -> Building n'existe pas dans la table des transitions : pour un agent mort,
+/// c'est une dérogation de l'opérateur (`AgentInfo::transition_forced`, auditée).
+/// Son énergie est alors remontée à `REVIVE_ENERGY` : sans énergie, il serait
+/// éliminé dès le tour suivant.
+pub fn revive(agent: &mut AgentInfo, operator: &str, audit: &AuditLog) -> Result<(), ControlError> {
+    let reason = "Relancé par l'opérateur";
+    let result = match agent.state() {
+        // Crashed ne peut aller que vers Backoff : on passe par cet état
+        AgentState::Crashed => agent
+            .transition(AgentState::Backoff, reason)
+            .and_then(|_| agent.transition(AgentState::Building, reason)),
+        AgentState::Paused | AgentState::Quarantined | AgentState::Backoff => {
+            agent.transition(AgentState::Building, reason)
+        }
+        AgentState::Dead => {
+            let energy = agent.energy;
+            agent.energy = energy.max(REVIVE_ENERGY);
+            agent.transition_forced(
+                AgentState::Building,
+                &format!("{} (énergie {} -> {})", reason, energy, agent.energy),
+                operator,
+                audit,
+            );
+            Ok(())
+        }
+        state => {
+            return Err(ControlError::conflict(format!(
+                "Agent {} non relançable depuis l'état {}",
+                agent.name, state
+            )))
+        }
+    };
+    result.map_err(|e| ControlError::conflict(e.to_string()))?;
+    // Compteur remis à zéro : sinon le prochain échec le tuerait immédiatement
+    agent.reset_crash_count();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::audit_log::read_records;
+
+    #[test]
+    fn reviving_a_dead_agent_is_an_audited_override() {
+        let dir = std::env::temp_dir().join(format!("revive-override-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let audit = AuditLog::open(&dir.join("audit.jsonl")).unwrap();
+
+        let mut agent = AgentInfo::for_test("lazarus");
+        agent
+            .transition(AgentState::Dead, "énergie épuisée")
+            .unwrap();
+        agent.energy = 0;
+        // La table générique reste fermée : seule la dérogation relance un agent mort
+        assert!(agent
+            .transition(AgentState::Building, "cycle de vie")
+            .is_err());
+
+        revive(&mut agent, "alice", &audit).unwrap();
+        assert_eq!(agent.state(), AgentState::Building);
+        assert_eq!(agent.energy, REVIVE_ENERGY);
+        let last = agent.state_history().last().unwrap();
+        assert_eq!(
+            (last.from, last.to),
+            (AgentState::Dead, AgentState::Building)
+        );
+
+        let records = read_records(&dir.join("audit.jsonl")).unwrap().records;
+        match &records.last().unwrap().event {
+            AuditEvent::OperatorAction {
+                operator,
+                action,
+                detail,
+                ..
+            } => {
+                assert_eq!(operator, "alice");
+                assert_eq!(action, "forced_transition");
+                assert!(detail.starts_with("dead -> building"));
+                assert!(detail.ends_with("(énergie 0 -> 1000)"));
+            }
+            event => panic!("événement inattendu : {:?}", event),
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::agent_listing::AgentsListing;
+use crate::audit::audit_log::AuditEvent;
+use crate::diversity::DiversityIntervention;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::project_paths::ProjectPaths;
+
+impl LifeManager {
+    /// Interventions quand la diversité de la population passe sous le seuil :
+    /// taux de mutation relevé (tant qu'elle reste faible), puis, au plus une fois
+    /// par `cooldown_ticks`, ajout d'immigrants du template ou d'agents archivés
+    pub(in crate::pipelines) fn preserve_diversity(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        tick: u64,
+    ) {
+        let config = &self.experiment.diversity;
+        let (low, mean_distance, can_inject) = {
+            let records = self.records.lock().unwrap();
+            let state = &records.diversity;
+            let population = state.metrics.get(&None).cloned().unwrap_or_default();
+            (
+                population.is_low(config),
+                population.mean_distance,
+                state.can_inject(tick, config),
+            )
+        };
+
+        let mut added = 0;
+        for intervention in &config.interventions {
+            let detail = match intervention {
+                DiversityIntervention::RaiseMutationRate => {
+                    let mut records = self.records.lock().unwrap();
+                    let state = &mut records.diversity;
+                    if state.boosted == low {
+                        continue;
+                    }
+                    state.boosted = low;
+                    format!("Taux de mutation : {}", state.mutation_rate(config))
+                }
+                _ if !low || !can_inject => continue,
+                DiversityIntervention::RandomImmigrants => {
+                    let names = self.inject_immigrants(listing, paths, config.immigrants);
+                    if names.is_empty() {
+                        continue;
+                    }
+                    added += names.len();
+                    format!("Immigrants du template : {}", names.join(", "))
+                }
+                DiversityIntervention::ReseedFromArchive => {
+                    let names = self.reseed_from_archive(listing, paths, config.immigrants);
+                    if names.is_empty() {
+                        continue;
+                    }
+                    added += names.len();
+                    format!("Agents archivés réintroduits : {}", names.join(", "))
+                }
+            };
+            println!("🌱 Diversité {:.3} : {}", mean_distance, detail);
+            self.audit.record(AuditEvent::DiversityIntervention {
+                intervention: *intervention,
+                mean_distance,
+                detail,
+            });
+        }
+
+        if low && can_inject {
+            self.records.lock().unwrap().diversity.last_injection = Some(tick);
+        }
+        if added > 0 {
+            self.persist_listing(listing);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::pipelines::life_cycle::agent_clone;
+use crate::project_paths::ProjectPaths;
+
+impl LifeManager {
+    /// Demande `reproduce` : clone l'agent dans son île s'il peut en payer le coût.
+    /// Une île pleine accepte la naissance : l'élagage du tour suivant élimine
+    /// alors son agent le moins bien classé (éventuellement le nouveau-né).
+    pub(in crate::pipelines) fn reproduce(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        name: &str,
+    ) -> Result<String, String> {
+        let cost = self.experiment.ipc.reproduce_cost;
+        let parent = listing
+            .agents
+            .iter()
+            .find(|a| a.name == name && a.state() == AgentState::Running)
+            .ok_or_else(|| format!("Agent {} non Running", name))?;
+        if parent.energy < cost {
+            return Err(format!(
+                "Énergie insuffisante ({} < {})",
+                parent.energy, cost
+            ));
+        }
+        let clone = agent_clone::clone_agent(
+            parent,
+            &parent.island,
+            listing.next_index(),
+            self.mutation_rate(),
+            self.experiment.master_seed,
+            paths,
+            &self.audit,
+        )
+        .map_err(|e| e.message)?;
+        let detail = format!("Clone {} créé (coût : {} d'énergie)", clone.name, cost);
+        listing.agents.push(clone);
+        if let Some(parent) = listing.agents.iter_mut().find(|a| a.name == name) {
+            parent.energy -= cost;
+        }
+        Ok(detail)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::genome_distance::genome_distance;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::pipelines::life_cycle::agent_clone;
+use crate::project_paths::ProjectPaths;
+use crate::species;
+
+impl LifeManager {
+    /// Clone jusqu'à `count` agents archivés, en commençant par ceux dont le génome
+    /// est le plus éloigné (en moyenne) des génomes vivants
+    pub(in crate::pipelines) fn reseed_from_archive(
+        &self,
+        listing: &mut AgentsListing,
+        paths: &ProjectPaths,
+        count: usize,
+    ) -> Vec<String> {
+        let weights = &self.experiment.speciation.weights;
+        let living = species::living_genomes(listing, paths);
+        let mut candidates: Vec<(String, f64)> = listing
+            .agents
+            .iter()
+            .filter(|agent| agent.state() == AgentState::Archived && agent.path.is_dir())
+            .filter_map(|agent| {
+                let bytes = std::fs::read(paths.agent_genome_path(&agent.island, &agent.name));
+                let genome = bincode::deserialize(&bytes.ok()?).ok()?;
+                let distance = living
+                    .values()
+                    .map(|other| genome_distance(&genome, other, weights))
+                    .sum::<f64>()
+                    / living.len().max(1) as f64;
+                Some((agent.name.clone(), distance))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut names = Vec::new();
+        for (name, _) in candidates.into_iter().take(count) {
+            let Some(island) = self.island_with_room(listing) else {
+                break;
+            };
+            let Some(archived) = listing.agents.iter().find(|a| a.name == name) else {
+                continue;
+            };
+            match agent_clone::clone_agent(
+                archived,
+                &island,
+                listing.next_index(),
+                self.mutation_rate(),
+                self.experiment.master_seed,
+                paths,
+                &self.audit,
+            ) {
+                Ok(copy) => {
+                    names.push(copy.name.clone());
+                    listing.agents.push(copy);
+                }
+                Err(e) => eprintln!("⚠️ Réintroduction de {} impossible : {}", name, e.message),
+            }
+        }
+        names
+    }
+}
@@ -0,0 +1,98 @@
+use crate::agent_config::AgentConfig;
+use crate::agent_listing::AgentInfo;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::{AuditEvent, AuditLog};
+use crate::monitoring::metrics::Metrics;
+use crate::notifications::notifier;
+
+/// Applique une transition d'état et signale les transitions interdites
+/// sans interrompre le pipeline. Retourne `true` si la transition a eu lieu.
+pub fn apply_transition(agent: &mut AgentInfo, to: AgentState, reason: &str) -> bool {
+    match agent.transition(to, reason) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("⚠️ {}", e);
+            false
+        }
+    }
+}
+
+/// Exécute `change` sur l'agent, puis consigne dans le journal d'audit (et les métriques)
+/// chaque transition que `change` a ajoutée à son historique
+pub fn recording_transitions<R>(
+    agent: &mut AgentInfo,
+    audit: &AuditLog,
+    metrics: &Metrics,
+    change: impl FnOnce(&mut AgentInfo) -> R,
+) -> R {
+    let history_len = agent.state_history().len();
+    let result = change(agent);
+    for transition in &agent.state_history()[history_len..] {
+        metrics.transition(transition.to);
+        audit.record(AuditEvent::StateChanged {
+            agent: agent.name.clone(),
+            from: transition.from,
+            to: transition.to,
+            reason: transition.reason.clone(),
+        });
+    }
+    result
+}
+
+/// Compte un échec (compilation, lancement, agent bloqué) comme un crash (Dead au-delà de max_retries)
+pub fn handle_failure(agent: &mut AgentInfo, config: &AgentConfig, audit: &AuditLog, reason: &str) {
+    agent.increment_crash();
+    audit.record(AuditEvent::Crash {
+        agent: agent.name.clone(),
+        crash_count: agent.crash_count,
+    });
+    apply_transition(agent, AgentState::Crashed, reason);
+    if agent.crash_count > config.max_retries {
+        apply_transition(agent, AgentState::Dead, "Too many crashes");
+        notifier::notify_disabled(agent, "Too many crashes".into());
+        audit.record(AuditEvent::Killed {
+            agent: agent.name.clone(),
+            reason: "Too many crashes".into(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::audit_log::read_records;
+
+    #[test]
+    fn only_transitions_made_inside_the_scope_are_recorded() {
+        let dir = std::env::temp_dir().join(format!("recording-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let audit = AuditLog::open(&dir.join("audit.jsonl")).unwrap();
+        let metrics = Metrics::default();
+
+        // for_test passe déjà par Created -> Building -> Running, hors enregistrement
+        let mut agent = AgentInfo::for_test("a");
+        let crashed = recording_transitions(&mut agent, &audit, &metrics, |agent| {
+            apply_transition(agent, AgentState::Crashed, "crash")
+                && apply_transition(agent, AgentState::Backoff, "attente")
+        });
+        assert!(crashed);
+
+        let recorded: Vec<(AgentState, AgentState)> = read_records(&dir.join("audit.jsonl"))
+            .unwrap()
+            .records
+            .into_iter()
+            .filter_map(|record| match record.event {
+                AuditEvent::StateChanged { from, to, .. } => Some((from, to)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            recorded,
+            vec![
+                (AgentState::Running, AgentState::Crashed),
+                (AgentState::Crashed, AgentState::Backoff)
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::agent_listing::AgentsListing;
+use crate::pipelines::life_agent_after_init::LifeManager;
+use crate::seeding::{stream_rng, SeedStream};
+use crate::world;
+
+impl LifeManager {
+    /// Tour du monde simulé, puis envoi à chaque agent présent de ce qu'il perçoit
+    pub(in crate::pipelines) fn step_world(&self, listing: &mut AgentsListing, tick: u64) {
+        let config = &self.experiment.world;
+        let mut rng = stream_rng(self.experiment.master_seed, SeedStream::World { tick });
+        world::step(listing, config, &mut rng);
+        for agent in &listing.agents {
+            let perception =
+                listing
+                    .world
+                    .perception(&agent.name, agent.energy, config.perception_radius);
+            if let Some(perception) = perception {
+                self.ipc.send(&agent.name, &perception);
+            }
+        }
+        self.persist_listing(listing);
+    }
+}
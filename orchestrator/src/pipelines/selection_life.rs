@@ -1,16 +1,21 @@
 use crate::agent_config::AgentConfig;
 use crate::agent_listing::AgentInfo;
 use crate::agent_needs::apply_energy_loss;
-use crate::agent_selection::{should_survive_agent, SelectionCriteria};
+use crate::agent_selection::{
+    selection_score, should_survive_agent, should_survive_pareto, SelectionCriteria, SelectionMode,
+};
+use crate::agent_state::AgentState;
 use crate::agent_structural_code::AgentStructuralCode;
+use crate::audit::audit_log::{AuditEvent, AuditLog};
 use crate::manage_agents_commands;
-use crate::monitoring::resource_monitor;
+use crate::monitoring::resource_monitor::ResourceMonitor;
 use crate::notifications::notifier;
+use crate::pipelines::life_cycle::state_transition::apply_transition;
 use std::process::Child;
 
 /// Gère la sélection naturelle d'un agent en exécutant une série de vérifications
-/// par ordre de priorité. Si l'agent échoue à l'une de ces vérifications, il est
-/// terminé avec une notification appropriée.
+/// par ordre de priorité. Si l'agent échoue à l'une de ces vérifications, il quitte
+/// l'état Running (Backoff, Crashed ou Dead) avec une notification appropriée.
 ///
 /// # Arguments
 /// * `agent` - L'agent à évaluer
@@ -18,52 +23,75 @@ use std::process::Child;
 /// * `total_metrics` - Métriques de code analysées pour l'agent
 /// * `config` - Configuration globale (limites de ressources, etc.)
 /// * `criteria` - Critères de sélection spécifiques
+/// * `audit` - Journal d'audit recevant crashs, scores (quand ils changent) et raisons de mort
+/// * `resources` - Surveillance des processus (dernière mesure de fin de tour)
+/// * `code_energy_loss` - Perte d'énergie liée au code (remplacée par le coût de vie du monde simulé)
+/// * `mode` - Mode de sélection : en mode `pareto`, le score de pénalité ne tue plus
+///   (il reste journalisé), seules les contraintes dures s'appliquent
 ///
 /// # Retourne
 /// `true` si l'agent a survécu à toutes les vérifications, `false` sinon
+#[allow(clippy::too_many_arguments)]
 pub fn process_natural_selection(
     agent: &mut AgentInfo,
     process: &mut Child,
     total_metrics: &AgentStructuralCode,
     config: &AgentConfig,
     criteria: &SelectionCriteria,
+    audit: &AuditLog,
+    resources: &ResourceMonitor,
+    code_energy_loss: bool,
+    mode: SelectionMode,
 ) -> bool {
     // --------------------------------
     // 1. DÉTECTION DE CRASH
     // --------------------------------
-    // Vérifie d'abord si l'agent a crashé trop souvent
-    // Si c'est le cas, inutile de continuer les autres vérifications
-    if manage_agents_commands::check_agent_crashed(agent.is_running, process) {
+    // Un processus terminé n'a plus besoin des autres vérifications
+    if let Some(exit_code) = manage_agents_commands::check_agent_exit(process) {
+        if exit_code == 0 {
+            // Fin normale : relance différée, sans compter de crash
+            apply_transition(agent, AgentState::Backoff, "Processus terminé normalement");
+            return false;
+        }
+
         agent.increment_crash(); // Utilise la méthode de AgentInfo
+        audit.record(AuditEvent::Crash {
+            agent: agent.name.clone(),
+            crash_count: agent.crash_count,
+        });
+        apply_transition(
+            agent,
+            AgentState::Crashed,
+            &format!("Code de sortie {}", exit_code),
+        );
 
-        // Si l'agent a dépassé le nombre maximal de tentatives, on le désactive
+        // Si l'agent a dépassé le nombre maximal de tentatives, il meurt
         if agent.crash_count > config.max_retries {
-            agent.active = false;
+            apply_transition(agent, AgentState::Dead, "Too many crashes");
             notifier::notify_disabled(agent, "Too many crashes".into());
-            return false;
+            record_kill(audit, agent, "Too many crashes");
         } else {
-            // Sinon on notifie le crash mais on laisse une chance
+            // Sinon on notifie le crash : le cycle de vie le passera en Backoff
             notifier::notify_crashed(agent, "Agent crash détecté".into());
         }
+        return false;
     }
 
     // --------------------------------
     // 2. VÉRIFICATION DES RESSOURCES
     // --------------------------------
     // Termine l'agent s'il consomme trop de ressources système
-    let over_limit = resource_monitor::check_resource_usage(
-        process,
-        config.memory_limit_mb,
-        config.cpu_limit_percent,
-    );
+    let over_limit =
+        resources.exceeds_limits(process, config.memory_limit_mb, config.cpu_limit_percent);
 
     if over_limit {
         notifier::notify_resource_limit(agent, "memory/cpu".into(), config.memory_limit_mb);
-        notifier::notify_killed(agent, "Agent tué pour dépassement de ressources".into());
-        if let Err(e) = manage_agents_commands::kill_agent(&mut agent.is_running, process) {
-            eprintln!("Failed to kill agent {}: {}", agent.name, e);
-        }
-        // Ne supprime plus l'agent de processes ici
+        kill_agent_process(
+            agent,
+            process,
+            "Agent tué pour dépassement de ressources",
+            audit,
+        );
         return false;
     }
 
@@ -71,12 +99,14 @@ pub fn process_natural_selection(
     // 3. GESTION DE L'ÉNERGIE
     // --------------------------------
     // Applique la perte d'énergie basée sur les patterns de code
-    apply_energy_loss(agent, total_metrics);
+    // (le monde simulé, s'il est actif, prélève son propre coût de vie)
+    if code_energy_loss {
+        apply_energy_loss(agent, total_metrics);
+    }
 
     // Vérifie si l'agent a encore assez d'énergie pour fonctionner
     if agent.energy <= 0 {
-        agent.active = false;
-        notifier::notify_disabled(agent, "Agent épuisé (énergie nulle)".into());
+        kill_agent_process(agent, process, "Agent épuisé (énergie nulle)", audit);
         return false;
     }
 
@@ -84,31 +114,73 @@ pub fn process_natural_selection(
     // 4. RÈGLES DE SÉLECTION NATURELLE
     // --------------------------------
     // Applique un ensemble de règles complexes pour déterminer si l'agent
-    // doit survivre (sécurité, validité, qualité du code, etc.)
-    if !should_survive_agent(
-        agent.is_safe,
-        agent.is_valid,
+    // doit survivre (sécurité, validité, qualité du code, etc.).
+    // Sécurité et compilation sont vérifiées plus tôt dans le tour : un agent qui
+    // arrive ici n'est ni en quarantaine ni mort, son code est donc sûr et valide.
+    let (is_safe, is_valid) = (true, true);
+    let score = selection_score(
+        is_safe,
+        is_valid,
         0,
         0,
         config.memory_limit_mb,
         config.cpu_limit_percent,
         total_metrics,
         criteria,
-        agent.energy,
-    ) {
+    );
+    let survived = match mode {
+        SelectionMode::Pareto => should_survive_pareto(is_safe, is_valid, agent.energy),
+        _ => should_survive_agent(
+            is_safe,
+            is_valid,
+            0,
+            0,
+            config.memory_limit_mb,
+            config.cpu_limit_percent,
+            total_metrics,
+            criteria,
+            agent.energy,
+        ),
+    };
+    // Journalisé seulement quand le score change (l'énergie a ses propres événements)
+    if !survived || agent.selection_score != Some(score) {
+        audit.record(AuditEvent::SelectionScore {
+            agent: agent.name.clone(),
+            score,
+            max_score: criteria.max_score,
+            energy: agent.energy,
+            survived,
+        });
+    }
+    agent.selection_score = Some(score);
+
+    if !survived {
         notifier::notify_resource_limit(
             agent,
             "selection_naturelle".into(),
             config.memory_limit_mb,
         );
-        notifier::notify_killed(agent, "Agent tué par sélection naturelle".into());
-        if let Err(e) = manage_agents_commands::kill_agent(&mut agent.is_running, process) {
-            eprintln!("Failed to kill agent {}: {}", agent.name, e);
-        }
-        // Ne supprime plus l'agent de processes ici
+        kill_agent_process(agent, process, "Agent tué par sélection naturelle", audit);
         return false;
     }
 
     // L'agent a survécu à toutes les vérifications
     true
 }
+
+/// Termine le processus d'un agent et le fait passer à l'état Dead
+fn kill_agent_process(agent: &mut AgentInfo, process: &mut Child, reason: &str, audit: &AuditLog) {
+    if let Err(e) = manage_agents_commands::kill_agent(process) {
+        eprintln!("Failed to kill agent {}: {}", agent.name, e);
+    }
+    apply_transition(agent, AgentState::Dead, reason);
+    notifier::notify_killed(agent, reason.to_string());
+    record_kill(audit, agent, reason);
+}
+
+fn record_kill(audit: &AuditLog, agent: &AgentInfo, reason: &str) {
+    audit.record(AuditEvent::Killed {
+        agent: agent.name.clone(),
+        reason: reason.to_string(),
+    });
+}
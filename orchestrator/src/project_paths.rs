@@ -5,22 +5,34 @@ pub const WORKSPACE_DIR: &str = "."; // Racine du projet (dossier courant)
 pub const ORCHESTRATOR_DIR: &str = "orchestrator"; // Sous-dossier orchestrator
 pub const AGENTS_DIR: &str = "agents"; // Sous-dossier agents
 pub const TEMPLATE_DIR: &str = "agent_template"; // Sous-dossier template des agents
+pub const LOGS_DIR: &str = "logs"; // Sous-dossier des logs et du journal d'audit
+pub const SNAPSHOTS_DIR: &str = "snapshots"; // Sous-dossier des snapshots de population
+pub const IPC_DIR: &str = "ipc"; // Sous-dossier des sockets agent ↔ orchestrateur
 
 pub struct ProjectPaths {
     pub workspace_dir: PathBuf,
     pub orchestrator_dir: PathBuf,
     pub agents_dir: PathBuf,
     pub template_dir: PathBuf,
+    pub logs_dir: PathBuf,
+    pub snapshots_dir: PathBuf,
 }
 
 impl ProjectPaths {
     pub fn new() -> Self {
-        let workspace_path = Path::new(WORKSPACE_DIR).to_path_buf();
+        Self::with_workspace(Path::new(WORKSPACE_DIR))
+    }
+
+    /// Chemins relatifs à un autre workspace (ex: restauration d'un fork)
+    pub fn with_workspace(workspace_dir: &Path) -> Self {
+        let workspace_path = workspace_dir.to_path_buf();
         Self {
             workspace_dir: workspace_path.clone(),
             orchestrator_dir: workspace_path.join(ORCHESTRATOR_DIR),
             agents_dir: workspace_path.join(AGENTS_DIR),
             template_dir: workspace_path.join(TEMPLATE_DIR),
+            logs_dir: workspace_path.join(LOGS_DIR),
+            snapshots_dir: workspace_path.join(SNAPSHOTS_DIR),
         }
     }
 
@@ -29,8 +41,48 @@ impl ProjectPaths {
         self.agents_dir.join("listing_agents.bin")
     }
 
+    /// Helper pour obtenir le dossier d'une île (agents/<île>/)
+    pub fn island_dir(&self, island: &str) -> PathBuf {
+        self.agents_dir.join(island)
+    }
+
+    /// Helper pour obtenir le dossier d'un agent (agents/<île>/<agent>/)
+    pub fn agent_dir(&self, island: &str, agent_name: &str) -> PathBuf {
+        self.island_dir(island).join(agent_name)
+    }
+
     /// Helper pour obtenir le chemin du fichier genome.bin d'un agent
-    pub fn agent_genome_path(&self, agent_name: &str) -> PathBuf {
-        self.agents_dir.join(agent_name).join("genome.bin")
+    pub fn agent_genome_path(&self, island: &str, agent_name: &str) -> PathBuf {
+        self.agent_dir(island, agent_name).join("genome.bin")
+    }
+
+    /// Helper pour obtenir le chemin du journal d'audit (JSONL, ajout seul)
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.logs_dir.join("audit_log.jsonl")
+    }
+
+    /// Helper pour obtenir le dossier des sorties (stdout.log, stderr.log) d'un agent
+    pub fn agent_logs_dir(&self, agent_name: &str) -> PathBuf {
+        self.logs_dir.join("agents").join(agent_name)
+    }
+
+    /// Helper pour obtenir le dossier des sockets IPC des agents
+    pub fn ipc_dir(&self) -> PathBuf {
+        self.workspace_dir.join(IPC_DIR)
+    }
+
+    /// Helper pour obtenir le chemin de l'archive d'un snapshot
+    pub fn snapshot_path(&self, label: &str) -> PathBuf {
+        self.snapshots_dir.join(format!("{}.tar.gz", label))
+    }
+
+    /// Helper pour obtenir le chemin des jetons d'accès à l'API et à la passerelle ws
+    pub fn access_tokens_path(&self) -> PathBuf {
+        self.workspace_dir.join(protocol::ACCESS_TOKENS_FILE)
+    }
+
+    /// Helper pour obtenir le chemin de la configuration d'expérience (graine, taille...)
+    pub fn experiment_config_path(&self) -> PathBuf {
+        self.workspace_dir.join("experiment.ron")
     }
 }
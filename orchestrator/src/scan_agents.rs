@@ -54,10 +54,13 @@ impl RustScanner {
             file_metrics.insert(rel_path, metrics);
         }
 
+        // Fichiers hachés dans l'ordre de leurs chemins (celui d'une HashMap varie d'un processus à l'autre)
         let code_hash = {
             let mut hasher = Sha256::new();
-            for content in files.values() {
-                hasher.update(content.as_bytes());
+            let mut paths: Vec<&String> = files.keys().collect();
+            paths.sort();
+            for path in paths {
+                hasher.update(files[path].as_bytes());
             }
             format!("{:x}", hasher.finalize())
         };
@@ -0,0 +1,226 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+
+/// Flux aléatoires indépendants dérivés de la graine maîtresse de l'expérience.
+/// Chaque flux est reproductible : même graine + même flux = même séquence.
+#[derive(Debug, Clone, Copy)]
+pub enum SeedStream {
+    /// Flux propre à un agent (génome initial, graine passée au processus)
+    Agent { index: u64 },
+    /// Flux propre à une génération (recombinaisons, décisions aléatoires du tour)
+    Generation { generation: u64, index: u64 },
+    /// Flux des problèmes d'une évaluation du harnais de tâches (tour `round`)
+    Tasks { round: u64 },
+    /// Flux du monde simulé au tour `tick` (création, placement des agents)
+    World { tick: u64 },
+}
+
+/// Dérive une graine 64 bits pour un flux donné (SHA-256 de la graine maîtresse et du flux)
+pub fn derive_seed(master_seed: u64, stream: SeedStream) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(master_seed.to_le_bytes());
+    match stream {
+        SeedStream::Agent { index } => {
+            hasher.update(b"agent");
+            hasher.update(index.to_le_bytes());
+        }
+        SeedStream::Generation { generation, index } => {
+            hasher.update(b"generation");
+            hasher.update(generation.to_le_bytes());
+            hasher.update(index.to_le_bytes());
+        }
+        SeedStream::Tasks { round } => {
+            hasher.update(b"tasks");
+            hasher.update(round.to_le_bytes());
+        }
+        SeedStream::World { tick } => {
+            hasher.update(b"world");
+            hasher.update(tick.to_le_bytes());
+        }
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Générateur déterministe pour un flux donné
+pub fn stream_rng(master_seed: u64, stream: SeedStream) -> StdRng {
+    StdRng::seed_from_u64(derive_seed(master_seed, stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_listing::AgentsListing;
+    use crate::agent_selection::{should_survive_agent, SelectionCriteria};
+    use crate::agent_structural_code::AgentStructuralCode;
+    use crate::audit::audit_log::{read_records, AuditEvent, AuditLog};
+    use crate::experiment_config::ExperimentConfig;
+    use crate::genetic_recombination::recombine_in_generation;
+    use crate::genome::GenomeConfig;
+    use crate::pipelines::initiate::generate_initial_genome;
+    use crate::pipelines::initiate::listing::load_agents_listing;
+    use crate::pipelines::initiate_project;
+    use crate::pipelines::life_cycle::agent_clone::clone_agent;
+    use crate::project_paths::ProjectPaths;
+    use std::fs;
+    use std::path::Path;
+
+    fn template_files() -> Vec<(String, String)> {
+        [
+            "src/main.rs",
+            "src/genome/neural.rs",
+            "src/genome/memory_graph.rs",
+            "src/genome/symbolic.rs",
+            "src/genome/curiosity.rs",
+            "src/genome/rl.rs",
+            "src/genome/hormones.rs",
+            "src/genome/communication.rs",
+            "src/genome/selfmod.rs",
+            "src/genome/meta.rs",
+            "src/genome/mutate/modules.rs",
+        ]
+        .iter()
+        .map(|path| (path.to_string(), String::new()))
+        .collect()
+    }
+
+    /// Rejoue une mini-expérience : génomes initiaux, une recombinaison, décisions de sélection
+    fn run_experiment(
+        master_seed: u64,
+        files: &[(String, String)],
+    ) -> (Vec<GenomeConfig>, GenomeConfig, Vec<bool>) {
+        let experiment = ExperimentConfig {
+            master_seed,
+            nb_agents: 6,
+            ..ExperimentConfig::default()
+        };
+        let genomes: Vec<GenomeConfig> = (1..=experiment.nb_agents as u64)
+            .map(|index| {
+                let mut rng = stream_rng(master_seed, SeedStream::Agent { index });
+                generate_initial_genome(files, &mut rng)
+            })
+            .collect();
+        let child = recombine_in_generation(&genomes[0], &genomes[1], &experiment, 1, 0);
+
+        let criteria = SelectionCriteria::default();
+        let decisions = genomes
+            .iter()
+            .map(|genome| {
+                let active = genome.files.iter().filter(|f| f.active).count() as u32;
+                let metrics = AgentStructuralCode {
+                    unwrap_count: active * 10,
+                };
+                should_survive_agent(true, true, 0, 0, 500, 50, &metrics, &criteria, 1000)
+            })
+            .collect();
+
+        (genomes, child, decisions)
+    }
+
+    #[test]
+    fn same_seed_reproduces_genomes_and_selection() {
+        let files = template_files();
+        let mut shuffled = files.clone();
+        shuffled.reverse(); // l'ordre du scan ne doit pas compter
+
+        let first = run_experiment(42, &files);
+        let second = run_experiment(42, &shuffled);
+
+        assert_eq!(first.0, second.0);
+        assert_eq!(first.1, second.1);
+        assert_eq!(first.2, second.2);
+    }
+
+    #[test]
+    fn different_seeds_give_different_streams() {
+        let files = template_files();
+        assert_ne!(run_experiment(1, &files).0, run_experiment(2, &files).0);
+        assert_ne!(
+            derive_seed(7, SeedStream::Agent { index: 1 }),
+            derive_seed(
+                7,
+                SeedStream::Generation {
+                    generation: 0,
+                    index: 1
+                }
+            )
+        );
+    }
+
+    /// Initialise une population dans un workspace neuf puis clone son premier agent :
+    /// retourne le listing obtenu et les événements journalisés
+    fn run_population(master_seed: u64, workspace: &Path) -> (AgentsListing, Vec<AuditEvent>) {
+        let _ = fs::remove_dir_all(workspace);
+        let paths = ProjectPaths::with_workspace(workspace);
+        let template = &paths.template_dir;
+        fs::create_dir_all(template.join("src/genome")).unwrap();
+        fs::write(
+            template.join("Cargo.toml"),
+            "[package]\nname = \"{agent_name}\"\n",
+        )
+        .unwrap();
+        fs::write(template.join("src/main.rs"), "mod genome;\nfn main() {}\n").unwrap();
+        fs::write(template.join("src/genome/neural.rs"), "pub fn think() {}\n").unwrap();
+        fs::write(template.join("src/genome/rl.rs"), "pub fn learn() {}\n").unwrap();
+
+        let experiment = ExperimentConfig {
+            master_seed,
+            ..ExperimentConfig::default()
+        };
+        let audit = AuditLog::open(&paths.audit_log_path()).unwrap();
+        initiate_project(&paths, &experiment, &audit).unwrap();
+        let mut listing = load_agents_listing(paths.agent_listing_path()).unwrap();
+        let parent = &listing.agents[0];
+        let child = clone_agent(
+            parent,
+            &parent.island,
+            listing.next_index(),
+            0.5,
+            master_seed,
+            &paths,
+            &audit,
+        )
+        .unwrap();
+        listing.agents.push(child);
+
+        let events = read_records(&paths.audit_log_path())
+            .unwrap()
+            .records
+            .into_iter()
+            .map(|record| record.event)
+            .collect();
+        (listing, events)
+    }
+
+    #[test]
+    fn same_seed_reproduces_agent_ids_listing_and_events() {
+        let workspace = std::env::temp_dir().join(format!("seeding-run-{}", std::process::id()));
+        let (first, first_events) = run_population(42, &workspace);
+        let (second, second_events) = run_population(42, &workspace);
+        let _ = fs::remove_dir_all(&workspace);
+
+        let summary = |listing: &AgentsListing| {
+            listing
+                .agents
+                .iter()
+                .map(|agent| {
+                    (
+                        agent.id.clone(),
+                        agent.name.clone(),
+                        agent.path.clone(),
+                        agent.lineage.seed,
+                        agent.lineage.parents.clone(),
+                        agent.file_hashes.code_hash.clone(),
+                        agent.energy,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(first.agents.len(), 4);
+        assert_eq!(summary(&first), summary(&second));
+        assert_eq!(first_events, second_events);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::agent_lineage::AgentLineage;
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::agent_state::AgentState;
+use crate::island::DEFAULT_ISLAND;
+use crate::snapshot::manifest::SnapshotManifest;
+use crate::tasks::coevolution::CoevolutionState;
+use crate::world::WorldState;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reconstruit le listing d'un snapshot des versions 1 à 4.
+/// Leur listing binaire dépend de la structure d'`AgentInfo` de l'époque et n'est
+/// plus lisible : chaque agent est recréé depuis son entrée du manifeste et son
+/// dossier extrait dans `staging_dir`. Généalogie, historique d'états, archive de
+/// nouveauté, monde simulé et problèmes coévolués ne sont pas récupérés.
+pub fn rebuild_listing(
+    manifest: &SnapshotManifest,
+    staging_dir: &Path,
+    agents_entry: &str,
+) -> io::Result<AgentsListing> {
+    let agents_dir = staging_dir.join(agents_entry);
+    let mut agents = Vec::new();
+    for entry in &manifest.agents {
+        let agent_dir = agents_dir.join(&entry.island).join(&entry.name);
+        if manifest.format_version == 1 {
+            // Version 1 : `agents/<agent>`, et le manifeste listait aussi les agents
+            // sans dossier (ceux-là ne peuvent pas être reconstruits)
+            let legacy_dir = agents_dir.join(&entry.name);
+            if !legacy_dir.is_dir() {
+                println!("⚠️ Agent {} absent de l'archive, ignoré", entry.name);
+                continue;
+            }
+            fs::create_dir_all(agents_dir.join(DEFAULT_ISLAND))?;
+            fs::rename(&legacy_dir, &agent_dir)?;
+        }
+
+        let lineage = AgentLineage {
+            index: agents.len() as u64 + 1,
+            ..Default::default()
+        };
+        let mut agent = AgentInfo::new(
+            entry.id.clone(),
+            entry.name.clone(),
+            agent_dir,
+            entry.island.clone(),
+            lineage,
+        );
+        agent.energy = entry.energy;
+        agent.file_hashes.code_hash = entry.code_hash.clone();
+        restore_state(&mut agent, entry.state, manifest.format_version)?;
+        agents.push(agent);
+    }
+
+    Ok(AgentsListing {
+        agents,
+        novelty_archive: Vec::new(),
+        world: WorldState::default(),
+        coevolution: CoevolutionState::default(),
+    })
+}
+
+/// Ramène un agent recréé (Created) à l'état du manifeste par des transitions
+/// autorisées. Un agent vivant reste Created : il est recompilé à la reprise.
+fn restore_state(agent: &mut AgentInfo, state: AgentState, version: u32) -> io::Result<()> {
+    let path: &[AgentState] = match state {
+        AgentState::Paused => &[AgentState::Paused],
+        AgentState::Quarantined => &[AgentState::Quarantined],
+        AgentState::Dead => &[AgentState::Dead],
+        AgentState::Archived => &[AgentState::Dead, AgentState::Archived],
+        _ => &[],
+    };
+    let reason = format!("Restauré depuis un snapshot v{}", version);
+    for &to in path {
+        agent
+            .transition(to, &reason)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::agent_listing::AgentInfo;
+use crate::agent_state::AgentState;
+use crate::island::DEFAULT_ISLAND;
+use serde::{Deserialize, Serialize};
+
+/// Version du format d'archive (à incrémenter si la structure change) :
+/// - 1 : listing binaire, dossiers `agents/<agent>`
+/// - 2 : îles (`agents/<île>/<agent>`, île de chaque agent dans le manifeste)
+/// - 3 : monde simulé dans le listing binaire
+/// - 4 : problèmes coévolués dans le listing binaire
+/// - 5 : listing JSON et configuration d'expérience archivée
+///
+/// Les versions 1 à 4 sont reconstruites depuis le manifeste (voir `migration`)
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 5;
+
+/// Nom du manifeste à la racine de l'archive
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Résumé d'un agent dans le manifeste
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotAgentEntry {
+    pub name: String,
+    pub id: String,
+    // Absente en version 1 (avant les îles)
+    #[serde(default = "default_island")]
+    pub island: String,
+    pub state: AgentState,
+    pub energy: i32,
+    pub code_hash: String,
+}
+
+/// Manifeste décrivant le contenu d'un snapshot de population
+/// (`agents` : agents dont le dossier est présent dans l'archive,
+/// `experiment_file` : configuration d'expérience, si le workspace en avait une)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub label: String,
+    pub created_at: u64, // Timestamp UNIX
+    pub listing_file: String,
+    #[serde(default)]
+    pub experiment_file: Option<String>,
+    pub agents: Vec<SnapshotAgentEntry>,
+}
+
+impl SnapshotManifest {
+    pub fn new<'a>(
+        label: &str,
+        created_at: u64,
+        listing_file: &str,
+        experiment_file: Option<&str>,
+        archived: impl IntoIterator<Item = &'a AgentInfo>,
+    ) -> Self {
+        Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            label: label.to_string(),
+            created_at,
+            listing_file: listing_file.to_string(),
+            experiment_file: experiment_file.map(String::from),
+            agents: archived
+                .into_iter()
+                .map(|agent| SnapshotAgentEntry {
+                    name: agent.name.clone(),
+                    id: agent.id.clone(),
+                    island: agent.island.clone(),
+                    state: agent.state(),
+                    energy: agent.energy,
+                    code_hash: agent.file_hashes.code_hash.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn default_island() -> String {
+    DEFAULT_ISLAND.to_string()
+}
@@ -0,0 +1,3 @@
+pub mod format_migration;
+pub mod manifest;
+pub mod population_snapshot;
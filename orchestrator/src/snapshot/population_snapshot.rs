@@ -0,0 +1,433 @@
+use crate::agent_listing::{AgentInfo, AgentsListing};
+use crate::audit::audit_log::{current_timestamp, AuditEvent, AuditLog};
+use crate::island::is_valid_island_name;
+use crate::pipelines::initiate::listing::save_agents_listing;
+use crate::project_paths::ProjectPaths;
+use crate::snapshot::format_migration;
+use crate::snapshot::manifest::{SnapshotManifest, MANIFEST_FILE, SNAPSHOT_FORMAT_VERSION};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Nom du listing dans l'archive (JSON : lisible même si `AgentInfo` évolue)
+const LISTING_ENTRY: &str = "listing_agents.json";
+/// Nom de la configuration d'expérience dans l'archive
+const EXPERIMENT_ENTRY: &str = "experiment.ron";
+/// Préfixe des dossiers d'agents dans l'archive (`agents/<île>/<agent>`)
+const AGENTS_ENTRY: &str = "agents";
+/// Dossiers jamais archivés (artefacts de compilation)
+const EXCLUDED_DIRS: &[&str] = &["target"];
+
+/// Crée un snapshot compressé de toute la population :
+/// - manifeste JSON (label, date, état et énergie de chaque agent archivé)
+/// - listing tel qu'en mémoire, avec l'état du monde simulé et les problèmes
+///   coévolués (l'appelant tient le verrou : capture cohérente)
+/// - configuration d'expérience (`experiment.ron`), si le workspace en a une
+/// - sources et génome de chaque agent, sans `target/` (un agent dont le dossier a
+///   disparu reste dans le listing mais n'est pas listé dans le manifeste)
+///
+/// L'archive est écrite dans un fichier temporaire puis renommée.
+pub fn create_snapshot(
+    paths: &ProjectPaths,
+    listing: &AgentsListing,
+    label: Option<&str>,
+) -> io::Result<PathBuf> {
+    let created_at = current_timestamp();
+    let label = match label {
+        Some(label) => label.to_string(),
+        None => format!("snapshot-{}", created_at),
+    };
+    if label.is_empty()
+        || !label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Label de snapshot invalide: {}", label),
+        ));
+    }
+
+    fs::create_dir_all(&paths.snapshots_dir)?;
+    let archive_path = paths.snapshot_path(&label);
+    if archive_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Le snapshot {} existe déjà", archive_path.display()),
+        ));
+    }
+
+    let tmp_path = archive_path.with_extension("tmp");
+    match write_archive(&tmp_path, paths, listing, &label, created_at) {
+        Ok(()) => fs::rename(&tmp_path, &archive_path)?,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+    }
+
+    println!(
+        "📦 Snapshot {} créé ({} agents) : {}",
+        label,
+        listing.agents.len(),
+        archive_path.display()
+    );
+    Ok(archive_path)
+}
+
+fn write_archive(
+    tmp_path: &Path,
+    paths: &ProjectPaths,
+    listing: &AgentsListing,
+    label: &str,
+    created_at: u64,
+) -> io::Result<()> {
+    let encoder = GzEncoder::new(File::create(tmp_path)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let archived: Vec<&AgentInfo> = listing
+        .agents
+        .iter()
+        .filter(|agent| agent.path.is_dir())
+        .collect();
+    let experiment_path = paths.experiment_config_path();
+    let experiment_file = experiment_path.is_file().then_some(EXPERIMENT_ENTRY);
+    let manifest = SnapshotManifest::new(
+        label,
+        created_at,
+        LISTING_ENTRY,
+        experiment_file,
+        archived.iter().copied(),
+    );
+    append_bytes(
+        &mut builder,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&manifest)?,
+        created_at,
+    )?;
+
+    let listing_bytes = serde_json::to_vec(listing)?;
+    append_bytes(&mut builder, LISTING_ENTRY, &listing_bytes, created_at)?;
+    if experiment_file.is_some() {
+        builder.append_path_with_name(&experiment_path, EXPERIMENT_ENTRY)?;
+    }
+
+    for agent in archived {
+        let entry = Path::new(AGENTS_ENTRY)
+            .join(&agent.island)
+            .join(&agent.name);
+        append_dir_filtered(&mut builder, &agent.path, &entry)?;
+    }
+
+    let file = builder.into_inner()?.finish()?;
+    file.sync_all()
+}
+
+fn append_bytes<W: Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    bytes: &[u8],
+    mtime: u64,
+) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)
+}
+
+/// Ajoute récursivement un dossier à l'archive en ignorant `EXCLUDED_DIRS`
+fn append_dir_filtered<W: Write>(
+    builder: &mut tar::Builder<W>,
+    src: &Path,
+    entry: &Path,
+) -> io::Result<()> {
+    builder.append_dir(entry, src)?;
+    for dir_entry in fs::read_dir(src)? {
+        let dir_entry = dir_entry?;
+        let file_type = dir_entry.file_type()?;
+        let name = dir_entry.file_name();
+        if file_type.is_dir() {
+            if EXCLUDED_DIRS.iter().any(|excluded| name == *excluded) {
+                continue;
+            }
+            append_dir_filtered(builder, &dir_entry.path(), &entry.join(&name))?;
+        } else if file_type.is_file() {
+            builder.append_path_with_name(dir_entry.path(), entry.join(&name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Restaure une population depuis un snapshot.
+/// L'archive est extraite à part puis validée avant de remplacer `agents/` et
+/// `experiment.ron` ; les anciens sont conservés avec le suffixe
+/// `.before-restore-<timestamp>`. Une archive d'une version antérieure est
+/// reconstruite depuis son manifeste. La restauration est consignée dans `audit`.
+/// Restaurer dans un autre workspace permet de forker une expérience.
+pub fn restore_snapshot(
+    archive_path: &Path,
+    paths: &ProjectPaths,
+    audit: &AuditLog,
+) -> io::Result<AgentsListing> {
+    fs::create_dir_all(&paths.workspace_dir)?;
+    let staging_dir = paths.workspace_dir.join(".restore_staging");
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+
+    let result = unpack_and_validate(archive_path, &staging_dir, paths);
+    let (manifest, listing) = match result {
+        Ok(restored) => restored,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging_dir);
+            return Err(e);
+        }
+    };
+
+    // Remplacement du dossier agents/ et de la configuration (les anciens sont conservés)
+    let restored_at = current_timestamp();
+    keep_before_restore(&paths.agents_dir, restored_at)?;
+    let staged_agents = staging_dir.join(AGENTS_ENTRY);
+    if staged_agents.exists() {
+        fs::rename(&staged_agents, &paths.agents_dir)?;
+    } else {
+        fs::create_dir_all(&paths.agents_dir)?;
+    }
+    if let Some(experiment_file) = &manifest.experiment_file {
+        let experiment_path = paths.experiment_config_path();
+        keep_before_restore(&experiment_path, restored_at)?;
+        fs::rename(staging_dir.join(experiment_file), &experiment_path)?;
+    }
+    save_agents_listing(paths.agent_listing_path(), &listing)?;
+    fs::remove_dir_all(&staging_dir)?;
+
+    let migrated_from =
+        (manifest.format_version != SNAPSHOT_FORMAT_VERSION).then_some(manifest.format_version);
+    audit.record(AuditEvent::SnapshotRestored {
+        label: manifest.label.clone(),
+        archive: archive_path.display().to_string(),
+        agents: listing.agents.len(),
+        migrated_from,
+    });
+    println!(
+        "♻️ Population restaurée depuis {} ({} agents)",
+        archive_path.display(),
+        listing.agents.len()
+    );
+    Ok(listing)
+}
+
+/// Renomme `path` (s'il existe) en `<nom>.before-restore-<timestamp>`
+fn keep_before_restore(path: &Path, restored_at: u64) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".before-restore-{}", restored_at));
+    fs::rename(path, &backup)?;
+    println!(
+        "🗄️ Ancienne version conservée dans {}",
+        Path::new(&backup).display()
+    );
+    Ok(())
+}
+
+fn unpack_and_validate(
+    archive_path: &Path,
+    staging_dir: &Path,
+    paths: &ProjectPaths,
+) -> io::Result<(SnapshotManifest, AgentsListing)> {
+    let decoder = GzDecoder::new(File::open(archive_path)?);
+    tar::Archive::new(decoder).unpack(staging_dir)?;
+
+    let manifest: SnapshotManifest =
+        serde_json::from_slice(&fs::read(staging_dir.join(MANIFEST_FILE))?)?;
+    let mut listing: AgentsListing = match manifest.format_version {
+        SNAPSHOT_FORMAT_VERSION => {
+            serde_json::from_slice(&fs::read(staging_dir.join(&manifest.listing_file))?)?
+        }
+        1..SNAPSHOT_FORMAT_VERSION => {
+            println!(
+                "🔁 Snapshot v{} : listing reconstruit depuis le manifeste",
+                manifest.format_version
+            );
+            format_migration::rebuild_listing(&manifest, staging_dir, AGENTS_ENTRY)?
+        }
+        version => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Version de snapshot non supportée: {}", version),
+            ))
+        }
+    };
+    if let Some(experiment_file) = &manifest.experiment_file {
+        if experiment_file != EXPERIMENT_ENTRY || !staging_dir.join(experiment_file).is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Configuration {} absente de l'archive", experiment_file),
+            ));
+        }
+    }
+
+    // Le manifeste v1 listait aussi les agents sans dossier (ignorés à la reconstruction)
+    let archived = match manifest.format_version {
+        1 => &[][..],
+        _ => &manifest.agents[..],
+    };
+    for entry in archived {
+        let agent_dir = staging_dir
+            .join(AGENTS_ENTRY)
+            .join(&entry.island)
+            .join(&entry.name);
+        if !is_valid_island_name(&entry.island) || !agent_dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Agent {} absent de l'archive", entry.name),
+            ));
+        }
+    }
+
+    // Les chemins sont recalculés pour le workspace de destination
+    for agent in listing.agents.iter_mut() {
+        if !is_valid_island_name(&agent.island) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Île invalide pour l'agent {}", agent.name),
+            ));
+        }
+        agent.path = paths.agent_dir(&agent.island, &agent.name);
+    }
+    Ok((manifest, listing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_state::AgentState;
+    use crate::audit::audit_log::read_records;
+
+    #[test]
+    fn snapshot_round_trip_skips_agents_without_directory() {
+        let root = std::env::temp_dir().join(format!("snapshot-round-trip-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let source = ProjectPaths::with_workspace(&root.join("source"));
+        let target = ProjectPaths::with_workspace(&root.join("target"));
+
+        let mut listing = AgentsListing::for_test();
+        for name in ["kept", "lost"] {
+            let mut agent = AgentInfo::for_test(name);
+            agent.path = source.agent_dir(&agent.island, name);
+            listing.agents.push(agent);
+        }
+        let kept = &listing.agents[0];
+        fs::create_dir_all(kept.path.join("src")).unwrap();
+        fs::create_dir_all(kept.path.join("target")).unwrap();
+        fs::write(kept.path.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(kept.path.join("target/binary"), "artefact").unwrap();
+
+        fs::write(source.experiment_config_path(), "(nb_agents: 2)").unwrap();
+        fs::create_dir_all(&target.workspace_dir).unwrap();
+        fs::write(target.experiment_config_path(), "(nb_agents: 9)").unwrap();
+
+        let archive = create_snapshot(&source, &listing, Some("round-trip")).unwrap();
+        let audit = AuditLog::open(&target.audit_log_path()).unwrap();
+        let restored = restore_snapshot(&archive, &target, &audit).unwrap();
+
+        assert_eq!(restored.agents.len(), 2);
+        let kept_dir = target.agent_dir(&restored.agents[0].island, "kept");
+        assert_eq!(restored.agents[0].path, kept_dir);
+        assert_eq!(
+            fs::read_to_string(kept_dir.join("src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+        assert!(!kept_dir.join("target").exists());
+        assert!(!restored.agents[1].path.exists());
+        assert_eq!(
+            fs::read_to_string(target.experiment_config_path()).unwrap(),
+            "(nb_agents: 2)"
+        );
+        let records = read_records(&target.audit_log_path()).unwrap().records;
+        assert!(matches!(
+            &records[0].event,
+            AuditEvent::SnapshotRestored { label, agents: 2, migrated_from: None, .. }
+                if label == "round-trip"
+        ));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    /// Archive au format v1 : listing binaire illisible aujourd'hui, dossiers `agents/<agent>`
+    fn write_v1_archive(path: &Path) {
+        let manifest = serde_json::json!({
+            "format_version": 1,
+            "label": "ancien",
+            "created_at": 1,
+            "listing_file": "listing_agents.bin",
+            "agents": [
+                { "name": "vivant", "id": "id-1", "state": "running", "energy": 640, "code_hash": "h1" },
+                { "name": "archive", "id": "id-2", "state": "archived", "energy": 0, "code_hash": "h2" },
+                { "name": "perdu", "id": "id-3", "state": "running", "energy": 10, "code_hash": "h3" },
+            ],
+        });
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(path).unwrap(),
+            Compression::default(),
+        ));
+        let manifest = serde_json::to_vec(&manifest).unwrap();
+        append_bytes(&mut builder, MANIFEST_FILE, &manifest, 1).unwrap();
+        append_bytes(&mut builder, "listing_agents.bin", b"\x01\x02", 1).unwrap();
+        for name in ["vivant", "archive"] {
+            let main = format!("agents/{}/src/main.rs", name);
+            append_bytes(&mut builder, &main, b"fn main() {}", 1).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn legacy_snapshot_is_rebuilt_from_its_manifest() {
+        let root = std::env::temp_dir().join(format!("snapshot-legacy-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let target = ProjectPaths::with_workspace(&root.join("target"));
+        fs::create_dir_all(&target.workspace_dir).unwrap();
+        let archive = root.join("ancien.tar.gz");
+        write_v1_archive(&archive);
+
+        let audit = AuditLog::open(&target.audit_log_path()).unwrap();
+        let restored = restore_snapshot(&archive, &target, &audit).unwrap();
+
+        // L'agent sans dossier est ignoré, les autres rejoignent l'île par défaut
+        let summary: Vec<(&str, AgentState, i32)> = restored
+            .agents
+            .iter()
+            .map(|agent| (agent.name.as_str(), agent.state(), agent.energy))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("vivant", AgentState::Created, 640),
+                ("archive", AgentState::Archived, 0)
+            ]
+        );
+        let dir = target.agent_dir(crate::island::DEFAULT_ISLAND, "vivant");
+        assert_eq!(restored.agents[0].path, dir);
+        assert!(dir.join("src/main.rs").is_file());
+        let reloaded =
+            crate::pipelines::initiate::listing::load_agents_listing(target.agent_listing_path())
+                .unwrap();
+        assert_eq!(reloaded.agents.len(), 2);
+        let records = read_records(&target.audit_log_path()).unwrap().records;
+        assert!(matches!(
+            records[0].event,
+            AuditEvent::SnapshotRestored {
+                migrated_from: Some(1),
+                ..
+            }
+        ));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::agent_listing::AgentsListing;
+use crate::audit::audit_log::AuditEvent;
+use crate::genome::GenomeConfig;
+use crate::genome_distance::{genome_distance, DistanceWeights};
+use crate::project_paths::ProjectPaths;
+use protocol::SpeciesSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Regroupement des agents en espèces (à la manière de NEAT)
+/// - compatibility_threshold: distance maximale au représentant d'une espèce
+/// - weights: poids des termes de la distance entre génomes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciationConfig {
+    pub compatibility_threshold: f64,
+    #[serde(default)]
+    pub weights: DistanceWeights,
+}
+
+impl Default for SpeciationConfig {
+    fn default() -> Self {
+        Self {
+            compatibility_threshold: 0.3,
+            weights: DistanceWeights::default(),
+        }
+    }
+}
+
+/// Espèce suivie d'un tour à l'autre : son représentant sert de référence
+/// pour y rattacher les agents
+struct Species {
+    representative: GenomeConfig,
+    representative_agent: String,
+    best_fitness: f64,
+}
+
+/// Statistiques d'une espèce à la fin d'un tour (agents vivants uniquement)
+/// - first_generation / last_generation: générations de ses membres
+/// - best_fitness: meilleure aptitude atteinte depuis l'apparition de l'espèce
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeciesStats {
+    pub representative: String,
+    pub size: usize,
+    pub mean_fitness: f64,
+    pub max_fitness: f64,
+    pub best_fitness: f64,
+    pub first_generation: u32,
+    pub last_generation: u32,
+}
+
+impl SpeciesStats {
+    pub fn summary(&self, id: u32) -> SpeciesSummary {
+        SpeciesSummary {
+            id,
+            representative: self.representative.clone(),
+            size: self.size,
+            mean_fitness: self.mean_fitness,
+            max_fitness: self.max_fitness,
+            best_fitness: self.best_fitness,
+            first_generation: self.first_generation,
+            last_generation: self.last_generation,
+        }
+    }
+
+    fn event(&self, id: u32) -> AuditEvent {
+        AuditEvent::SpeciesSummary {
+            species: id,
+            representative: self.representative.clone(),
+            size: self.size,
+            mean_fitness: self.mean_fitness,
+            max_fitness: self.max_fitness,
+            best_fitness: self.best_fitness,
+            first_generation: self.first_generation,
+            last_generation: self.last_generation,
+        }
+    }
+}
+
+/// Espèces de la population, mises à jour à chaque tour.
+/// Les numéros d'espèce sont conservés dans le listing (`AgentInfo::species`) :
+/// à la reprise, chaque espèce repart d'un de ses membres vivants.
+#[derive(Default)]
+pub struct SpeciesTracker {
+    species: BTreeMap<u32, Species>,
+    stats: BTreeMap<u32, SpeciesStats>,
+    next_id: u32,
+}
+
+impl SpeciesTracker {
+    /// Rattache chaque agent vivant à une espèce et retourne les événements d'audit
+    /// (résumés modifiés, espèces éteintes).
+    /// Un agent reste dans son espèce tant qu'il est assez proche de son représentant ;
+    /// sinon il rejoint la première espèce compatible, ou en fonde une nouvelle.
+    pub fn speciate(
+        &mut self,
+        listing: &mut AgentsListing,
+        genomes: &HashMap<String, GenomeConfig>,
+        config: &SpeciationConfig,
+    ) -> Vec<AuditEvent> {
+        let compatible = |species: &Species, genome: &GenomeConfig| {
+            genome_distance(&species.representative, genome, &config.weights)
+                <= config.compatibility_threshold
+        };
+
+        // Reprise : les numéros déjà présents dans le listing ne sont jamais réattribués,
+        // même si un agent sans espèce est traité avant leurs membres
+        let listed = listing
+            .agents
+            .iter()
+            .filter_map(|agent| agent.species)
+            .max();
+        if let Some(max) = listed {
+            self.next_id = self.next_id.max(max + 1);
+        }
+
+        for agent in listing.agents.iter_mut() {
+            if !agent.state().is_alive() {
+                continue;
+            }
+            let Some(genome) = genomes.get(&agent.name) else {
+                agent.species = None;
+                continue;
+            };
+
+            // Espèce connue du listing mais pas encore suivie (reprise) : l'agent la représente
+            if let Some(id) = agent.species.filter(|id| !self.species.contains_key(id)) {
+                self.species
+                    .insert(id, Species::founded_by(&agent.name, genome));
+            }
+            let current = agent
+                .species
+                .filter(|id| compatible(&self.species[id], genome));
+            let id = current
+                .or_else(|| {
+                    self.species
+                        .iter()
+                        .find(|(_, species)| compatible(species, genome))
+                        .map(|(id, _)| *id)
+                })
+                .unwrap_or_else(|| {
+                    let id = self.next_id.max(1);
+                    self.next_id = id + 1;
+                    self.species
+                        .insert(id, Species::founded_by(&agent.name, genome));
+                    id
+                });
+            agent.species = Some(id);
+        }
+
+        self.update_stats(listing, genomes)
+    }
+
+    /// Recalcule les statistiques, remplace les représentants disparus
+    /// et retire les espèces sans membre vivant
+    fn update_stats(
+        &mut self,
+        listing: &AgentsListing,
+        genomes: &HashMap<String, GenomeConfig>,
+    ) -> Vec<AuditEvent> {
+        let mut stats: BTreeMap<u32, SpeciesStats> = BTreeMap::new();
+        for agent in listing.agents.iter().filter(|a| a.state().is_alive()) {
+            let Some(id) = agent.species else {
+                continue;
+            };
+            let fitness = agent.fitness();
+            let generation = agent.lineage.generation;
+            let entry = stats.entry(id).or_insert_with(|| SpeciesStats {
+                representative: agent.name.clone(),
+                size: 0,
+                mean_fitness: 0.0,
+                max_fitness: f64::NEG_INFINITY,
+                best_fitness: f64::NEG_INFINITY,
+                first_generation: generation,
+                last_generation: generation,
+            });
+            entry.size += 1;
+            entry.mean_fitness += fitness;
+            entry.max_fitness = entry.max_fitness.max(fitness);
+            entry.first_generation = entry.first_generation.min(generation);
+            entry.last_generation = entry.last_generation.max(generation);
+        }
+
+        let mut events = Vec::new();
+        let extinct: Vec<u32> = self
+            .species
+            .keys()
+            .filter(|id| !stats.contains_key(id))
+            .copied()
+            .collect();
+        for id in extinct {
+            if let Some(species) = self.species.remove(&id) {
+                events.push(AuditEvent::SpeciesExtinct {
+                    species: id,
+                    best_fitness: species.best_fitness,
+                });
+            }
+        }
+
+        for (id, entry) in stats.iter_mut() {
+            let Some(species) = self.species.get_mut(id) else {
+                continue;
+            };
+            entry.mean_fitness /= entry.size as f64;
+            species.best_fitness = species.best_fitness.max(entry.max_fitness);
+            entry.best_fitness = species.best_fitness;
+
+            // Représentant mort : le premier membre vivant prend le relais
+            let representative_alive = listing.agents.iter().any(|a| {
+                a.name == species.representative_agent
+                    && a.state().is_alive()
+                    && a.species == Some(*id)
+            });
+            if representative_alive {
+                entry.representative = species.representative_agent.clone();
+            } else if let Some(genome) = genomes.get(&entry.representative) {
+                species.representative = genome.clone();
+                species.representative_agent = entry.representative.clone();
+            }
+
+            if self.stats.get(id) != Some(entry) {
+                events.push(entry.event(*id));
+            }
+        }
+        self.stats = stats;
+        events
+    }
+
+    /// Résumés des espèces vivantes, par numéro
+    pub fn summaries(&self) -> Vec<SpeciesSummary> {
+        self.stats
+            .iter()
+            .map(|(id, stats)| stats.summary(*id))
+            .collect()
+    }
+}
+
+impl Species {
+    fn founded_by(agent: &str, genome: &GenomeConfig) -> Self {
+        Self {
+            representative: genome.clone(),
+            representative_agent: agent.to_string(),
+            best_fitness: f64::NEG_INFINITY,
+        }
+    }
+}
+
+/// Génomes des agents vivants (genome.bin illisible : agent ignoré pour ce tour)
+pub fn living_genomes(
+    listing: &AgentsListing,
+    paths: &ProjectPaths,
+) -> HashMap<String, GenomeConfig> {
+    listing
+        .agents
+        .iter()
+        .filter(|agent| agent.state().is_alive())
+        .filter_map(|agent| {
+            let bytes = std::fs::read(paths.agent_genome_path(&agent.island, &agent.name)).ok()?;
+            let genome = bincode::deserialize(&bytes).ok()?;
+            Some((agent.name.clone(), genome))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_listing::AgentInfo;
+    use crate::genome::FileGene;
+
+    /// Génome de 4 fichiers dont les `active` premiers sont activés
+    fn genome(active: usize) -> GenomeConfig {
+        GenomeConfig {
+            files: (0..4)
+                .map(|i| FileGene {
+                    path: format!("src/{}.rs", i),
+                    active: i < active,
+                    functions: HashMap::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn population(genomes: &[(&str, usize)]) -> (AgentsListing, HashMap<String, GenomeConfig>) {
+        let mut listing = AgentsListing::for_test();
+        let mut by_name = HashMap::new();
+        for (name, active) in genomes {
+            listing.agents.push(AgentInfo::for_test(name));
+            by_name.insert(name.to_string(), genome(*active));
+        }
+        (listing, by_name)
+    }
+
+    fn species_of(listing: &AgentsListing) -> Vec<Option<u32>> {
+        listing.agents.iter().map(|agent| agent.species).collect()
+    }
+
+    #[test]
+    fn agents_within_threshold_share_a_species() {
+        // Un fichier sur 4 activé différemment : distance 0.25 (poids par défaut)
+        let (mut listing, genomes) = population(&[("a", 2), ("b", 3), ("c", 2)]);
+        let config = SpeciationConfig {
+            compatibility_threshold: 0.3,
+            ..SpeciationConfig::default()
+        };
+        SpeciesTracker::default().speciate(&mut listing, &genomes, &config);
+        assert_eq!(species_of(&listing), vec![Some(1), Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn agents_beyond_threshold_found_new_species() {
+        let (mut listing, genomes) = population(&[("a", 2), ("b", 3), ("c", 0)]);
+        let config = SpeciationConfig {
+            compatibility_threshold: 0.2,
+            ..SpeciationConfig::default()
+        };
+        let events = SpeciesTracker::default().speciate(&mut listing, &genomes, &config);
+        assert_eq!(species_of(&listing), vec![Some(1), Some(2), Some(3)]);
+        let summaries = events
+            .iter()
+            .filter(|event| matches!(event, AuditEvent::SpeciesSummary { .. }))
+            .count();
+        assert_eq!(summaries, 3);
+    }
+
+    #[test]
+    fn species_without_living_member_goes_extinct() {
+        let (mut listing, genomes) = population(&[("a", 0), ("b", 4)]);
+        let config = SpeciationConfig::default();
+        let mut tracker = SpeciesTracker::default();
+        tracker.speciate(&mut listing, &genomes, &config);
+        listing.agents[1]
+            .transition(crate::agent_state::AgentState::Dead, "mort")
+            .unwrap();
+        let events = tracker.speciate(&mut listing, &genomes, &config);
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, AuditEvent::SpeciesExtinct { species: 2, .. })));
+        assert_eq!(tracker.summaries().len(), 1);
+    }
+
+    #[test]
+    fn resumed_species_ids_are_not_reused() {
+        // Reprise : « a » n'a pas encore d'espèce, « b » et « c » gardent les leurs
+        let (mut listing, genomes) = population(&[("a", 2), ("b", 0), ("c", 4)]);
+        listing.agents[1].species = Some(1);
+        listing.agents[2].species = Some(4);
+        let config = SpeciationConfig {
+            compatibility_threshold: 0.2,
+            ..SpeciationConfig::default()
+        };
+        let mut tracker = SpeciesTracker::default();
+        tracker.speciate(&mut listing, &genomes, &config);
+        assert_eq!(species_of(&listing), vec![Some(5), Some(1), Some(4)]);
+
+        // Nouvelle espèce suivante : après le plus grand numéro connu
+        let mut genomes = genomes;
+        listing.agents.push(AgentInfo::for_test("d"));
+        genomes.insert("d".to_string(), genome(1));
+        tracker.speciate(&mut listing, &genomes, &config);
+        assert_eq!(listing.agents[3].species, Some(6));
+    }
+}
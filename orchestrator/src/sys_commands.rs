@@ -2,7 +2,7 @@ use std::process::{Child, Command};
 
 /// Commandes système bas niveau pour la gestion des processus :
 /// - Lancement de processus
-/// - Kill de processus
+/// - Kill de processus (SIGTERM pour un arrêt propre, SIGKILL sinon)
 /// - Vérification du statut (vivant/mort)
 
 #[allow(dead_code)]
@@ -14,6 +14,21 @@ pub fn kill_process(child: &mut Child) -> std::io::Result<()> {
     child.kill()
 }
 
+/// Demande au processus de s'arrêter (SIGTERM), sans attendre sa fin
+pub fn terminate_process(child: &Child) -> std::io::Result<()> {
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "kill -TERM {} a échoué",
+            child.id()
+        )))
+    }
+}
+
 /// Vérifie si un processus est toujours en vie
 /// Retourne None si vivant, Some(code) si terminé
 pub fn check_process_status(child: &mut Child) -> std::io::Result<Option<i32>> {
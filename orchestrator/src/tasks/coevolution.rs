@@ -0,0 +1,362 @@
+use crate::tasks::harness::{TaskConfig, TaskRound};
+use crate::tasks::suites::TaskInstance;
+use protocol::CoevolutionSummary;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Coévolution des problèmes : une population de problèmes évolue contre les agents.
+/// L'aptitude d'un problème est le taux d'échec des agents qui l'affrontent ; les
+/// meilleurs survivent, les autres sont remplacés par des mutants (nouvelle instance,
+/// difficulté perturbée). Le meilleur problème de chaque génération entre au
+/// panthéon (Hall of Fame), dont un échantillon est reposé à chaque évaluation
+/// pour que les agents ne puissent pas oublier les anciens problèmes (cycles).
+///
+/// Configuration (inactive par défaut)
+/// - population: problèmes de la population évolutive
+/// - survivors: part des meilleurs problèmes conservés à chaque génération
+/// - mutation_sigma: variation relative maximale de la difficulté d'un mutant
+/// - max_difficulty: plafond de la difficulté des mutants (au moins `MIN_DIFFICULTY`),
+///   sans lequel des problèmes trop durs pour tous finiraient par saturer la population
+/// - hall_of_fame: taille maximale du panthéon (les plus anciens sont oubliés)
+/// - hall_of_fame_samples: problèmes du panthéon reposés à chaque évaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoevolutionConfig {
+    pub enabled: bool,
+    pub population: usize,
+    pub survivors: f64,
+    pub mutation_sigma: f64,
+    pub max_difficulty: f64,
+    pub hall_of_fame: usize,
+    pub hall_of_fame_samples: usize,
+}
+
+impl Default for CoevolutionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            population: 8,
+            survivors: 0.5,
+            mutation_sigma: 0.25,
+            max_difficulty: 10.0,
+            hall_of_fame: 10,
+            hall_of_fame_samples: 3,
+        }
+    }
+}
+
+/// Difficulté minimale d'un mutant
+pub const MIN_DIFFICULTY: f64 = 0.1;
+
+/// Problème évolué : instance tirée de sa graine dans la suite de la configuration
+/// nommée `suite`, dont les paramètres sont multipliés par `difficulty`
+/// - fitness: taux d'échec des agents (0 à 1) lors de sa dernière évaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Challenge {
+    pub id: u64,
+    pub suite: String,
+    pub seed: u64,
+    pub difficulty: f64,
+    pub generation: u32,
+    pub fitness: Option<f64>,
+}
+
+impl Challenge {
+    /// Instance du problème (aucune si sa suite a disparu de la configuration)
+    fn instance(&self, config: &TaskConfig) -> Option<(usize, TaskInstance)> {
+        let index = suite_index(config, &self.suite)?;
+        let task = config.suites[index].suite.scaled(self.difficulty).build();
+        let instance = task.generate(&mut StdRng::seed_from_u64(self.seed));
+        Some((index, instance))
+    }
+}
+
+/// Position dans la configuration de la suite nommée `name` : les problèmes gardent
+/// leur suite si l'opérateur réordonne, ajoute ou retire des suites
+fn suite_index(config: &TaskConfig, name: &str) -> Option<usize> {
+    config
+        .suites
+        .iter()
+        .position(|suite| suite.suite.name() == name)
+}
+
+/// Population de problèmes et panthéon, sauvegardés avec le listing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoevolutionState {
+    pub generation: u32,
+    pub next_id: u64,
+    pub population: Vec<Challenge>,
+    pub hall_of_fame: Vec<Challenge>,
+}
+
+/// Problèmes d'une évaluation coévoluée : la population, puis l'échantillon du panthéon
+pub struct CoevolutionRound {
+    pub round: TaskRound,
+    challenges: Vec<Challenge>,
+    samples: usize,
+}
+
+impl CoevolutionState {
+    fn new_challenge(&mut self, suite: &str, difficulty: f64, rng: &mut StdRng) -> Challenge {
+        self.next_id += 1;
+        Challenge {
+            id: self.next_id,
+            suite: suite.to_string(),
+            seed: rng.gen(),
+            difficulty,
+            generation: self.generation,
+            fitness: None,
+        }
+    }
+
+    /// Problèmes de la génération courante (population créée à la première évaluation,
+    /// ou recréée si les suites ont changé) et échantillon du panthéon
+    pub fn prepare(&mut self, config: &TaskConfig, rng: &mut StdRng) -> CoevolutionRound {
+        let coevolution = &config.coevolution;
+        let suites = config.suites.len();
+        let configured = |challenge: &Challenge| suite_index(config, &challenge.suite).is_some();
+        self.population.retain(configured);
+        self.hall_of_fame.retain(configured);
+        if self.population.is_empty() && suites > 0 {
+            for index in 0..coevolution.population {
+                let suite = config.suites[index % suites].suite.name();
+                let challenge = self.new_challenge(suite, 1.0, rng);
+                self.population.push(challenge);
+            }
+        }
+
+        let samples = coevolution
+            .hall_of_fame_samples
+            .min(self.hall_of_fame.len());
+        let mut challenges = self.population.clone();
+        challenges.extend(
+            index::sample(rng, self.hall_of_fame.len(), samples)
+                .into_iter()
+                .map(|index| self.hall_of_fame[index].clone()),
+        );
+        let problems = challenges
+            .iter()
+            .filter_map(|challenge| challenge.instance(config))
+            .collect();
+        CoevolutionRound {
+            round: TaskRound::with_problems(config, problems),
+            challenges,
+            samples,
+        }
+    }
+
+    /// Fin d'une génération : aptitude des problèmes d'après les scores des agents
+    /// (un vecteur de scores par agent, dans l'ordre du tour), entrée du meilleur au
+    /// panthéon, puis sélection et mutation. Aucun résumé si aucun agent n'a été évalué.
+    pub fn advance(
+        &mut self,
+        config: &CoevolutionConfig,
+        round: CoevolutionRound,
+        scores: &[Vec<f64>],
+        rng: &mut StdRng,
+    ) -> Option<CoevolutionSummary> {
+        let population = round.challenges.len() - round.samples;
+        if scores.is_empty() || population == 0 {
+            return None;
+        }
+        let failure = |problem: usize| {
+            scores
+                .iter()
+                .map(|agent| 1.0 - agent.get(problem).copied().unwrap_or(0.0))
+                .sum::<f64>()
+                / scores.len() as f64
+        };
+        let mut evaluated: Vec<Challenge> = round.challenges[..population].to_vec();
+        for (problem, challenge) in evaluated.iter_mut().enumerate() {
+            challenge.fitness = Some(failure(problem));
+        }
+        let fitness = |challenge: &Challenge| challenge.fitness.unwrap_or(0.0);
+        evaluated.sort_by(|a, b| fitness(b).total_cmp(&fitness(a)));
+
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+        let failures: Vec<f64> = evaluated.iter().filter_map(|c| c.fitness).collect();
+        let hall_of_fame_failures: Vec<f64> =
+            (population..round.challenges.len()).map(failure).collect();
+        let agent_scores: Vec<f64> = scores.iter().map(|agent| mean(agent)).collect();
+        let summary = CoevolutionSummary {
+            generation: self.generation,
+            challenges: evaluated.len(),
+            mean_failure: mean(&failures),
+            best_failure: failures.first().copied().unwrap_or(0.0),
+            mean_difficulty: mean(&evaluated.iter().map(|c| c.difficulty).collect::<Vec<_>>()),
+            hall_of_fame_size: self.hall_of_fame.len(),
+            hall_of_fame_failure: (!hall_of_fame_failures.is_empty())
+                .then(|| mean(&hall_of_fame_failures)),
+            mean_agent_score: mean(&agent_scores),
+        };
+
+        if let Some(best) = evaluated.first() {
+            self.hall_of_fame.push(best.clone());
+            let excess = self.hall_of_fame.len().saturating_sub(config.hall_of_fame);
+            self.hall_of_fame.drain(..excess);
+        }
+
+        let kept = ((evaluated.len() as f64 * config.survivors.clamp(0.0, 1.0)).ceil() as usize)
+            .clamp(1, evaluated.len());
+        evaluated.truncate(kept);
+        self.generation += 1;
+        let sigma = config.mutation_sigma.abs();
+        let max_difficulty = config.max_difficulty.max(MIN_DIFFICULTY);
+        let mut next = evaluated.clone();
+        while next.len() < config.population {
+            let parent = &evaluated[rng.gen_range(0..evaluated.len())];
+            let factor = 1.0 + sigma * rng.gen_range(-1.0..=1.0);
+            let difficulty = (parent.difficulty * factor).clamp(MIN_DIFFICULTY, max_difficulty);
+            let suite = parent.suite.clone();
+            let child = self.new_challenge(&suite, difficulty, rng);
+            next.push(child);
+        }
+        self.population = next;
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::suites::{SuiteKind, TaskSuiteConfig};
+
+    fn task_config(coevolution: CoevolutionConfig) -> TaskConfig {
+        TaskConfig {
+            suites: vec![TaskSuiteConfig {
+                suite: SuiteKind::Arithmetic { max_operand: 10 },
+                instances: 1,
+                weight: 1.0,
+            }],
+            coevolution,
+            ..Default::default()
+        }
+    }
+
+    /// Une génération où tous les agents échouent partout (aptitude maximale des problèmes)
+    fn failed_generation(
+        state: &mut CoevolutionState,
+        config: &TaskConfig,
+        rng: &mut StdRng,
+    ) -> Option<CoevolutionSummary> {
+        let round = state.prepare(config, rng);
+        let scores = vec![vec![0.0; round.challenges.len()]; 2];
+        state.advance(&config.coevolution, round, &scores, rng)
+    }
+
+    #[test]
+    fn mutants_never_exceed_max_difficulty() {
+        let config = task_config(CoevolutionConfig {
+            enabled: true,
+            population: 6,
+            survivors: 0.5,
+            mutation_sigma: 3.0,
+            max_difficulty: 2.0,
+            ..Default::default()
+        });
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut state = CoevolutionState::default();
+        for _ in 0..30 {
+            failed_generation(&mut state, &config, &mut rng).unwrap();
+            assert!(state
+                .population
+                .iter()
+                .all(|challenge| { (MIN_DIFFICULTY..=2.0).contains(&challenge.difficulty) }));
+        }
+    }
+
+    #[test]
+    fn best_challenge_enters_a_bounded_hall_of_fame() {
+        let config = task_config(CoevolutionConfig {
+            enabled: true,
+            population: 4,
+            hall_of_fame: 2,
+            ..Default::default()
+        });
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut state = CoevolutionState::default();
+        for generation in 0..3 {
+            let summary = failed_generation(&mut state, &config, &mut rng).unwrap();
+            assert_eq!(summary.generation, generation);
+            assert_eq!(summary.mean_failure, 1.0);
+        }
+        assert_eq!(state.generation, 3);
+        assert_eq!(state.hall_of_fame.len(), 2);
+        assert_eq!(state.population.len(), 4);
+    }
+
+    #[test]
+    fn no_summary_without_scores() {
+        let config = task_config(CoevolutionConfig::default());
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut state = CoevolutionState::default();
+        let round = state.prepare(&config, &mut rng);
+        assert!(state
+            .advance(&config.coevolution, round, &[], &mut rng)
+            .is_none());
+        assert_eq!(state.generation, 0);
+    }
+
+    #[test]
+    fn challenges_follow_their_suite_when_suites_are_reordered() {
+        let mut config = task_config(CoevolutionConfig {
+            enabled: true,
+            population: 4,
+            ..Default::default()
+        });
+        config.suites.push(TaskSuiteConfig {
+            suite: SuiteKind::SequencePrediction {
+                length: 4,
+                max_step: 5,
+            },
+            instances: 1,
+            weight: 1.0,
+        });
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut state = CoevolutionState::default();
+        failed_generation(&mut state, &config, &mut rng).unwrap();
+
+        let ids: Vec<u64> = state.population.iter().map(|c| c.id).collect();
+        config.suites.reverse();
+        let round = state.prepare(&config, &mut rng);
+        assert_eq!(
+            ids,
+            state.population.iter().map(|c| c.id).collect::<Vec<_>>()
+        );
+        for challenge in &round.challenges {
+            let (index, _) = challenge.instance(&config).unwrap();
+            assert_eq!(config.suites[index].suite.name(), challenge.suite);
+        }
+
+        // Suite retirée : ses problèmes disparaissent de la population et du panthéon
+        config
+            .suites
+            .retain(|suite| suite.suite.name() == "arithmetic");
+        state.prepare(&config, &mut rng);
+        assert!(state
+            .population
+            .iter()
+            .chain(&state.hall_of_fame)
+            .all(|challenge| challenge.suite == "arithmetic"));
+    }
+
+    #[test]
+    fn nan_scores_do_not_abort_the_generation() {
+        let config = task_config(CoevolutionConfig {
+            enabled: true,
+            population: 3,
+            ..Default::default()
+        });
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut state = CoevolutionState::default();
+        let round = state.prepare(&config, &mut rng);
+        let mut scores = vec![vec![0.5; round.challenges.len()]; 2];
+        scores[0][1] = f64::NAN;
+        assert!(state
+            .advance(&config.coevolution, round, &scores, &mut rng)
+            .is_some());
+        assert_eq!(state.population.len(), 3);
+    }
+}
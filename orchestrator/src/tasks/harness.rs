@@ -0,0 +1,246 @@
+use crate::manage_agents_commands;
+use crate::tasks::coevolution::CoevolutionConfig;
+use crate::tasks::suites::{TaskInstance, TaskSuite, TaskSuiteConfig};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Harnais de tâches : l'agent est lancé avec `--tasks` et dialogue en JSON, une ligne par message
+/// - harnais → agent : `{"id": 3, "suite": "arithmetic", "input": {...}}`
+/// - agent → harnais : `{"id": 3, "answer": ...}` (les autres lignes de stdout sont ignorées)
+///
+/// Les problèmes sont posés un par un ; stdin est fermé après le dernier ou quand
+/// le budget de temps est épuisé (problèmes restants notés 0).
+///
+/// Configuration (vide par défaut : aucune évaluation)
+/// - suites: suites de problèmes posées à chaque évaluation
+/// - interval_ticks: tours entre deux évaluations des agents Running
+/// - time_budget_ms: temps accordé à un agent pour toute une évaluation (lancement compris)
+/// - fitness_weight: aptitude gagnée pour un score parfait
+/// - energy_income: énergie gagnée pour un score parfait
+/// - coevolution: problèmes évoluant contre les agents au lieu d'être tirés à chaque évaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskConfig {
+    pub suites: Vec<TaskSuiteConfig>,
+    pub interval_ticks: u64,
+    pub time_budget_ms: u64,
+    pub fitness_weight: f64,
+    pub energy_income: f64,
+    #[serde(default)]
+    pub coevolution: CoevolutionConfig,
+}
+
+impl Default for TaskConfig {
+    fn default() -> Self {
+        Self {
+            suites: Vec::new(),
+            interval_ticks: 12, // 1 minute à un tour toutes les 5 s
+            time_budget_ms: 2000,
+            fitness_weight: 1000.0,
+            energy_income: 100.0,
+            coevolution: CoevolutionConfig::default(),
+        }
+    }
+}
+
+impl TaskConfig {
+    /// Vérifie les suites configurées (paramètres et poids)
+    pub fn validate(&self) -> Result<(), String> {
+        for suite in &self.suites {
+            suite.suite.validate()?;
+            if !(suite.weight.is_finite() && suite.weight >= 0.0) {
+                return Err(format!(
+                    "{} : poids {} invalide (positif ou nul attendu)",
+                    suite.suite.name(),
+                    suite.weight
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Vrai si une évaluation a lieu à la fin du tour `tick` (compté à partir de 1)
+    pub fn is_due(&self, tick: u64) -> bool {
+        !self.suites.is_empty()
+            && self.interval_ticks > 0
+            && tick.is_multiple_of(self.interval_ticks)
+    }
+
+    pub fn time_budget(&self) -> Duration {
+        Duration::from_millis(self.time_budget_ms)
+    }
+}
+
+/// Problèmes d'une évaluation, les mêmes pour tous les agents du tour
+pub struct TaskRound {
+    suites: Vec<(&'static str, f64, Box<dyn TaskSuite>)>,
+    problems: Vec<(usize, TaskInstance)>,
+}
+
+impl TaskRound {
+    /// Tire les instances de chaque suite
+    pub fn prepare(config: &TaskConfig, rng: &mut StdRng) -> Self {
+        let mut round = Self::with_problems(config, Vec::new());
+        round.problems = config
+            .suites
+            .iter()
+            .enumerate()
+            .flat_map(|(index, suite)| (0..suite.instances).map(move |_| index))
+            .map(|index| (index, round.suites[index].2.generate(rng)))
+            .collect();
+        round
+    }
+
+    /// Tour aux problèmes imposés (indice de leur suite dans la configuration, instance)
+    pub fn with_problems(config: &TaskConfig, problems: Vec<(usize, TaskInstance)>) -> Self {
+        let suites = config
+            .suites
+            .iter()
+            .map(|suite| (suite.suite.name(), suite.weight, suite.suite.build()))
+            .collect();
+        Self { suites, problems }
+    }
+}
+
+/// Résultat de l'évaluation d'un agent
+/// - score: moyenne des suites pondérée par leur poids (0 à 1)
+/// - suite_scores: score moyen de chaque suite (problème sans réponse : 0)
+/// - problem_scores: score de chaque problème, dans l'ordre du tour
+/// - answered: problèmes ayant reçu une réponse
+/// - timed_out: budget de temps épuisé avant la fin
+#[derive(Debug, Clone)]
+pub struct TaskResult {
+    pub score: f64,
+    pub suite_scores: BTreeMap<String, f64>,
+    pub problem_scores: Vec<f64>,
+    pub answered: usize,
+    pub timed_out: bool,
+}
+
+#[derive(Serialize)]
+struct TaskRequest<'a> {
+    id: usize,
+    suite: &'a str,
+    input: &'a Value,
+}
+
+#[derive(Deserialize)]
+struct TaskAnswer {
+    id: usize,
+    answer: Value,
+}
+
+/// Évalue un agent sur les problèmes du tour, dans un processus dédié
+/// (stderr ajouté à `log_dir/stderr.log`)
+pub fn evaluate(
+    round: &TaskRound,
+    agent_path: &Path,
+    seed: u64,
+    log_dir: &Path,
+    budget: Duration,
+) -> io::Result<TaskResult> {
+    let deadline = Instant::now() + budget;
+    let mut child = manage_agents_commands::run_agent_tasks(agent_path, seed, log_dir)?;
+    let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(io::Error::other("stdin/stdout de l'agent indisponibles"));
+    };
+
+    // Lecture de stdout dans un thread : les réponses sont attendues avec un délai
+    let (lines, answers) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if lines.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut scores = vec![0.0; round.problems.len()];
+    let mut answered = 0;
+    let mut timed_out = false;
+    'problems: for (id, (suite, instance)) in round.problems.iter().enumerate() {
+        let (name, _, task) = &round.suites[*suite];
+        let request = TaskRequest {
+            id,
+            suite: name,
+            input: &instance.input,
+        };
+        let sent = serde_json::to_string(&request)
+            .map_err(io::Error::other)
+            .and_then(|line| writeln!(stdin, "{}", line))
+            .and_then(|_| stdin.flush());
+        if sent.is_err() {
+            break; // Agent terminé
+        }
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match answers.recv_timeout(remaining) {
+                Ok(line) => {
+                    let Ok(answer) = serde_json::from_str::<TaskAnswer>(&line) else {
+                        continue;
+                    };
+                    if answer.id == id {
+                        scores[id] = task.score(instance, &answer.answer).clamp(0.0, 1.0);
+                        answered += 1;
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    timed_out = true;
+                    break 'problems;
+                }
+                Err(RecvTimeoutError::Disconnected) => break 'problems,
+            }
+        }
+    }
+    drop(stdin);
+    let _ = child.kill();
+    let _ = child.wait();
+
+    Ok(round.result(&scores, answered, timed_out))
+}
+
+impl TaskRound {
+    /// Scores par suite et score global pondéré
+    fn result(&self, scores: &[f64], answered: usize, timed_out: bool) -> TaskResult {
+        let mut suite_scores = BTreeMap::new();
+        let mut weighted = 0.0;
+        let mut total_weight = 0.0;
+        for (index, (name, weight, _)) in self.suites.iter().enumerate() {
+            let suite: Vec<f64> = self
+                .problems
+                .iter()
+                .zip(scores)
+                .filter(|((suite, _), _)| *suite == index)
+                .map(|(_, score)| *score)
+                .collect();
+            if suite.is_empty() {
+                continue;
+            }
+            let mean = suite.iter().sum::<f64>() / suite.len() as f64;
+            suite_scores.insert(name.to_string(), mean);
+            weighted += weight * mean;
+            total_weight += weight;
+        }
+        TaskResult {
+            score: if total_weight > 0.0 {
+                weighted / total_weight
+            } else {
+                0.0
+            },
+            suite_scores,
+            problem_scores: scores.to_vec(),
+            answered,
+            timed_out,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod coevolution;
+pub mod harness;
+pub mod suites;
@@ -0,0 +1,390 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+
+/// Instance d'un problème : `input` est envoyé à l'agent, `expected` reste côté harnais
+#[derive(Debug, Clone)]
+pub struct TaskInstance {
+    pub input: Value,
+    pub expected: Value,
+}
+
+/// Suite de problèmes notés. Une nouvelle suite implémente ce trait
+/// et s'ajoute à `SuiteKind` pour être décrite dans experiment.ron.
+pub trait TaskSuite: Send + Sync {
+    /// Tire une instance (même `rng` = même instance)
+    fn generate(&self, rng: &mut StdRng) -> TaskInstance;
+    /// Note une réponse entre 0 (fausse ou illisible) et 1 (parfaite)
+    fn score(&self, instance: &TaskInstance, answer: &Value) -> f64;
+}
+
+/// Borne des opérandes et des pas : produits et termes tiennent dans un `i64`
+pub const MAX_OPERAND: i64 = 1_000_000_000;
+/// Borne de la longueur des suites à prédire
+pub const MAX_SEQUENCE_LENGTH: usize = 1000;
+/// Borne du côté des grilles (chaque mur posé relance un parcours de la grille)
+pub const MAX_GRID_SIZE: i64 = 100;
+
+/// Suites disponibles et leurs paramètres
+/// - sequence_prediction: terme suivant d'une suite arithmétique de `length` termes
+/// - arithmetic: résultat de `a op b` (+, -, *) avec des opérandes jusqu'à `max_operand`
+/// - grid_navigation: déplacements (`U`, `D`, `L`, `R`) du départ à l'arrivée d'une grille
+///   `size` × `size` dont une part `obstacle_density` des cases sont des murs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuiteKind {
+    SequencePrediction { length: usize, max_step: i64 },
+    Arithmetic { max_operand: i64 },
+    GridNavigation { size: i64, obstacle_density: f64 },
+}
+
+impl SuiteKind {
+    /// Nom de la suite (champ `suite` des problèmes envoyés et des scores journalisés)
+    pub fn name(&self) -> &'static str {
+        match self {
+            SuiteKind::SequencePrediction { .. } => "sequence_prediction",
+            SuiteKind::Arithmetic { .. } => "arithmetic",
+            SuiteKind::GridNavigation { .. } => "grid_navigation",
+        }
+    }
+
+    /// Vérifie les paramètres lus dans experiment.ron (sinon le tirage paniquerait)
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            SuiteKind::SequencePrediction { length, max_step } => {
+                if !(0..=MAX_OPERAND).contains(&max_step) {
+                    return Err(format!(
+                        "sequence_prediction : max_step {} hors de [0, {}]",
+                        max_step, MAX_OPERAND
+                    ));
+                }
+                if length > MAX_SEQUENCE_LENGTH {
+                    return Err(format!(
+                        "sequence_prediction : length {} au-delà de {}",
+                        length, MAX_SEQUENCE_LENGTH
+                    ));
+                }
+            }
+            SuiteKind::Arithmetic { max_operand } => {
+                if !(0..=MAX_OPERAND).contains(&max_operand) {
+                    return Err(format!(
+                        "arithmetic : max_operand {} hors de [0, {}]",
+                        max_operand, MAX_OPERAND
+                    ));
+                }
+            }
+            SuiteKind::GridNavigation {
+                size,
+                obstacle_density,
+            } => {
+                if !(1..=MAX_GRID_SIZE).contains(&size) {
+                    return Err(format!(
+                        "grid_navigation : size {} hors de [1, {}]",
+                        size, MAX_GRID_SIZE
+                    ));
+                }
+                if !(0.0..=1.0).contains(&obstacle_density) {
+                    return Err(format!(
+                        "grid_navigation : obstacle_density {} hors de [0, 1]",
+                        obstacle_density
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Suite dont les paramètres de taille sont multipliés par `difficulty`
+    /// (problèmes coévolués : opérandes, pas, côté de la grille), sans dépasser
+    /// les bornes de `validate`
+    pub fn scaled(&self, difficulty: f64) -> SuiteKind {
+        let scale =
+            |value: i64, max: i64| ((value as f64 * difficulty).round() as i64).clamp(1, max);
+        match *self {
+            SuiteKind::SequencePrediction { length, max_step } => SuiteKind::SequencePrediction {
+                length,
+                max_step: scale(max_step, MAX_OPERAND),
+            },
+            SuiteKind::Arithmetic { max_operand } => SuiteKind::Arithmetic {
+                max_operand: scale(max_operand, MAX_OPERAND),
+            },
+            SuiteKind::GridNavigation {
+                size,
+                obstacle_density,
+            } => SuiteKind::GridNavigation {
+                size: scale(size, MAX_GRID_SIZE),
+                obstacle_density,
+            },
+        }
+    }
+
+    pub fn build(&self) -> Box<dyn TaskSuite> {
+        match *self {
+            SuiteKind::SequencePrediction { length, max_step } => {
+                Box::new(SequencePrediction { length, max_step })
+            }
+            SuiteKind::Arithmetic { max_operand } => Box::new(Arithmetic { max_operand }),
+            SuiteKind::GridNavigation {
+                size,
+                obstacle_density,
+            } => Box::new(GridNavigation {
+                size,
+                obstacle_density,
+            }),
+        }
+    }
+}
+
+/// Suite configurée dans l'expérience
+/// - instances: problèmes posés à chaque évaluation
+/// - weight: poids de la suite dans le score global
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSuiteConfig {
+    pub suite: SuiteKind,
+    #[serde(default = "default_instances")]
+    pub instances: usize,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+}
+
+fn default_instances() -> usize {
+    5
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// Réponse exacte attendue : 1 si identique, 0 sinon
+fn exact(instance: &TaskInstance, answer: &Value) -> f64 {
+    match (answer.as_i64(), instance.expected.as_i64()) {
+        (Some(answer), Some(expected)) if answer == expected => 1.0,
+        _ => 0.0,
+    }
+}
+
+struct SequencePrediction {
+    length: usize,
+    max_step: i64,
+}
+
+impl TaskSuite for SequencePrediction {
+    fn generate(&self, rng: &mut StdRng) -> TaskInstance {
+        let start: i64 = rng.gen_range(-20..=20);
+        let max_step = self.max_step.max(0);
+        let step = rng.gen_range(-max_step..=max_step);
+        // Bornes de `validate` : aucun dépassement ; au-delà, termes en arithmétique modulaire
+        let term = |i: i64| start.wrapping_add(i.wrapping_mul(step));
+        let sequence: Vec<i64> = (0..self.length as i64).map(term).collect();
+        TaskInstance {
+            input: json!({ "sequence": sequence }),
+            expected: json!(term(self.length as i64)),
+        }
+    }
+
+    fn score(&self, instance: &TaskInstance, answer: &Value) -> f64 {
+        exact(instance, answer)
+    }
+}
+
+struct Arithmetic {
+    max_operand: i64,
+}
+
+impl TaskSuite for Arithmetic {
+    fn generate(&self, rng: &mut StdRng) -> TaskInstance {
+        let max_operand = self.max_operand.max(0);
+        let a = rng.gen_range(0..=max_operand);
+        let b = rng.gen_range(0..=max_operand);
+        // Opérandes bornés par `MAX_OPERAND` : le produit tient dans un i64 ;
+        // au-delà, le résultat attendu est celui de l'arithmétique modulaire (wrapping)
+        let (op, expected) = match rng.gen_range(0..3) {
+            0 => ("+", a.wrapping_add(b)),
+            1 => ("-", a.wrapping_sub(b)),
+            _ => ("*", a.wrapping_mul(b)),
+        };
+        TaskInstance {
+            input: json!({ "a": a, "op": op, "b": b }),
+            expected: json!(expected),
+        }
+    }
+
+    fn score(&self, instance: &TaskInstance, answer: &Value) -> f64 {
+        exact(instance, answer)
+    }
+}
+
+struct GridNavigation {
+    size: i64,
+    obstacle_density: f64,
+}
+
+/// Grille envoyée à l'agent (coordonnées `[x, y]`, origine en haut à gauche)
+#[derive(Debug, Serialize, Deserialize)]
+struct Grid {
+    size: i64,
+    walls: Vec<(i64, i64)>,
+    start: (i64, i64),
+    goal: (i64, i64),
+}
+
+impl Grid {
+    fn is_free(&self, (x, y): (i64, i64)) -> bool {
+        (0..self.size).contains(&x) && (0..self.size).contains(&y) && !self.walls.contains(&(x, y))
+    }
+
+    /// Longueur du plus court chemin (parcours en largeur), aucune si l'arrivée est inaccessible
+    fn shortest_path(&self) -> Option<usize> {
+        let mut seen = HashSet::from([self.start]);
+        let mut queue = VecDeque::from([(self.start, 0)]);
+        while let Some((cell, distance)) = queue.pop_front() {
+            if cell == self.goal {
+                return Some(distance);
+            }
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let next = (cell.0 + dx, cell.1 + dy);
+                if self.is_free(next) && seen.insert(next) {
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl TaskSuite for GridNavigation {
+    fn generate(&self, rng: &mut StdRng) -> TaskInstance {
+        let size = self.size.max(2);
+        let corner = (size - 1, size - 1);
+        let mut grid = Grid {
+            size,
+            walls: Vec::new(),
+            start: (0, 0),
+            goal: corner,
+        };
+        // Les murs qui couperaient le chemin sont retirés
+        for y in 0..size {
+            for x in 0..size {
+                if (x, y) != grid.start
+                    && (x, y) != grid.goal
+                    && rng.gen_bool(self.obstacle_density.clamp(0.0, 1.0))
+                {
+                    grid.walls.push((x, y));
+                    if grid.shortest_path().is_none() {
+                        grid.walls.pop();
+                    }
+                }
+            }
+        }
+        let shortest = grid.shortest_path().unwrap_or(0);
+        TaskInstance {
+            input: serde_json::to_value(&grid).unwrap_or_default(),
+            expected: json!(shortest),
+        }
+    }
+
+    /// 1 pour un plus court chemin, moins pour un chemin plus long, 0 s'il heurte un mur
+    /// ou n'atteint pas l'arrivée
+    fn score(&self, instance: &TaskInstance, answer: &Value) -> f64 {
+        let (Ok(grid), Some(moves), Some(shortest)) = (
+            serde_json::from_value::<Grid>(instance.input.clone()),
+            answer.as_str(),
+            instance.expected.as_u64(),
+        ) else {
+            return 0.0;
+        };
+        let mut cell = grid.start;
+        for step in moves.chars() {
+            let (dx, dy) = match step {
+                'U' => (0, -1),
+                'D' => (0, 1),
+                'L' => (-1, 0),
+                'R' => (1, 0),
+                _ => return 0.0,
+            };
+            cell = (cell.0 + dx, cell.1 + dy);
+            if !grid.is_free(cell) {
+                return 0.0;
+            }
+        }
+        if cell != grid.goal {
+            return 0.0;
+        }
+        (shortest as f64 / moves.len().max(1) as f64).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let invalid = [
+            SuiteKind::SequencePrediction {
+                length: 5,
+                max_step: -1,
+            },
+            SuiteKind::SequencePrediction {
+                length: MAX_SEQUENCE_LENGTH + 1,
+                max_step: 3,
+            },
+            SuiteKind::Arithmetic { max_operand: -5 },
+            SuiteKind::Arithmetic {
+                max_operand: MAX_OPERAND + 1,
+            },
+            SuiteKind::GridNavigation {
+                size: 0,
+                obstacle_density: 0.2,
+            },
+            SuiteKind::GridNavigation {
+                size: 5,
+                obstacle_density: 1.5,
+            },
+        ];
+        for suite in invalid {
+            assert!(suite.validate().is_err(), "{:?}", suite);
+        }
+        assert!(SuiteKind::Arithmetic { max_operand: 0 }.validate().is_ok());
+    }
+
+    #[test]
+    fn largest_operands_do_not_overflow() {
+        let suite = SuiteKind::Arithmetic {
+            max_operand: MAX_OPERAND,
+        }
+        .build();
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..100 {
+            let instance = suite.generate(&mut rng);
+            let (a, b) = (instance.input["a"].as_i64(), instance.input["b"].as_i64());
+            let expected = match instance.input["op"].as_str() {
+                Some("+") => a.unwrap().checked_add(b.unwrap()),
+                Some("-") => a.unwrap().checked_sub(b.unwrap()),
+                _ => a.unwrap().checked_mul(b.unwrap()),
+            };
+            assert_eq!(instance.expected.as_i64(), expected);
+            assert_eq!(suite.score(&instance, &instance.expected), 1.0);
+        }
+    }
+
+    #[test]
+    fn scaling_stays_within_bounds() {
+        let scaled = SuiteKind::GridNavigation {
+            size: 50,
+            obstacle_density: 0.2,
+        }
+        .scaled(1000.0);
+        assert!(scaled.validate().is_ok());
+        let scaled = SuiteKind::Arithmetic { max_operand: 10 }.scaled(f64::MAX);
+        assert_eq!(
+            scaled,
+            SuiteKind::Arithmetic {
+                max_operand: MAX_OPERAND
+            }
+        );
+    }
+}
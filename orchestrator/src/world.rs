@@ -0,0 +1,462 @@
+use crate::agent_listing::AgentsListing;
+use crate::agent_state::AgentState;
+use crate::audit::audit_log::AuditEvent;
+use crate::ipc::messages::OrchestratorMessage;
+use protocol::{WorldInteractionKind, WorldView};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Monde simulé partagé : une grille torique dont certaines cases portent des
+/// ressources qui se régénèrent. Les agents Running y sont placés au hasard,
+/// perçoivent leur voisinage et agissent par le canal IPC ; les ressources
+/// consommées deviennent de l'énergie. Quand le monde est actif, il remplace la
+/// perte d'énergie liée au code par un coût de vie (`metabolism`) à chaque tour.
+///
+/// Configuration (inactif par défaut)
+/// - width / height: dimensions de la grille
+/// - fertility: part des cases portant des ressources
+/// - max_resource: ressources maximales d'une case fertile
+/// - regen_per_tick: ressources regagnées par case fertile à chaque tour
+/// - metabolism: énergie perdue par chaque agent présent à chaque tour
+/// - perception_radius: rayon (en cases) du voisinage perçu
+/// - max_actions_per_tick: actions acceptées par agent et par tour
+/// - consume_max: ressources consommées au plus par action `consume`
+/// - move_cost: énergie payée pour un déplacement
+/// - attack_cost / attack_damage: coût d'une attaque et énergie prise à la cible
+///   (l'attaquant en récupère la moitié)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldConfig {
+    pub enabled: bool,
+    pub width: u32,
+    pub height: u32,
+    pub fertility: f64,
+    pub max_resource: u32,
+    pub regen_per_tick: u32,
+    pub metabolism: i32,
+    pub perception_radius: u32,
+    pub max_actions_per_tick: u32,
+    pub consume_max: u32,
+    pub move_cost: i32,
+    pub attack_cost: i32,
+    pub attack_damage: i32,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: 16,
+            height: 16,
+            fertility: 0.3,
+            max_resource: 200,
+            regen_per_tick: 10,
+            metabolism: 20,
+            perception_radius: 2,
+            max_actions_per_tick: 3,
+            consume_max: 50,
+            move_cost: 2,
+            attack_cost: 20,
+            attack_damage: 100,
+        }
+    }
+}
+
+/// Borne du nombre de cases de la grille (capacités et ressources en mémoire)
+pub const MAX_CELLS: u64 = 1_000_000;
+
+impl WorldConfig {
+    /// Vérifie les dimensions et la fertilité lues dans experiment.ron
+    /// (sinon la génération et le placement des agents paniqueraient)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format!(
+                "Monde de dimensions nulles : {} x {}",
+                self.width, self.height
+            ));
+        }
+        if self.width as u64 * self.height as u64 > MAX_CELLS {
+            return Err(format!(
+                "Monde de {} x {} cases, au-delà de {}",
+                self.width, self.height, MAX_CELLS
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.fertility) {
+            return Err(format!("Fertilité {} hors de [0, 1]", self.fertility));
+        }
+        Ok(())
+    }
+}
+
+/// Actions d'un agent dans le monde (message `{"type": "act", "action": "move", ...}`)
+/// - move: déplacement d'au plus une case par axe
+/// - consume: ressources de sa case converties en énergie
+/// - share: énergie donnée à un agent voisin
+/// - attack: énergie prise à un agent voisin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum WorldAction {
+    Move { dx: i32, dy: i32 },
+    Consume { amount: u32 },
+    Share { to: String, amount: i32 },
+    Attack { target: String },
+}
+
+/// Case perçue par un agent, relativement à sa position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenCell {
+    pub dx: i32,
+    pub dy: i32,
+    pub resource: u32,
+}
+
+/// Agent voisin perçu, relativement à sa position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenAgent {
+    pub agent: String,
+    pub dx: i32,
+    pub dy: i32,
+}
+
+/// État du monde, sauvegardé avec le listing (et donc dans les snapshots)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorldState {
+    pub width: u32,
+    pub height: u32,
+    pub capacity: Vec<u32>,
+    pub resources: Vec<u32>,
+    pub positions: BTreeMap<String, (u32, u32)>,
+    // Actions reçues pendant le tour, par agent
+    #[serde(skip)]
+    actions: HashMap<String, u32>,
+}
+
+impl WorldState {
+    /// Grille neuve : cases fertiles tirées au hasard, pleines de ressources
+    fn generate(config: &WorldConfig, rng: &mut impl Rng) -> Self {
+        let cells = (config.width * config.height) as usize;
+        let fertility = config.fertility.clamp(0.0, 1.0);
+        let capacity: Vec<u32> = (0..cells)
+            .map(|_| {
+                if rng.gen_bool(fertility) {
+                    config.max_resource
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Self {
+            width: config.width,
+            height: config.height,
+            resources: capacity.clone(),
+            capacity,
+            positions: BTreeMap::new(),
+            actions: HashMap::new(),
+        }
+    }
+
+    fn index(&self, (x, y): (u32, u32)) -> usize {
+        (y * self.width + x) as usize
+    }
+
+    /// Case atteinte depuis `(x, y)` en se décalant de `(dx, dy)` (grille torique)
+    fn offset(&self, (x, y): (u32, u32), dx: i32, dy: i32) -> (u32, u32) {
+        (
+            (x as i64 + dx as i64).rem_euclid(self.width as i64) as u32,
+            (y as i64 + dy as i64).rem_euclid(self.height as i64) as u32,
+        )
+    }
+
+    /// Décalage le plus court de `from` vers `to` sur chaque axe
+    fn delta(&self, from: (u32, u32), to: (u32, u32)) -> (i32, i32) {
+        let axis = |a: u32, b: u32, size: u32| {
+            let d = (b as i64 - a as i64).rem_euclid(size as i64);
+            if d > size as i64 / 2 {
+                (d - size as i64) as i32
+            } else {
+                d as i32
+            }
+        };
+        (
+            axis(from.0, to.0, self.width),
+            axis(from.1, to.1, self.height),
+        )
+    }
+
+    /// Deux agents sont voisins s'ils sont sur la même case ou sur des cases adjacentes
+    fn adjacent(&self, a: &str, b: &str) -> bool {
+        match (self.positions.get(a), self.positions.get(b)) {
+            (Some(&pa), Some(&pb)) => {
+                let (dx, dy) = self.delta(pa, pb);
+                dx.abs() <= 1 && dy.abs() <= 1
+            }
+            _ => false,
+        }
+    }
+
+    /// Ressources totales présentes dans le monde
+    pub fn total_resources(&self) -> u64 {
+        self.resources.iter().map(|&r| r as u64).sum()
+    }
+
+    /// Voisinage perçu par un agent présent dans le monde
+    pub fn perception(&self, agent: &str, energy: i32, radius: u32) -> Option<OrchestratorMessage> {
+        let &position = self.positions.get(agent)?;
+        let radius = radius as i32;
+        let mut cells = Vec::new();
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let resource = self.resources[self.index(self.offset(position, dx, dy))];
+                cells.push(SeenCell { dx, dy, resource });
+            }
+        }
+        let agents = self
+            .positions
+            .iter()
+            .filter(|(name, _)| name.as_str() != agent)
+            .map(|(name, &other)| (name, self.delta(position, other)))
+            .filter(|(_, (dx, dy))| dx.abs() <= radius && dy.abs() <= radius)
+            .map(|(name, (dx, dy))| SeenAgent {
+                agent: name.clone(),
+                dx,
+                dy,
+            })
+            .collect();
+        Some(OrchestratorMessage::Perception {
+            x: position.0,
+            y: position.1,
+            energy,
+            cells,
+            agents,
+        })
+    }
+
+    /// Vue complète du monde (API de contrôle)
+    pub fn view(&self) -> WorldView {
+        WorldView {
+            width: self.width,
+            height: self.height,
+            resources: self
+                .resources
+                .chunks(self.width.max(1) as usize)
+                .map(|row| row.to_vec())
+                .collect(),
+            total_resources: self.total_resources(),
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+/// Un tour du monde : création au premier tour, agents Running placés (les autres
+/// retirés), régénération des ressources et coût de vie des agents présents
+pub fn step(listing: &mut AgentsListing, config: &WorldConfig, rng: &mut impl Rng) {
+    let world = &mut listing.world;
+    if world.width != config.width || world.height != config.height || world.capacity.is_empty() {
+        *world = WorldState::generate(config, rng);
+    }
+    world.actions.clear();
+
+    world.positions.retain(|name, _| {
+        listing
+            .agents
+            .iter()
+            .any(|a| &a.name == name && a.state() == AgentState::Running)
+    });
+    for agent in listing.agents.iter_mut() {
+        if agent.state() != AgentState::Running {
+            continue;
+        }
+        if !world.positions.contains_key(&agent.name) {
+            let position = (
+                rng.gen_range(0..world.width),
+                rng.gen_range(0..world.height),
+            );
+            world.positions.insert(agent.name.clone(), position);
+        }
+        agent.energy -= config.metabolism;
+    }
+
+    for (resource, capacity) in world.resources.iter_mut().zip(&world.capacity) {
+        *resource = (*resource + config.regen_per_tick).min(*capacity);
+    }
+}
+
+/// Applique l'action d'un agent présent dans le monde. Retourne l'événement
+/// d'audit des interactions entre agents (partage, attaque).
+pub fn act(
+    listing: &mut AgentsListing,
+    name: &str,
+    action: WorldAction,
+    config: &WorldConfig,
+) -> Result<Option<AuditEvent>, String> {
+    let world = &mut listing.world;
+    let Some(&position) = world.positions.get(name) else {
+        return Err(format!("Agent {} absent du monde", name));
+    };
+    let count = world.actions.entry(name.to_string()).or_default();
+    if *count >= config.max_actions_per_tick {
+        return Err(format!("Trop d'actions ce tour ({})", count));
+    }
+    *count += 1;
+
+    let index_of = |agents: &AgentsListing, name: &str| {
+        agents
+            .agents
+            .iter()
+            .position(|a| a.name == name && a.state() == AgentState::Running)
+    };
+    let actor = index_of(listing, name).ok_or_else(|| format!("Agent {} non Running", name))?;
+
+    match action {
+        WorldAction::Move { dx, dy } => {
+            let world = &mut listing.world;
+            let target = world.offset(position, dx.clamp(-1, 1), dy.clamp(-1, 1));
+            world.positions.insert(name.to_string(), target);
+            listing.agents[actor].energy -= config.move_cost;
+            Ok(None)
+        }
+        WorldAction::Consume { amount } => {
+            let world = &mut listing.world;
+            let cell = world.index(position);
+            let taken = amount.min(config.consume_max).min(world.resources[cell]);
+            world.resources[cell] -= taken;
+            listing.agents[actor].energy += taken as i32;
+            Ok(None)
+        }
+        WorldAction::Share { to, amount } => {
+            if !listing.world.adjacent(name, &to) {
+                return Err(format!("{} n'est pas voisin de {}", to, name));
+            }
+            let target = index_of(listing, &to).ok_or_else(|| format!("Agent {} absent", to))?;
+            let amount = amount.min(listing.agents[actor].energy);
+            if amount <= 0 {
+                return Err(format!("Quantité invalide : {}", amount));
+            }
+            listing.agents[actor].energy -= amount;
+            listing.agents[target].energy += amount;
+            Ok(Some(AuditEvent::WorldInteraction {
+                agent: name.to_string(),
+                target: to,
+                action: WorldInteractionKind::Share,
+                amount,
+            }))
+        }
+        WorldAction::Attack { target } => {
+            if !listing.world.adjacent(name, &target) {
+                return Err(format!("{} n'est pas voisin de {}", target, name));
+            }
+            let victim =
+                index_of(listing, &target).ok_or_else(|| format!("Agent {} absent", target))?;
+            let damage = config
+                .attack_damage
+                .min(listing.agents[victim].energy.max(0));
+            listing.agents[victim].energy -= damage;
+            listing.agents[actor].energy += damage / 2 - config.attack_cost;
+            Ok(Some(AuditEvent::WorldInteraction {
+                agent: name.to_string(),
+                target,
+                action: WorldInteractionKind::Attack,
+                amount: damage,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_listing::AgentInfo;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn world_config(width: u32, height: u32) -> WorldConfig {
+        WorldConfig {
+            enabled: true,
+            width,
+            height,
+            fertility: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn populated(config: &WorldConfig, names: &[&str]) -> AgentsListing {
+        let mut listing = AgentsListing::for_test();
+        for name in names {
+            listing.agents.push(AgentInfo::for_test(name));
+        }
+        step(&mut listing, config, &mut StdRng::seed_from_u64(1));
+        listing
+    }
+
+    #[test]
+    fn invalid_dimensions_and_fertility_are_rejected() {
+        assert!(world_config(0, 4).validate().is_err());
+        assert!(world_config(4, 0).validate().is_err());
+        assert!(world_config(100_000, 100_000).validate().is_err());
+        let mut config = world_config(4, 4);
+        config.fertility = 1.5;
+        assert!(config.validate().is_err());
+        config.fertility = -0.1;
+        assert!(config.validate().is_err());
+        assert!(WorldConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn grid_wraps_and_deltas_take_the_short_way() {
+        let listing = populated(&world_config(5, 4), &[]);
+        let world = &listing.world;
+        assert_eq!(world.offset((0, 0), -1, -1), (4, 3));
+        assert_eq!(world.offset((4, 3), 1, 1), (0, 0));
+        assert_eq!(world.delta((0, 0), (4, 3)), (-1, -1));
+        assert_eq!(world.delta((0, 0), (2, 2)), (2, 2));
+    }
+
+    #[test]
+    fn step_places_running_agents_and_charges_metabolism() {
+        let config = world_config(3, 3);
+        let listing = populated(&config, &["a", "b"]);
+        for agent in &listing.agents {
+            let &(x, y) = listing.world.positions.get(&agent.name).unwrap();
+            assert!(x < 3 && y < 3);
+            assert_eq!(agent.energy, 1000 - config.metabolism);
+        }
+    }
+
+    #[test]
+    fn consume_converts_resources_into_energy() {
+        let config = world_config(2, 2);
+        let mut listing = populated(&config, &["a"]);
+        let before = listing.agents[0].energy;
+        act(
+            &mut listing,
+            "a",
+            WorldAction::Consume { amount: 500 },
+            &config,
+        )
+        .unwrap();
+        assert_eq!(listing.agents[0].energy, before + config.consume_max as i32);
+    }
+
+    #[test]
+    fn interactions_require_neighbours_and_respect_the_action_budget() {
+        let config = world_config(9, 9);
+        let mut listing = populated(&config, &["a", "b"]);
+        listing.world.positions.insert("a".into(), (0, 0));
+        listing.world.positions.insert("b".into(), (4, 4));
+        let share = |to: &str| WorldAction::Share {
+            to: to.into(),
+            amount: 10,
+        };
+        assert!(act(&mut listing, "a", share("b"), &config).is_err());
+
+        listing.world.positions.insert("b".into(), (8, 8));
+        let event = act(&mut listing, "a", share("b"), &config).unwrap();
+        assert!(matches!(
+            event,
+            Some(AuditEvent::WorldInteraction { amount: 10, .. })
+        ));
+        act(&mut listing, "a", share("b"), &config).unwrap();
+        // max_actions_per_tick = 3 : la quatrième action du tour est refusée
+        assert!(act(&mut listing, "a", share("b"), &config).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::commands::Command;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Fichier des jetons d'accès, régénéré à chaque démarrage de l'orchestrateur
+/// (relatif au workspace ; lu par la passerelle `ws`)
+pub const ACCESS_TOKENS_FILE: &str = "access_tokens.json";
+
+/// Rôle accordé par un jeton : lecture seule, ou actions sur la population
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    Viewer,
+    Operator,
+}
+
+impl Command {
+    /// Rôle minimal pour exécuter la commande
+    pub fn required_role(&self) -> Role {
+        if self.is_mutating() || self.is_privileged() {
+            Role::Operator
+        } else {
+            Role::Viewer
+        }
+    }
+}
+
+/// Jetons partagés (un par rôle)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessTokens {
+    pub viewer: String,
+    pub operator: String,
+}
+
+impl AccessTokens {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(std::io::Error::from)
+    }
+
+    /// Rôle accordé par un jeton (None : jeton inconnu)
+    pub fn role_of(&self, token: &str) -> Option<Role> {
+        if constant_time_eq(token, &self.operator) {
+            Some(Role::Operator)
+        } else if constant_time_eq(token, &self.viewer) {
+            Some(Role::Viewer)
+        } else {
+            None
+        }
+    }
+}
+
+/// Comparaison dont la durée ne dépend pas de la position de la première différence
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Gène d'un fichier source : actif ou non, avec ses fonctions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneView {
+    pub path: String,
+    pub active: bool,
+    pub functions: BTreeMap<String, bool>,
+}
+
+/// Génome d'un agent (réponse de `GetGenome`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenomeView {
+    pub files: Vec<GeneView>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// Bloc de différences, avec son en-tête au format unifié (`@@ -a,b +c,d @@`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDiff {
+    pub path: String,
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// Différences de source d'un agent (réponse de `GetSourceDiff`)
+/// - reference: ce à quoi l'agent est comparé (`parent <nom>` ou `template`)
+/// - files: fichiers modifiés uniquement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceDiff {
+    pub reference: String,
+    pub files: Vec<FileDiff>,
+}
+
+/// Dernières lignes des sorties d'un agent (réponse de `GetLogs`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentLogs {
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+}
+
+/// Mesure de ressources d'un agent (réponse de `GetResourceHistory`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub timestamp: u64,
+    pub memory_mb: u64,
+    pub cpu_percent: f64,
+}
+
+/// Canal IPC d'un agent (réponse de `GetAgentChannel`)
+/// - connected: l'agent est connecté à sa socket
+/// - last_seen: horodatage de son dernier message (absent depuis le dernier lancement)
+/// - metrics: dernières mesures qu'il a déclarées
+/// - logs: dernières lignes de journal envoyées sur le canal
+/// - topics: sujets auxquels il est abonné
+/// - messages: statistiques de ses échanges avec les autres agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentChannel {
+    pub connected: bool,
+    pub last_seen: Option<u64>,
+    pub metrics: BTreeMap<String, f64>,
+    pub logs: Vec<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub messages: MessageStats,
+}
+
+/// Messages échangés par un agent via le broker de l'orchestrateur
+/// - sent / bytes_sent: messages remis au broker (un par destinataire) et leur taille
+/// - received: messages délivrés à l'agent
+/// - rejected: envois refusés (taille, débit, énergie ou destinataire inconnu)
+/// - dropped: messages perdus, sa boîte aux lettres étant pleine
+/// - energy_spent: énergie payée pour ses envois
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageStats {
+    pub sent: u64,
+    pub bytes_sent: u64,
+    pub received: u64,
+    pub rejected: u64,
+    pub dropped: u64,
+    pub energy_spent: i64,
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// États du cycle de vie d'un agent (champ `state` de `AgentSummary`, événement `state_changed`)
+/// - Created: agent initialisé, jamais compilé
+/// - Building: compilation / (re)lancement en cours
+/// - Running: processus actif
+/// - Backoff: attente avant relance (après crash ou fin de processus)
+/// - Crashed: processus terminé anormalement
+/// - Paused: suspendu par un opérateur (processus arrêté, ignoré par le cycle de vie)
+/// - Quarantined: code jugé dangereux, exécuté à nouveau seulement si un opérateur le relance
+/// - Dead: éliminé (sélection, ressources, énergie, compilation...) ; seule une dérogation
+///   explicite et auditée de l'opérateur peut le relancer
+/// - Archived: conservé pour l'historique uniquement
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentState {
+    #[default]
+    Created,
+    Building,
+    Running,
+    Backoff,
+    Crashed,
+    Paused,
+    Quarantined,
+    Dead,
+    Archived,
+}
+
+impl AgentState {
+    /// Tous les états, dans l'ordre du cycle de vie
+    pub const ALL: [AgentState; 9] = [
+        AgentState::Created,
+        AgentState::Building,
+        AgentState::Running,
+        AgentState::Backoff,
+        AgentState::Crashed,
+        AgentState::Paused,
+        AgentState::Quarantined,
+        AgentState::Dead,
+        AgentState::Archived,
+    ];
+
+    /// Table des transitions autorisées (seul endroit où elles sont définies)
+    pub fn can_transition_to(self, next: AgentState) -> bool {
+        use AgentState::*;
+        matches!(
+            (self, next),
+            (Created, Building | Paused | Quarantined | Dead)
+                | (Building, Running | Crashed | Paused | Quarantined | Dead)
+                | (
+                    Running,
+                    Building | Backoff | Crashed | Paused | Quarantined | Dead
+                )
+                | (Crashed, Backoff | Dead)
+                | (Backoff, Building | Paused | Quarantined | Dead)
+                | (Paused, Building | Dead)
+                | (Quarantined, Building | Dead | Archived)
+                | (Dead, Archived)
+        )
+    }
+
+    /// Nom de l'état tel que sérialisé (`running`, `dead`...)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgentState::Created => "created",
+            AgentState::Building => "building",
+            AgentState::Running => "running",
+            AgentState::Backoff => "backoff",
+            AgentState::Crashed => "crashed",
+            AgentState::Paused => "paused",
+            AgentState::Quarantined => "quarantined",
+            AgentState::Dead => "dead",
+            AgentState::Archived => "archived",
+        }
+    }
+
+    /// Un agent vivant peut encore être (re)lancé par le cycle de vie
+    pub fn is_alive(self) -> bool {
+        !matches!(
+            self,
+            AgentState::Quarantined | AgentState::Dead | AgentState::Archived
+        )
+    }
+}
+
+impl fmt::Display for AgentState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
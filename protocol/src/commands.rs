@@ -0,0 +1,308 @@
+use crate::agent_state::AgentState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Commandes opérateur, exécutées par le cycle de vie de l'orchestrateur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    ListAgents,
+    GetAgent {
+        name: String,
+    },
+    GetGenome {
+        name: String,
+    },
+    /// Tue un agent ; refusée sans `confirm: true`
+    Kill {
+        name: String,
+        #[serde(default)]
+        confirm: bool,
+    },
+    Pause {
+        name: String,
+    },
+    /// Reprend un agent en pause
+    Resume {
+        name: String,
+    },
+    /// Relance un agent mort (dérogation de l'opérateur), en quarantaine ou en attente ;
+    /// refusée sans `confirm: true`
+    Revive {
+        name: String,
+        #[serde(default)]
+        confirm: bool,
+    },
+    /// Crée un nouvel agent (génération suivante) à partir du code d'un agent
+    Clone {
+        name: String,
+    },
+    Snapshot {
+        label: Option<String>,
+    },
+    GetConfig,
+    ListGenerations,
+    ListSpecies,
+    /// Front de Pareto de chaque génération (sélection multi-objectif)
+    ListParetoFronts,
+    /// Ressources et positions des agents du monde simulé
+    GetWorld,
+    /// Résumé de chaque génération de problèmes coévolués
+    ListCoevolution,
+    /// Différences de source avec le parent (ou le template)
+    GetSourceDiff {
+        name: String,
+    },
+    /// Dernières `lines` lignes de stdout/stderr
+    GetLogs {
+        name: String,
+        lines: usize,
+    },
+    GetResourceHistory {
+        name: String,
+    },
+    /// Derniers `limit` événements du journal concernant l'agent
+    GetAgentEvents {
+        name: String,
+        limit: usize,
+    },
+    /// Canal IPC de l'agent : connexion, dernières mesures et lignes de journal
+    GetAgentChannel {
+        name: String,
+    },
+    GetSelectionParams,
+    SetSelectionParams {
+        params: SelectionParams,
+    },
+    /// Refus d'accès constaté par la passerelle ws (connexion, jeton, rôle, chemin),
+    /// consigné par l'API de contrôle dans le journal d'audit (événement `access_denied`).
+    /// Envoyée par la passerelle seule : elle ne relaie pas celles de ses clients.
+    ReportAccessDenied {
+        operator: String,
+        action: String,
+        reason: String,
+    },
+}
+
+impl Command {
+    /// Nom de la commande tel que sérialisé (ex: `kill`, `set_selection_params`)
+    pub fn name(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.get("command")?.as_str().map(String::from))
+            .unwrap_or_default()
+    }
+
+    /// Vrai pour les commandes irréversibles ou dérogatoires (kill, revive)
+    /// envoyées sans `confirm: true` : elles sont refusées par l'orchestrateur
+    pub fn lacks_confirmation(&self) -> bool {
+        matches!(
+            self,
+            Command::Kill { confirm: false, .. } | Command::Revive { confirm: false, .. }
+        )
+    }
+
+    /// Vrai pour les commandes réservées au rôle operator sans modifier la population
+    /// (non journalisées comme actions d'opérateur)
+    pub fn is_privileged(&self) -> bool {
+        matches!(self, Command::ReportAccessDenied { .. })
+    }
+
+    /// Vrai pour les commandes qui modifient la population ou l'expérience
+    /// (journalisées avec le nom de l'opérateur)
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Command::Kill { .. }
+                | Command::Pause { .. }
+                | Command::Resume { .. }
+                | Command::Revive { .. }
+                | Command::Clone { .. }
+                | Command::Snapshot { .. }
+                | Command::SetSelectionParams { .. }
+        )
+    }
+}
+
+/// Paramètres de sélection naturelle modifiables pendant l'exécution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectionParams {
+    pub penalty_if_not_safe: u32,
+    pub penalty_if_not_valid: u32,
+    pub penalty_if_resource_exceeded: u32,
+    pub penalty_per_unwrap: u32,
+    pub max_score: u32,
+}
+
+/// Vue résumée d'un agent (réponse de `ListAgents` et des actions sur un agent)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentSummary {
+    pub name: String,
+    pub id: String,
+    #[serde(default)]
+    pub island: String,
+    #[serde(default)]
+    pub species: Option<u32>,
+    #[serde(default)]
+    pub novelty: Option<f64>,
+    #[serde(default)]
+    pub pareto_front: Option<u32>,
+    #[serde(default)]
+    pub task_score: Option<f64>,
+    pub state: AgentState,
+    pub state_since: u64,
+    pub energy: i32,
+    pub fitness: f64,
+    pub crash_count: u32,
+    pub generation: u32,
+}
+
+/// Résumé d'une génération (réponse de `ListGenerations`, événement `generation_summary`)
+/// - diversity: part de génotypes distincts parmi les agents vivants (0 à 1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationSummary {
+    pub generation: u32,
+    pub alive: usize,
+    pub dead: usize,
+    pub mean_fitness: f64,
+    pub max_fitness: f64,
+    #[serde(default)]
+    pub diversity: f64,
+}
+
+/// Résumé d'une espèce (réponse de `ListSpecies`, événement `species_summary`)
+/// - representative: agent dont le génome sert de référence à l'espèce
+/// - best_fitness: meilleure aptitude atteinte depuis l'apparition de l'espèce
+/// - first_generation / last_generation: générations de ses membres vivants
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesSummary {
+    pub id: u32,
+    pub representative: String,
+    pub size: usize,
+    pub mean_fitness: f64,
+    pub max_fitness: f64,
+    pub best_fitness: f64,
+    pub first_generation: u32,
+    pub last_generation: u32,
+}
+
+/// Front de Pareto d'une génération (réponse de `ListParetoFronts`, événement `pareto_front`) :
+/// agents vivants de la génération qu'aucun autre ne domine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParetoFront {
+    pub generation: u32,
+    pub members: Vec<ParetoMember>,
+}
+
+/// Membre d'un front de Pareto et valeur brute de chaque objectif
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParetoMember {
+    pub agent: String,
+    pub objectives: BTreeMap<String, f64>,
+}
+
+/// Monde simulé (réponse de `GetWorld`)
+/// - resources: ressources de chaque case, ligne par ligne (`resources[y][x]`)
+/// - total_resources: somme des ressources du monde
+/// - positions: case `(x, y)` de chaque agent présent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldView {
+    pub width: u32,
+    pub height: u32,
+    pub resources: Vec<Vec<u32>>,
+    pub total_resources: u64,
+    pub positions: BTreeMap<String, (u32, u32)>,
+}
+
+/// Génération de problèmes coévolués (réponse de `ListCoevolution`, événement
+/// `coevolution_generation`)
+/// - mean_failure / best_failure: taux d'échec des agents, moyen et sur le problème le plus dur
+/// - mean_difficulty: facteur de difficulté moyen des problèmes
+/// - hall_of_fame_failure: taux d'échec sur l'échantillon du panthéon (aucun s'il est vide)
+/// - mean_agent_score: score moyen des agents sur tous les problèmes du tour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoevolutionSummary {
+    pub generation: u32,
+    pub challenges: usize,
+    pub mean_failure: f64,
+    pub best_failure: f64,
+    pub mean_difficulty: f64,
+    pub hall_of_fame_size: usize,
+    pub hall_of_fame_failure: Option<f64>,
+    pub mean_agent_score: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_survive_a_json_round_trip() {
+        let commands = vec![
+            Command::ListAgents,
+            Command::Kill {
+                name: "a".into(),
+                confirm: true,
+            },
+            Command::GetLogs {
+                name: "a".into(),
+                lines: 20,
+            },
+            Command::SetSelectionParams {
+                params: SelectionParams {
+                    penalty_if_not_safe: 1000,
+                    penalty_if_not_valid: 1000,
+                    penalty_if_resource_exceeded: 500,
+                    penalty_per_unwrap: 5,
+                    max_score: 1000,
+                },
+            },
+        ];
+        for command in commands {
+            let json = serde_json::to_string(&command).unwrap();
+            assert_eq!(serde_json::from_str::<Command>(&json).unwrap(), command);
+        }
+        let kill: Command = serde_json::from_str(r#"{"command":"kill","name":"a"}"#).unwrap();
+        assert_eq!(kill.name(), "kill");
+        assert!(kill.lacks_confirmation() && kill.is_mutating());
+    }
+
+    #[test]
+    fn gateway_reports_need_the_operator_role_without_being_operator_actions() {
+        let report = Command::ReportAccessDenied {
+            operator: "ws 127.0.0.1:5000 (déclaré : alice)".into(),
+            action: "hello".into(),
+            reason: "Jeton d'accès manquant ou invalide".into(),
+        };
+        assert_eq!(report.name(), "report_access_denied");
+        assert_eq!(report.required_role(), crate::Role::Operator);
+        assert!(report.is_privileged() && !report.is_mutating());
+    }
+
+    #[test]
+    fn agent_summary_carries_a_typed_state() {
+        let summary = AgentSummary {
+            name: "a".into(),
+            id: "id-a".into(),
+            island: "main".into(),
+            species: Some(2),
+            novelty: None,
+            pareto_front: Some(0),
+            task_score: Some(0.5),
+            state: AgentState::Backoff,
+            state_since: 10,
+            energy: 900,
+            fitness: 900.5,
+            crash_count: 1,
+            generation: 3,
+        };
+        let value = serde_json::to_value(&summary).unwrap();
+        assert_eq!(value["state"], "backoff");
+        assert_eq!(
+            serde_json::from_value::<AgentSummary>(value).unwrap(),
+            summary
+        );
+        let unknown = r#"{"name":"a","id":"i","state":"zombie","state_since":0,"energy":0,"fitness":0.0,"crash_count":0,"generation":0}"#;
+        assert!(serde_json::from_str::<AgentSummary>(unknown).is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use crate::agent_state::AgentState;
+use crate::commands::ParetoMember;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Une ligne du journal d'événements de l'orchestrateur (logs/audit_log.jsonl) :
+/// numéro de séquence monotone + horodatage + événement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub seq: u64,
+    pub timestamp: u64, // Timestamp UNIX
+    pub event: AuditEvent,
+}
+
+impl EventRecord {
+    /// Type de l'événement (`state_changed`, `killed`, `generation_summary`...)
+    pub fn kind(&self) -> &'static str {
+        self.event.kind()
+    }
+
+    /// Agent concerné, absent pour les résumés de génération
+    pub fn agent(&self) -> Option<&str> {
+        self.event.agent()
+    }
+}
+
+/// Événements du cycle de vie enregistrés dans le journal d'audit de l'orchestrateur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    AgentCreated {
+        agent: String,
+        id: String,
+        path: PathBuf,
+        energy: i32,
+        // Absente des journaux antérieurs aux graines reproductibles
+        #[serde(default)]
+        seed: u64,
+        #[serde(default)]
+        generation: u32,
+        #[serde(default)]
+        island: String,
+    },
+    ScanResult {
+        agent: String,
+        code_hash: String,
+        changed_files: Vec<String>,
+    },
+    Validity {
+        agent: String,
+        is_safe: bool,
+        is_valid: bool,
+    },
+    SelectionScore {
+        agent: String,
+        score: u32,
+        max_score: u32,
+        energy: i32,
+        survived: bool,
+    },
+    Crash {
+        agent: String,
+        crash_count: u32,
+    },
+    StateChanged {
+        agent: String,
+        from: AgentState,
+        to: AgentState,
+        reason: String,
+    },
+    Killed {
+        agent: String,
+        reason: String,
+    },
+    /// Résumé d'une génération, émis quand il change d'un tour à l'autre
+    GenerationSummary {
+        generation: u32,
+        alive: usize,
+        dead: usize,
+        mean_fitness: f64,
+        max_fitness: f64,
+        #[serde(default)]
+        diversity: f64,
+    },
+    /// Résumé d'une espèce, émis quand il change d'un tour à l'autre
+    SpeciesSummary {
+        species: u32,
+        representative: String,
+        size: usize,
+        mean_fitness: f64,
+        max_fitness: f64,
+        best_fitness: f64,
+        first_generation: u32,
+        last_generation: u32,
+    },
+    /// Espèce sans plus aucun membre vivant
+    SpeciesExtinct {
+        species: u32,
+        best_fitness: f64,
+    },
+    /// Diversité d'une génération (`generation` absent : population entière),
+    /// émise quand elle change d'un tour à l'autre
+    DiversityMetrics {
+        generation: Option<u32>,
+        alive: usize,
+        mean_distance: f64,
+        unique_code_hashes: usize,
+        mean_entropy: f64,
+        gene_entropy: BTreeMap<String, f64>,
+    },
+    /// Mesure prise (ou levée) face à une diversité trop faible
+    DiversityIntervention {
+        intervention: DiversityIntervention,
+        mean_distance: f64,
+        detail: String,
+    },
+    /// Front de Pareto d'une génération, émis quand il change d'un tour à l'autre
+    ParetoFront {
+        generation: u32,
+        members: Vec<ParetoMember>,
+    },
+    /// Génération de problèmes coévolués évaluée contre les agents
+    CoevolutionGeneration {
+        generation: u32,
+        challenges: usize,
+        mean_failure: f64,
+        best_failure: f64,
+        mean_difficulty: f64,
+        hall_of_fame_size: usize,
+        hall_of_fame_failure: Option<f64>,
+        mean_agent_score: f64,
+    },
+    /// Évaluation d'un agent par le harnais de tâches (`energy` : énergie après gain)
+    TaskEvaluated {
+        agent: String,
+        score: f64,
+        suite_scores: BTreeMap<String, f64>,
+        answered: usize,
+        timed_out: bool,
+        energy: i32,
+    },
+    /// Demande reçue sur le canal IPC d'un agent (`energy` : énergie après traitement)
+    AgentRequest {
+        agent: String,
+        request: String,
+        granted: bool,
+        detail: String,
+        energy: i32,
+    },
+    /// Agent connecté à son canal IPC mais silencieux depuis `silent_secs`
+    HeartbeatLost {
+        agent: String,
+        silent_secs: u64,
+        action: HungAction,
+    },
+    /// Partage ou attaque entre deux agents voisins dans le monde simulé
+    /// (`amount` : énergie donnée ou prise)
+    WorldInteraction {
+        agent: String,
+        target: String,
+        action: WorldInteractionKind,
+        amount: i32,
+    },
+    /// Variation de l'énergie d'un agent (`energy` : énergie après la variation,
+    /// `reason` : étape du cycle de vie ou commande qui l'a provoquée)
+    EnergyChanged {
+        agent: String,
+        delta: i32,
+        energy: i32,
+        reason: String,
+    },
+    /// Comportement d'un agent ajouté à l'archive de nouveauté
+    NoveltyArchived {
+        agent: String,
+        novelty: f64,
+        archive_size: usize,
+    },
+    /// Copie d'un agent (`source`) envoyée vers une autre île ; `agent` est la copie
+    Migrated {
+        agent: String,
+        source: String,
+        from_island: String,
+        to_island: String,
+    },
+    /// Commande d'un opérateur modifiant la population ou l'expérience
+    /// (`detail` : paramètres de la commande, ou message d'erreur en cas d'échec)
+    OperatorAction {
+        operator: String,
+        action: String,
+        #[serde(default)]
+        agent: Option<String>,
+        success: bool,
+        detail: String,
+    },
+    /// Population restaurée depuis un snapshot (`migrated_from` : version d'origine
+    /// d'une archive reconstruite depuis son manifeste, absente pour le format courant)
+    SnapshotRestored {
+        label: String,
+        archive: String,
+        agents: usize,
+        #[serde(default)]
+        migrated_from: Option<u32>,
+    },
+    /// Requête refusée par le contrôle d'accès de l'API (jeton absent, invalide ou insuffisant)
+    AccessDenied {
+        operator: String,
+        action: String,
+        reason: String,
+    },
+}
+
+impl AuditEvent {
+    /// Type de l'événement tel que sérialisé (`state_changed`, `killed`...)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuditEvent::AgentCreated { .. } => "agent_created",
+            AuditEvent::ScanResult { .. } => "scan_result",
+            AuditEvent::Validity { .. } => "validity",
+            AuditEvent::SelectionScore { .. } => "selection_score",
+            AuditEvent::Crash { .. } => "crash",
+            AuditEvent::StateChanged { .. } => "state_changed",
+            AuditEvent::Killed { .. } => "killed",
+            AuditEvent::GenerationSummary { .. } => "generation_summary",
+            AuditEvent::SpeciesSummary { .. } => "species_summary",
+            AuditEvent::SpeciesExtinct { .. } => "species_extinct",
+            AuditEvent::DiversityMetrics { .. } => "diversity_metrics",
+            AuditEvent::DiversityIntervention { .. } => "diversity_intervention",
+            AuditEvent::ParetoFront { .. } => "pareto_front",
+            AuditEvent::CoevolutionGeneration { .. } => "coevolution_generation",
+            AuditEvent::TaskEvaluated { .. } => "task_evaluated",
+            AuditEvent::AgentRequest { .. } => "agent_request",
+            AuditEvent::HeartbeatLost { .. } => "heartbeat_lost",
+            AuditEvent::WorldInteraction { .. } => "world_interaction",
+            AuditEvent::EnergyChanged { .. } => "energy_changed",
+            AuditEvent::NoveltyArchived { .. } => "novelty_archived",
+            AuditEvent::Migrated { .. } => "migrated",
+            AuditEvent::OperatorAction { .. } => "operator_action",
+            AuditEvent::SnapshotRestored { .. } => "snapshot_restored",
+            AuditEvent::AccessDenied { .. } => "access_denied",
+        }
+    }
+
+    /// Nom de l'agent concerné par l'événement (aucun pour les résumés de génération)
+    pub fn agent(&self) -> Option<&str> {
+        let agent = match self {
+            AuditEvent::AgentCreated { agent, .. }
+            | AuditEvent::ScanResult { agent, .. }
+            | AuditEvent::Validity { agent, .. }
+            | AuditEvent::SelectionScore { agent, .. }
+            | AuditEvent::Crash { agent, .. }
+            | AuditEvent::StateChanged { agent, .. }
+            | AuditEvent::Killed { agent, .. }
+            | AuditEvent::Migrated { agent, .. }
+            | AuditEvent::NoveltyArchived { agent, .. }
+            | AuditEvent::TaskEvaluated { agent, .. }
+            | AuditEvent::AgentRequest { agent, .. }
+            | AuditEvent::HeartbeatLost { agent, .. }
+            | AuditEvent::WorldInteraction { agent, .. }
+            | AuditEvent::EnergyChanged { agent, .. } => agent,
+            AuditEvent::OperatorAction { agent, .. } => return agent.as_deref(),
+            AuditEvent::GenerationSummary { .. }
+            | AuditEvent::SpeciesSummary { .. }
+            | AuditEvent::SpeciesExtinct { .. }
+            | AuditEvent::DiversityMetrics { .. }
+            | AuditEvent::DiversityIntervention { .. }
+            | AuditEvent::ParetoFront { .. }
+            | AuditEvent::CoevolutionGeneration { .. }
+            | AuditEvent::SnapshotRestored { .. }
+            | AuditEvent::AccessDenied { .. } => return None,
+        };
+        Some(agent)
+    }
+}
+
+/// Mesures prises quand la diversité de la population devient trop faible
+/// - raise_mutation_rate: les clones et migrants sont mutés avec `boosted_mutation_rate`
+/// - random_immigrants: `immigrants` nouveaux agents tirés du template
+/// - reseed_from_archive: `immigrants` agents archivés, les plus éloignés
+///   de la population vivante, sont clonés dans leur île
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiversityIntervention {
+    RaiseMutationRate,
+    RandomImmigrants,
+    ReseedFromArchive,
+}
+
+/// Réaction à un agent bloqué (connecté, puis silencieux trop longtemps)
+/// - restart: compté comme un crash (backoff puis relance, Dead au-delà de max_retries)
+/// - kill: agent Dead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HungAction {
+    #[default]
+    Restart,
+    Kill,
+}
+
+/// Interaction entre deux agents voisins du monde simulé
+/// - share: énergie donnée à la cible
+/// - attack: énergie prise à la cible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldInteractionKind {
+    Share,
+    Attack,
+}
+
+/// Sujets auxquels un client peut s'abonner
+/// - all: tous les événements (abonnement par défaut)
+/// - agent: les événements d'un agent donné
+/// - deaths: les morts d'agents uniquement
+/// - generations: les résumés de génération
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    All,
+    Agent(String),
+    Deaths,
+    Generations,
+}
+
+impl Topic {
+    pub fn matches(&self, record: &EventRecord) -> bool {
+        match self {
+            Topic::All => true,
+            Topic::Agent(name) => record.agent() == Some(name.as_str()),
+            Topic::Deaths => matches!(record.event, AuditEvent::Killed { .. }),
+            Topic::Generations => matches!(record.event, AuditEvent::GenerationSummary { .. }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seq: u64, event: AuditEvent) -> EventRecord {
+        EventRecord {
+            seq,
+            timestamp: 1_700_000_000 + seq,
+            event,
+        }
+    }
+
+    fn samples() -> Vec<EventRecord> {
+        vec![
+            record(
+                0,
+                AuditEvent::StateChanged {
+                    agent: "a".into(),
+                    from: AgentState::Running,
+                    to: AgentState::Dead,
+                    reason: "énergie épuisée".into(),
+                },
+            ),
+            record(
+                1,
+                AuditEvent::DiversityIntervention {
+                    intervention: DiversityIntervention::RandomImmigrants,
+                    mean_distance: 0.05,
+                    detail: "2 immigrants".into(),
+                },
+            ),
+            record(
+                2,
+                AuditEvent::HeartbeatLost {
+                    agent: "b".into(),
+                    silent_secs: 30,
+                    action: HungAction::Kill,
+                },
+            ),
+            record(
+                3,
+                AuditEvent::WorldInteraction {
+                    agent: "a".into(),
+                    target: "b".into(),
+                    action: WorldInteractionKind::Attack,
+                    amount: 12,
+                },
+            ),
+            record(
+                4,
+                AuditEvent::GenerationSummary {
+                    generation: 3,
+                    alive: 4,
+                    dead: 1,
+                    mean_fitness: 850.5,
+                    max_fitness: 1200.0,
+                    diversity: 0.75,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn event_records_survive_a_json_round_trip() {
+        for record in samples() {
+            let line = serde_json::to_string(&record).unwrap();
+            let parsed: EventRecord = serde_json::from_str(&line).unwrap();
+            assert_eq!(parsed, record);
+            let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(value["event"]["type"], record.kind(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn journal_lines_keep_their_snake_case_shape() {
+        let line = r#"{"seq":7,"timestamp":42,"event":{"type":"state_changed","agent":"a","from":"running","to":"quarantined","reason":"code dangereux"}}"#;
+        let record: EventRecord = serde_json::from_str(line).unwrap();
+        assert_eq!(record.agent(), Some("a"));
+        assert!(matches!(
+            record.event,
+            AuditEvent::StateChanged {
+                to: AgentState::Quarantined,
+                ..
+            }
+        ));
+        // Champs absents des anciens journaux
+        let line = r#"{"seq":0,"timestamp":1,"event":{"type":"agent_created","agent":"a","id":"id-a","path":"agents/a","energy":1000}}"#;
+        let record: EventRecord = serde_json::from_str(line).unwrap();
+        assert!(matches!(
+            record.event,
+            AuditEvent::AgentCreated {
+                seed: 0,
+                generation: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn topics_select_events_by_variant() {
+        let records = samples();
+        let killed = record(
+            5,
+            AuditEvent::Killed {
+                agent: "a".into(),
+                reason: "tué".into(),
+            },
+        );
+        assert!(Topic::Deaths.matches(&killed));
+        assert!(!records.iter().any(|r| Topic::Deaths.matches(r)));
+        let generations: Vec<u64> = records
+            .iter()
+            .filter(|r| Topic::Generations.matches(r))
+            .map(|r| r.seq)
+            .collect();
+        assert_eq!(generations, vec![4]);
+        let agent_b: Vec<u64> = records
+            .iter()
+            .filter(|r| Topic::Agent("b".into()).matches(r))
+            .map(|r| r.seq)
+            .collect();
+        assert_eq!(agent_b, vec![2]);
+    }
+}
@@ -0,0 +1,27 @@
+//! Protocole partagé entre l'orchestrateur, la passerelle `ws` et l'interface `ui`.
+//! Tous les messages sont des JSON tagués (`"type"`), sérialisés avec serde.
+
+mod access;
+mod agent_detail;
+mod agent_state;
+mod commands;
+mod events;
+mod messages;
+
+pub use access::{AccessTokens, Role, ACCESS_TOKENS_FILE};
+pub use agent_detail::{
+    AgentChannel, AgentLogs, DiffHunk, DiffLine, DiffLineKind, FileDiff, GeneView, GenomeView,
+    MessageStats, ResourceSample, SourceDiff,
+};
+pub use agent_state::AgentState;
+pub use commands::{
+    AgentSummary, CoevolutionSummary, Command, GenerationSummary, ParetoFront, ParetoMember,
+    SelectionParams, SpeciesSummary, WorldView,
+};
+pub use events::{
+    AuditEvent, DiversityIntervention, EventRecord, HungAction, Topic, WorldInteractionKind,
+};
+pub use messages::{ClientMessage, ServerMessage};
+
+/// Version du protocole, vérifiée lors de la poignée de main (`Hello` / `Welcome`)
+pub const PROTOCOL_VERSION: u32 = 1;
@@ -0,0 +1,70 @@
+use crate::access::Role;
+use crate::commands::Command;
+use crate::events::{EventRecord, Topic};
+use serde::{Deserialize, Serialize};
+
+/// Messages envoyés par un client à la passerelle.
+/// Le premier message doit être `Hello` avec la version du protocole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// `operator` : nom enregistré dans le journal d'audit pour les commandes du client.
+    /// `token` : jeton d'accès (fichier `access_tokens.json` de l'orchestrateur)
+    Hello {
+        version: u32,
+        #[serde(default)]
+        operator: Option<String>,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    Subscribe {
+        topic: Topic,
+    },
+    Unsubscribe {
+        topic: Topic,
+    },
+    /// `id` est renvoyé dans la `Response` ou l'`Error` correspondante
+    Command {
+        id: u64,
+        command: Command,
+    },
+}
+
+/// Messages envoyés par la passerelle à un client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// `role` : droits accordés par le jeton présenté
+    Welcome {
+        version: u32,
+        #[serde(default)]
+        role: Role,
+    },
+    Subscriptions {
+        topics: Vec<Topic>,
+    },
+    Event {
+        record: EventRecord,
+    },
+    Response {
+        id: u64,
+        data: serde_json::Value,
+    },
+    /// `id` absent pour les erreurs qui ne concernent pas une commande
+    Error {
+        id: Option<u64>,
+        message: String,
+    },
+}
+
+impl ClientMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("ClientMessage toujours sérialisable")
+    }
+}
+
+impl ServerMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("ServerMessage toujours sérialisable")
+    }
+}
@@ -0,0 +1,218 @@
+use crate::dashboard::charts::{LineChart, Series, XAxis};
+use crate::dashboard::controls::AgentActions;
+use crate::dashboard::model::AgentDetail;
+use crate::gateway::GatewayHandle;
+use gloo_timers::callback::Interval;
+use protocol::{AgentState, Command, DiffLineKind, ResourceSample};
+use yew::prelude::*;
+
+/// Lignes de stdout/stderr et événements demandés à l'orchestrateur
+const LOG_LINES: usize = 100;
+const EVENT_LIMIT: usize = 200;
+/// Rafraîchissement des sorties et des ressources (ms)
+const REFRESH_MS: u32 = 5000;
+
+#[derive(Properties, PartialEq)]
+pub struct AgentDetailProps {
+    pub detail: AgentDetail,
+    /// État courant de l'agent (None s'il n'est pas encore dans la liste)
+    pub state: Option<AgentState>,
+    /// Actions proposées (rôle operator)
+    pub can_act: bool,
+    pub on_close: Callback<()>,
+}
+
+/// Vue détaillée d'un agent : gènes, différences de source, sorties,
+/// ressources et historique d'événements
+#[function_component(AgentDetailView)]
+pub fn agent_detail_view(props: &AgentDetailProps) -> Html {
+    let gateway = use_context::<Option<GatewayHandle>>().flatten();
+
+    {
+        let name = props.detail.name.clone();
+        use_effect_with((name, gateway), |(name, gateway)| {
+            let refresh = gateway.clone().map(|gateway| {
+                let name = name.clone();
+                gateway.command(Command::GetGenome { name: name.clone() });
+                gateway.command(Command::GetSourceDiff { name: name.clone() });
+                gateway.command(Command::GetAgentEvents {
+                    name: name.clone(),
+                    limit: EVENT_LIMIT,
+                });
+                let fetch_live = move || {
+                    gateway.command(Command::GetLogs {
+                        name: name.clone(),
+                        lines: LOG_LINES,
+                    });
+                    gateway.command(Command::GetResourceHistory { name: name.clone() });
+                };
+                fetch_live();
+                Interval::new(REFRESH_MS, fetch_live)
+            });
+            move || drop(refresh)
+        });
+    }
+
+    let detail = &props.detail;
+    let on_close = props.on_close.reform(|_| ());
+
+    html! {
+        <section class="detail">
+            <header>
+                <h2 class="mono">{ &detail.name }</h2>
+                if let Some(state) = props.state.as_ref().filter(|_| props.can_act) {
+                    <AgentActions name={detail.name.clone()} state={*state} />
+                }
+                <button onclick={on_close}>{ "Fermer" }</button>
+            </header>
+            <div class="detail-grid">
+                { genes(detail) }
+                { resources(&detail.resources) }
+            </div>
+            { source_diff(detail) }
+            { logs(detail) }
+            { events(detail) }
+        </section>
+    }
+}
+
+fn loading() -> Html {
+    html! { <p class="empty">{ "Chargement…" }</p> }
+}
+
+fn genes(detail: &AgentDetail) -> Html {
+    let Some(genome) = &detail.genome else {
+        return html! { <div><h3>{ "Génome" }</h3>{ loading() }</div> };
+    };
+    let active = genome.files.iter().filter(|g| g.active).count();
+    html! {
+        <div>
+            <h3>{ format!("Génome ({active}/{} gènes actifs)", genome.files.len()) }</h3>
+            <ul class="genes">
+                { for genome.files.iter().map(|gene| {
+                    let functions = gene.functions.values().filter(|on| **on).count();
+                    html! {
+                        <li class={if gene.active { "active" } else { "inactive" }}>
+                            { if gene.active { "● " } else { "○ " } }
+                            <span class="mono">{ &gene.path }</span>
+                            if !gene.functions.is_empty() {
+                                { format!(" ({functions}/{} fonctions)", gene.functions.len()) }
+                            }
+                        </li>
+                    }
+                }) }
+            </ul>
+        </div>
+    }
+}
+
+fn resources(samples: &[ResourceSample]) -> Html {
+    let Some(first) = samples.first() else {
+        return html! {
+            <div><h3>{ "Ressources" }</h3><p class="empty">{ "Aucune mesure (agent arrêté ?)" }</p></div>
+        };
+    };
+    let series = |label, color, value: fn(&ResourceSample) -> f64| Series {
+        label,
+        color,
+        points: samples
+            .iter()
+            .map(|s| ((s.timestamp - first.timestamp) as f64, value(s)))
+            .collect(),
+    };
+    html! {
+        <div>
+            <h3>{ "Ressources" }</h3>
+            <LineChart title="Mémoire (Mo)" x_axis={XAxis::Seconds}
+                series={vec![series("mémoire", "#1e88e5", |s| s.memory_mb as f64)]} />
+            <LineChart title="CPU (%)" x_axis={XAxis::Seconds}
+                series={vec![series("cpu", "#fb8c00", |s| s.cpu_percent)]} />
+        </div>
+    }
+}
+
+fn source_diff(detail: &AgentDetail) -> Html {
+    let Some(diff) = &detail.diff else {
+        return html! { <div><h3>{ "Source" }</h3>{ loading() }</div> };
+    };
+    html! {
+        <div>
+            <h3>{ format!("Source comparée au {}", diff.reference) }</h3>
+            if diff.files.is_empty() {
+                <p class="empty">{ "Aucune différence" }</p>
+            }
+            { for diff.files.iter().map(|file| html! {
+                <details class="diff" open=true>
+                    <summary class="mono">{ &file.path }</summary>
+                    <pre>
+                        { for file.hunks.iter().map(|hunk| html! {
+                            <>
+                                <div class="hunk">{ &hunk.header }</div>
+                                { for hunk.lines.iter().map(|line| {
+                                    let (class, sign) = match line.kind {
+                                        DiffLineKind::Context => ("context", " "),
+                                        DiffLineKind::Added => ("added", "+"),
+                                        DiffLineKind::Removed => ("removed", "-"),
+                                    };
+                                    html! { <div class={class}>{ format!("{sign}{}", line.text) }</div> }
+                                }) }
+                            </>
+                        }) }
+                    </pre>
+                </details>
+            }) }
+        </div>
+    }
+}
+
+fn logs(detail: &AgentDetail) -> Html {
+    let Some(logs) = &detail.logs else {
+        return html! { <div><h3>{ "Sorties" }</h3>{ loading() }</div> };
+    };
+    let block = |title: &str, lines: &[String]| {
+        html! {
+            <div>
+                <h4>{ title }</h4>
+                <pre class="log">{ lines.join("\n") }</pre>
+            </div>
+        }
+    };
+    html! {
+        <div>
+            <h3>{ "Sorties" }</h3>
+            <div class="detail-grid">
+                { block("stdout", &logs.stdout) }
+                { block("stderr", &logs.stderr) }
+            </div>
+        </div>
+    }
+}
+
+fn events(detail: &AgentDetail) -> Html {
+    html! {
+        <div>
+            <h3>{ format!("Historique ({} événements)", detail.events.len()) }</h3>
+            <table class="events">
+                <thead><tr><th>{ "#" }</th><th>{ "Horodatage" }</th><th>{ "Type" }</th><th>{ "Détails" }</th></tr></thead>
+                <tbody>
+                    { for detail.events.iter().rev().map(|record| {
+                        // Le type et l'agent sont déjà affichés : seuls les autres champs sont détaillés
+                        let mut fields = serde_json::to_value(&record.event).unwrap_or_default();
+                        if let Some(object) = fields.as_object_mut() {
+                            object.remove("type");
+                            object.remove("agent");
+                        }
+                        html! {
+                            <tr key={record.seq}>
+                                <td>{ record.seq }</td>
+                                <td>{ record.timestamp }</td>
+                                <td>{ record.kind() }</td>
+                                <td class="mono">{ fields.to_string() }</td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,197 @@
+use crate::dashboard::model::format_duration;
+use protocol::{AgentState, AgentSummary};
+use std::cmp::Ordering;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+/// Colonnes triables du tableau
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Name,
+    Island,
+    Species,
+    State,
+    Generation,
+    Energy,
+    Fitness,
+    Novelty,
+    ParetoFront,
+    TaskScore,
+    Crashes,
+    Uptime,
+}
+
+impl Column {
+    const ALL: [Column; 12] = [
+        Column::Name,
+        Column::Island,
+        Column::Species,
+        Column::State,
+        Column::Generation,
+        Column::Energy,
+        Column::Fitness,
+        Column::Novelty,
+        Column::ParetoFront,
+        Column::TaskScore,
+        Column::Crashes,
+        Column::Uptime,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Column::Name => "Agent",
+            Column::Island => "Île",
+            Column::Species => "Espèce",
+            Column::State => "État",
+            Column::Generation => "Génération",
+            Column::Energy => "Énergie",
+            Column::Fitness => "Aptitude",
+            Column::Novelty => "Nouveauté",
+            Column::ParetoFront => "Front",
+            Column::TaskScore => "Tâches",
+            Column::Crashes => "Crashs",
+            Column::Uptime => "En marche depuis",
+        }
+    }
+
+    fn compare(self, a: &AgentSummary, b: &AgentSummary, now: u64) -> Ordering {
+        match self {
+            Column::Name => a.name.cmp(&b.name),
+            Column::Island => a.island.cmp(&b.island),
+            Column::Species => a.species.cmp(&b.species),
+            Column::State => a.state.cmp(&b.state),
+            Column::Generation => a.generation.cmp(&b.generation),
+            Column::Energy => a.energy.cmp(&b.energy),
+            Column::Fitness => a.fitness.total_cmp(&b.fitness),
+            Column::Novelty => a
+                .novelty
+                .unwrap_or(0.0)
+                .total_cmp(&b.novelty.unwrap_or(0.0)),
+            Column::ParetoFront => a.pareto_front.cmp(&b.pareto_front),
+            Column::TaskScore => a
+                .task_score
+                .unwrap_or(0.0)
+                .total_cmp(&b.task_score.unwrap_or(0.0)),
+            Column::Crashes => a.crash_count.cmp(&b.crash_count),
+            Column::Uptime => uptime(a, now).cmp(&uptime(b, now)),
+        }
+    }
+}
+
+/// Temps passé dans l'état Running (aucun si l'agent ne tourne pas)
+fn uptime(agent: &AgentSummary, now: u64) -> Option<u64> {
+    (agent.state == AgentState::Running).then(|| now.saturating_sub(agent.state_since))
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AgentsTableProps {
+    pub agents: Vec<AgentSummary>,
+    pub now: u64,
+    pub selected: Option<String>,
+    pub on_select: Callback<String>,
+}
+
+/// Tableau des agents, triable par colonne et filtrable par nom et par état
+#[function_component(AgentsTable)]
+pub fn agents_table(props: &AgentsTableProps) -> Html {
+    let sort = use_state(|| (Column::Name, true));
+    let name_filter = use_state(String::new);
+    let state_filter = use_state(String::new);
+
+    let on_name_filter = {
+        let name_filter = name_filter.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                name_filter.set(input.value());
+            }
+        })
+    };
+    let on_state_filter = {
+        let state_filter = state_filter.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                state_filter.set(select.value());
+            }
+        })
+    };
+
+    let mut states: Vec<AgentState> = props.agents.iter().map(|a| a.state).collect();
+    states.sort_unstable();
+    states.dedup();
+
+    let (column, ascending) = *sort;
+    let mut rows: Vec<&AgentSummary> = props
+        .agents
+        .iter()
+        .filter(|a| a.name.contains(name_filter.as_str()))
+        .filter(|a| state_filter.is_empty() || a.state.as_str() == *state_filter)
+        .collect();
+    rows.sort_by(|a, b| {
+        let order = column.compare(a, b, props.now);
+        if ascending {
+            order
+        } else {
+            order.reverse()
+        }
+    });
+
+    let header = |col: Column| {
+        let sort = sort.clone();
+        let onclick = Callback::from(move |_| {
+            let (current, ascending) = *sort;
+            sort.set((col, if current == col { !ascending } else { true }));
+        });
+        let arrow = match (col == column, ascending) {
+            (true, true) => " ▲",
+            (true, false) => " ▼",
+            _ => "",
+        };
+        html! { <th {onclick}>{ col.label() }{ arrow }</th> }
+    };
+
+    html! {
+        <section class="agents">
+            <div class="filters">
+                <input type="search" placeholder="Filtrer par nom" value={(*name_filter).clone()} oninput={on_name_filter} />
+                <select onchange={on_state_filter}>
+                    <option value="" selected={state_filter.is_empty()}>{ "Tous les états" }</option>
+                    { for states.iter().map(|s| html! {
+                        <option value={s.as_str()} selected={*state_filter == s.as_str()}>{ s.as_str() }</option>
+                    }) }
+                </select>
+                <span>{ format!("{} / {} agents", rows.len(), props.agents.len()) }</span>
+            </div>
+            <table>
+                <thead>
+                    <tr>{ for Column::ALL.into_iter().map(header) }</tr>
+                </thead>
+                <tbody>
+                    { for rows.iter().map(|agent| {
+                        let onclick = {
+                            let on_select = props.on_select.clone();
+                            let name = agent.name.clone();
+                            Callback::from(move |_| on_select.emit(name.clone()))
+                        };
+                        let selected = props.selected.as_ref() == Some(&agent.name);
+                        html! {
+                        <tr key={agent.name.clone()} {onclick} class={classes!(selected.then_some("selected"))}>
+                            <td class="mono">{ &agent.name }</td>
+                            <td>{ &agent.island }</td>
+                            <td>{ agent.species.map(|s| s.to_string()).unwrap_or_else(|| "—".into()) }</td>
+                            <td><span class={classes!("state", agent.state.as_str())}>{ agent.state.as_str() }</span></td>
+                            <td>{ agent.generation }</td>
+                            <td>{ agent.energy }</td>
+                            <td>{ format!("{:.1}", agent.fitness) }</td>
+                            <td>{ agent.novelty.map(|n| format!("{:.2}", n)).unwrap_or_else(|| "—".into()) }</td>
+                            <td>{ agent.pareto_front.map(|f| f.to_string()).unwrap_or_else(|| "—".into()) }</td>
+                            <td>{ agent.task_score.map(|s| format!("{:.0} %", s * 100.0)).unwrap_or_else(|| "—".into()) }</td>
+                            <td>{ agent.crash_count }</td>
+                            <td>{ uptime(agent, props.now).map(format_duration).unwrap_or_else(|| "—".into()) }</td>
+                        </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        </section>
+    }
+}
@@ -0,0 +1,121 @@
+use protocol::GenerationSummary;
+use yew::prelude::*;
+
+const WIDTH: f64 = 320.0;
+const HEIGHT: f64 = 160.0;
+const MARGIN: f64 = 28.0;
+
+/// Graduation de l'axe x
+#[derive(Clone, Copy, PartialEq)]
+pub enum XAxis {
+    Generation,
+    Seconds,
+}
+
+/// Série d'une courbe : libellé, couleur et points (génération, valeur)
+#[derive(Clone, PartialEq)]
+pub struct Series {
+    pub label: &'static str,
+    pub color: &'static str,
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LineChartProps {
+    pub title: &'static str,
+    pub series: Vec<Series>,
+    #[prop_or(XAxis::Generation)]
+    pub x_axis: XAxis,
+}
+
+/// Courbe SVG simple (axe x : génération ou secondes)
+#[function_component(LineChart)]
+pub fn line_chart(props: &LineChartProps) -> Html {
+    let points = props.series.iter().flat_map(|s| s.points.iter());
+    let (x_max, y_min, y_max) = points.fold((0.0f64, 0.0f64, 0.0f64), |(x, lo, hi), (px, py)| {
+        (x.max(*px), lo.min(*py), hi.max(*py))
+    });
+    // Évite une échelle nulle (une seule génération, valeurs constantes)
+    let x_span = if x_max > 0.0 { x_max } else { 1.0 };
+    let y_span = if y_max > y_min { y_max - y_min } else { 1.0 };
+    let project = |(x, y): (f64, f64)| {
+        (
+            MARGIN + x / x_span * (WIDTH - 2.0 * MARGIN),
+            HEIGHT - MARGIN - (y - y_min) / y_span * (HEIGHT - 2.0 * MARGIN),
+        )
+    };
+
+    let view_box = format!("0 0 {WIDTH} {HEIGHT}");
+    html! {
+        <figure class="chart">
+            <figcaption>
+                { props.title }
+                { for props.series.iter().map(|s| html! {
+                    <span class="legend" style={format!("color:{}", s.color)}>{ format!(" ● {}", s.label) }</span>
+                }) }
+            </figcaption>
+            <svg viewBox={view_box} width={WIDTH.to_string()} height={HEIGHT.to_string()}>
+                <line x1={MARGIN.to_string()} y1={(HEIGHT - MARGIN).to_string()}
+                      x2={(WIDTH - MARGIN).to_string()} y2={(HEIGHT - MARGIN).to_string()} class="axis" />
+                <line x1={MARGIN.to_string()} y1={MARGIN.to_string()}
+                      x2={MARGIN.to_string()} y2={(HEIGHT - MARGIN).to_string()} class="axis" />
+                <text x="2" y={(MARGIN - 6.0).to_string()} class="tick">{ format!("{y_max:.1}") }</text>
+                <text x="2" y={(HEIGHT - MARGIN + 14.0).to_string()} class="tick">{ format!("{y_min:.1}") }</text>
+                <text x={(WIDTH - MARGIN).to_string()} y={(HEIGHT - 6.0).to_string()} class="tick">{ match props.x_axis {
+                    XAxis::Generation => format!("g{x_max}"),
+                    XAxis::Seconds => format!("{x_max}s"),
+                } }</text>
+                { for props.series.iter().map(|s| {
+                    let projected: Vec<(f64, f64)> = s.points.iter().copied().map(project).collect();
+                    let path = projected.iter().map(|(x, y)| format!("{x:.1},{y:.1}")).collect::<Vec<_>>().join(" ");
+                    html! {
+                        <g>
+                            <polyline points={path} fill="none" stroke={s.color} stroke-width="2" />
+                            { for projected.iter().map(|(x, y)| html! {
+                                <circle cx={x.to_string()} cy={y.to_string()} r="3" fill={s.color} />
+                            }) }
+                        </g>
+                    }
+                }) }
+            </svg>
+        </figure>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GenerationChartsProps {
+    pub generations: Vec<GenerationSummary>,
+}
+
+/// Courbes par génération : taille de la population, aptitude moyenne/max, diversité
+#[function_component(GenerationCharts)]
+pub fn generation_charts(props: &GenerationChartsProps) -> Html {
+    let series = |label, color, value: fn(&GenerationSummary) -> f64| Series {
+        label,
+        color,
+        points: props
+            .generations
+            .iter()
+            .map(|g| (g.generation as f64, value(g)))
+            .collect(),
+    };
+
+    if props.generations.is_empty() {
+        return html! { <p class="empty">{ "Aucun résumé de génération pour l'instant" }</p> };
+    }
+    html! {
+        <section class="charts">
+            <LineChart title="Population" series={vec![
+                series("vivants", "#4caf50", |g| g.alive as f64),
+                series("morts", "#e53935", |g| g.dead as f64),
+            ]} />
+            <LineChart title="Aptitude" series={vec![
+                series("moyenne", "#1e88e5", |g| g.mean_fitness),
+                series("max", "#fb8c00", |g| g.max_fitness),
+            ]} />
+            <LineChart title="Diversité" series={vec![
+                series("génotypes distincts", "#8e24aa", |g| g.diversity),
+            ]} />
+        </section>
+    }
+}
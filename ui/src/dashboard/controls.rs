@@ -0,0 +1,190 @@
+use crate::gateway::GatewayHandle;
+use crate::operator::{confirm, prompt};
+use protocol::{AgentState, Command, SelectionParams};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct AgentActionsProps {
+    pub name: String,
+    /// État courant de l'agent (boutons proposés selon l'état)
+    pub state: AgentState,
+}
+
+/// Boutons d'action sur un agent ; les opérations destructives demandent confirmation
+#[function_component(AgentActions)]
+pub fn agent_actions(props: &AgentActionsProps) -> Html {
+    let gateway = use_context::<Option<GatewayHandle>>().flatten();
+    let action = |label: &'static str, confirmation: Option<String>, command: Command| {
+        let disabled = gateway.is_none();
+        let gateway = gateway.clone();
+        let onclick = Callback::from(move |_| {
+            if confirmation
+                .as_deref()
+                .is_some_and(|message| !confirm(message))
+            {
+                return;
+            }
+            if let Some(gateway) = &gateway {
+                gateway.command(command.clone());
+            }
+        });
+        html! { <button {onclick} {disabled}>{ label }</button> }
+    };
+
+    let name = props.name.clone();
+    html! {
+        <div class="actions">
+            if props.state == AgentState::Paused {
+                { action("Reprendre", None, Command::Resume { name: name.clone() }) }
+            } else if props.state.is_alive() {
+                { action(
+                    "Pause",
+                    Some(format!("Mettre l'agent {name} en pause ? Son processus sera arrêté.")),
+                    Command::Pause { name: name.clone() },
+                ) }
+            }
+            if matches!(
+                props.state,
+                AgentState::Quarantined | AgentState::Dead | AgentState::Crashed | AgentState::Backoff
+            ) {
+                { action(
+                    "Relancer",
+                    Some(format!("Relancer l'agent {name} ({}) ?", props.state)),
+                    Command::Revive {
+                        name: name.clone(),
+                        confirm: true,
+                    },
+                ) }
+            }
+            { action("Cloner", None, Command::Clone { name: name.clone() }) }
+            if !matches!(props.state, AgentState::Dead | AgentState::Archived) {
+                <span class="danger">
+                    { action(
+                        "Tuer",
+                        Some(format!("Tuer l'agent {name} ? Il ne sera plus relancé automatiquement.")),
+                        Command::Kill {
+                            name: name.clone(),
+                            confirm: true,
+                        },
+                    ) }
+                </span>
+            }
+        </div>
+    }
+}
+
+/// Bouton de sauvegarde de la population (étiquette facultative)
+#[function_component(SnapshotButton)]
+pub fn snapshot_button() -> Html {
+    let gateway = use_context::<Option<GatewayHandle>>().flatten();
+    let onclick = {
+        let gateway = gateway.clone();
+        Callback::from(move |_| {
+            let Some(label) = prompt("Étiquette du snapshot (facultative)", "") else {
+                return;
+            };
+            let label = Some(label.trim().to_string()).filter(|l| !l.is_empty());
+            if let Some(gateway) = &gateway {
+                gateway.command(Command::Snapshot { label });
+            }
+        })
+    };
+    html! { <button {onclick} disabled={gateway.is_none()}>{ "Snapshot" }</button> }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SelectionFormProps {
+    /// Paramètres en vigueur dans l'orchestrateur (None tant qu'ils ne sont pas reçus)
+    pub params: Option<SelectionParams>,
+    /// Modification autorisée (rôle operator) ; sinon simple consultation
+    pub can_act: bool,
+}
+
+/// Accès à un paramètre de sélection
+type Field = fn(&mut SelectionParams) -> &mut u32;
+
+/// Champs du formulaire : libellé et accès au paramètre correspondant
+const FIELDS: [(&str, Field); 5] = [
+    ("Pénalité code dangereux", |p| &mut p.penalty_if_not_safe),
+    ("Pénalité code invalide", |p| &mut p.penalty_if_not_valid),
+    ("Pénalité ressources dépassées", |p| {
+        &mut p.penalty_if_resource_exceeded
+    }),
+    ("Pénalité par unwrap", |p| &mut p.penalty_per_unwrap),
+    ("Score maximal", |p| &mut p.max_score),
+];
+
+/// Paramètres de sélection modifiables pendant l'exécution (appliqués au tour suivant)
+#[function_component(SelectionForm)]
+pub fn selection_form(props: &SelectionFormProps) -> Html {
+    let gateway = use_context::<Option<GatewayHandle>>().flatten();
+    // Modifications en cours, non encore envoyées
+    let draft = use_state(|| None::<SelectionParams>);
+
+    {
+        use_effect_with(gateway.clone(), |gateway| {
+            if let Some(gateway) = gateway {
+                gateway.command(Command::GetSelectionParams);
+            }
+        });
+    }
+
+    let Some(current) = (*draft).clone().or_else(|| props.params.clone()) else {
+        return html! { <p class="empty">{ "Chargement…" }</p> };
+    };
+
+    let field = |index: usize| {
+        let (label, access) = FIELDS[index];
+        let shown = *access(&mut current.clone());
+        let current = current.clone();
+        let draft = draft.clone();
+        let oninput = Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(number) = input.value().parse() {
+                let mut value = current.clone();
+                *access(&mut value) = number;
+                draft.set(Some(value));
+            }
+        });
+        html! {
+            <label>
+                { label }
+                <input type="number" min="0" value={shown.to_string()} {oninput}
+                    disabled={!props.can_act} />
+            </label>
+        }
+    };
+
+    let onsubmit = {
+        let draft = draft.clone();
+        let gateway = gateway.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(params) = (*draft).clone() else {
+                return;
+            };
+            if !confirm("Appliquer ces paramètres de sélection à toute la population ?") {
+                return;
+            }
+            if let Some(gateway) = &gateway {
+                gateway.command(Command::SetSelectionParams { params });
+            }
+            draft.set(None);
+        })
+    };
+    let onreset = {
+        let draft = draft.clone();
+        Callback::from(move |_| draft.set(None))
+    };
+
+    html! {
+        <form class="selection" {onsubmit}>
+            { for (0..FIELDS.len()).map(field) }
+            if props.can_act {
+                <button type="submit" disabled={draft.is_none() || gateway.is_none()}>{ "Appliquer" }</button>
+                <button type="button" onclick={onreset} disabled={draft.is_none()}>{ "Annuler" }</button>
+            }
+        </form>
+    }
+}
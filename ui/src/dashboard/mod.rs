@@ -0,0 +1,5 @@
+pub mod agent_detail;
+pub mod agents_table;
+pub mod charts;
+pub mod controls;
+pub mod model;
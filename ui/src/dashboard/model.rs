@@ -0,0 +1,229 @@
+use protocol::{
+    AgentLogs, AgentState, AgentSummary, AuditEvent, EventRecord, GenerationSummary, GenomeView,
+    ResourceSample, Role, SelectionParams, SourceDiff,
+};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use yew::Reducible;
+
+/// État du tableau de bord, alimenté par les réponses et le flux d'événements
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dashboard {
+    pub connected: bool,
+    /// Rôle accordé par le jeton d'accès (actions réservées au rôle operator)
+    pub role: Role,
+    /// Tentatives de reconnexion depuis la dernière connexion réussie
+    pub reconnect_attempts: u32,
+    pub agents: BTreeMap<String, AgentSummary>,
+    pub generations: BTreeMap<u32, GenerationSummary>,
+    pub last_error: Option<String>,
+    /// Résultat de la dernière action de l'opérateur
+    pub notice: Option<String>,
+    pub selection: Option<SelectionParams>,
+    pub now: u64, // Timestamp UNIX courant, pour les durées de fonctionnement
+    pub detail: Option<AgentDetail>,
+}
+
+/// Vue détaillée de l'agent ouvert ; chaque partie arrive par sa propre réponse
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentDetail {
+    pub name: String,
+    pub genome: Option<GenomeView>,
+    pub diff: Option<SourceDiff>,
+    pub logs: Option<AgentLogs>,
+    pub resources: Vec<ResourceSample>,
+    pub events: Vec<EventRecord>,
+}
+
+pub enum DetailPart {
+    Genome(GenomeView),
+    Diff(SourceDiff),
+    Logs(AgentLogs),
+    Resources(Vec<ResourceSample>),
+    Events(Vec<EventRecord>),
+}
+
+pub enum DashboardAction {
+    Connected(Role),
+    Disconnected,
+    Agents(Vec<AgentSummary>),
+    Generations(Vec<GenerationSummary>),
+    Event(EventRecord),
+    Error(String),
+    Tick(u64),
+    /// Ouvre (ou ferme) la vue détaillée d'un agent
+    Select(Option<String>),
+    Detail {
+        name: String,
+        part: DetailPart,
+    },
+    /// Réponse à une action sur un agent (état mis à jour sans attendre le rafraîchissement)
+    AgentUpdated {
+        agent: AgentSummary,
+        notice: String,
+    },
+    Notice(String),
+    Selection(SelectionParams),
+    SelectionChanged(SelectionParams),
+}
+
+impl Reducible for Dashboard {
+    type Action = DashboardAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut next = (*self).clone();
+        match action {
+            DashboardAction::Connected(role) => {
+                next.connected = true;
+                next.role = role;
+                next.reconnect_attempts = 0;
+                next.last_error = None;
+            }
+            DashboardAction::Disconnected => {
+                next.connected = false;
+                next.reconnect_attempts += 1;
+            }
+            DashboardAction::Agents(agents) => {
+                next.agents = agents.into_iter().map(|a| (a.name.clone(), a)).collect();
+            }
+            DashboardAction::Generations(generations) => {
+                next.generations = generations.into_iter().map(|g| (g.generation, g)).collect();
+            }
+            DashboardAction::Event(record) => next.apply_event(&record),
+            DashboardAction::Error(message) => {
+                next.last_error = Some(message);
+                next.notice = None;
+            }
+            DashboardAction::AgentUpdated { agent, notice } => {
+                next.agents.insert(agent.name.clone(), agent);
+                next.notice = Some(notice);
+                next.last_error = None;
+            }
+            DashboardAction::Notice(notice) => {
+                next.notice = Some(notice);
+                next.last_error = None;
+            }
+            DashboardAction::Selection(params) => next.selection = Some(params),
+            DashboardAction::SelectionChanged(params) => {
+                next.selection = Some(params);
+                next.notice = Some("Paramètres de sélection appliqués".into());
+                next.last_error = None;
+            }
+            DashboardAction::Tick(now) => next.now = now,
+            DashboardAction::Select(name) => {
+                next.detail = name.map(|name| AgentDetail {
+                    name,
+                    ..AgentDetail::default()
+                });
+            }
+            DashboardAction::Detail { name, part } => {
+                // Réponse arrivée après la fermeture ou le changement d'agent : ignorée
+                if let Some(detail) = next.detail.as_mut().filter(|d| d.name == name) {
+                    match part {
+                        DetailPart::Genome(genome) => detail.genome = Some(genome),
+                        DetailPart::Diff(diff) => detail.diff = Some(diff),
+                        DetailPart::Logs(logs) => detail.logs = Some(logs),
+                        DetailPart::Resources(samples) => detail.resources = samples,
+                        DetailPart::Events(events) => detail.events = events,
+                    }
+                }
+            }
+        }
+        Rc::new(next)
+    }
+}
+
+impl Dashboard {
+    /// Met à jour les agents et générations à partir d'un événement de l'orchestrateur.
+    /// L'aptitude n'est pas déduite des événements : elle arrive avec le rafraîchissement périodique.
+    fn apply_event(&mut self, record: &EventRecord) {
+        if let Some(detail) = self.detail.as_mut() {
+            let newer = detail
+                .events
+                .last()
+                .is_none_or(|last| last.seq < record.seq);
+            if record.agent() == Some(detail.name.as_str()) && newer {
+                detail.events.push(record.clone());
+            }
+        }
+
+        let name = match &record.event {
+            AuditEvent::GenerationSummary {
+                generation,
+                alive,
+                dead,
+                mean_fitness,
+                max_fitness,
+                diversity,
+            } => {
+                let summary = GenerationSummary {
+                    generation: *generation,
+                    alive: *alive,
+                    dead: *dead,
+                    mean_fitness: *mean_fitness,
+                    max_fitness: *max_fitness,
+                    diversity: *diversity,
+                };
+                self.generations.insert(summary.generation, summary);
+                return;
+            }
+            AuditEvent::AgentCreated {
+                agent,
+                id,
+                energy,
+                generation,
+                island,
+                ..
+            } => {
+                self.agents.insert(
+                    agent.clone(),
+                    AgentSummary {
+                        name: agent.clone(),
+                        id: id.clone(),
+                        island: island.clone(),
+                        species: None,
+                        novelty: None,
+                        pareto_front: None,
+                        task_score: None,
+                        state: AgentState::Created,
+                        state_since: record.timestamp,
+                        energy: *energy,
+                        fitness: *energy as f64,
+                        crash_count: 0,
+                        generation: *generation,
+                    },
+                );
+                return;
+            }
+            event => match event.agent() {
+                Some(name) => name,
+                None => return,
+            },
+        };
+
+        let Some(agent) = self.agents.get_mut(name) else {
+            return;
+        };
+        match &record.event {
+            AuditEvent::StateChanged { to, .. } => {
+                agent.state = *to;
+                agent.state_since = record.timestamp;
+            }
+            AuditEvent::SelectionScore { energy, .. }
+            | AuditEvent::TaskEvaluated { energy, .. }
+            | AuditEvent::AgentRequest { energy, .. }
+            | AuditEvent::EnergyChanged { energy, .. } => agent.energy = *energy,
+            AuditEvent::Crash { crash_count, .. } => agent.crash_count = *crash_count,
+            _ => {}
+        }
+    }
+}
+
+/// Durée lisible (ex: 1h02m, 3m20s, 45s)
+pub fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, (s % 3600) / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{s}s"),
+    }
+}
@@ -0,0 +1,233 @@
+use crate::dashboard::model::{Dashboard, DashboardAction, DetailPart};
+use futures_channel::mpsc::{self, UnboundedSender};
+use futures_util::{SinkExt, StreamExt};
+use gloo_net::websocket::{futures::WebSocket, Message};
+use protocol::{AgentSummary, ClientMessage, Command, ServerMessage, PROTOCOL_VERSION};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use yew::UseReducerDispatcher;
+
+/// Passerelle utilisée quand la page n'est pas servie en HTTP (ex: fichier local)
+const DEFAULT_GATEWAY_URL: &str = "ws://127.0.0.1:9001/ws";
+/// Chemin de la passerelle sur le serveur de la page (ws, ou proxy de `trunk serve`)
+const GATEWAY_PATH: &str = "/ws";
+
+/// Adresse de la passerelle : même hôte que la page, le binaire ws servant l'interface
+pub fn gateway_url() -> String {
+    let Some(location) = web_sys::window().map(|w| w.location()) else {
+        return DEFAULT_GATEWAY_URL.to_string();
+    };
+    let scheme = match location.protocol().as_deref() {
+        Ok("https:") => "wss",
+        Ok("http:") => "ws",
+        _ => return DEFAULT_GATEWAY_URL.to_string(),
+    };
+    match location.host() {
+        Ok(host) if !host.is_empty() => format!("{scheme}://{host}{GATEWAY_PATH}"),
+        _ => DEFAULT_GATEWAY_URL.to_string(),
+    }
+}
+
+/// Connexion à la passerelle ws : envoie les messages du client et
+/// transmet au tableau de bord les événements et réponses reçus.
+pub struct Gateway {
+    outgoing: UnboundedSender<ClientMessage>,
+    // Commandes en attente de réponse, par identifiant de corrélation
+    pending: Rc<RefCell<HashMap<u64, Command>>>,
+    next_id: Cell<u64>,
+}
+
+impl Gateway {
+    /// Ouvre la connexion et effectue la poignée de main ; `operator` est le nom
+    /// enregistré par l'orchestrateur avec les actions envoyées sur cette connexion,
+    /// `token` le jeton d'accès qui détermine le rôle accordé
+    pub fn connect(
+        url: &str,
+        operator: String,
+        token: Option<String>,
+        dispatch: UseReducerDispatcher<Dashboard>,
+    ) -> Option<Self> {
+        let (mut write, mut read) = match WebSocket::open(url) {
+            Ok(ws) => ws.split(),
+            Err(e) => {
+                dispatch.dispatch(DashboardAction::Error(format!("Connexion impossible: {e}")));
+                dispatch.dispatch(DashboardAction::Disconnected);
+                return None;
+            }
+        };
+        let (outgoing, mut queue) = mpsc::unbounded::<ClientMessage>();
+        let pending = Rc::new(RefCell::new(HashMap::new()));
+
+        // Émission : vide la file des messages du client
+        spawn_local(async move {
+            while let Some(message) = queue.next().await {
+                if write.send(Message::Text(message.to_json())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // Réception : décode les messages du serveur
+        let responses = pending.clone();
+        spawn_local(async move {
+            while let Some(message) = read.next().await {
+                match message {
+                    Ok(Message::Text(text)) => match serde_json::from_str::<ServerMessage>(&text) {
+                        Ok(message) => handle_server_message(message, &responses, &dispatch),
+                        Err(e) => dispatch
+                            .dispatch(DashboardAction::Error(format!("Message illisible: {e}"))),
+                    },
+                    Ok(Message::Bytes(_)) => {}
+                    Err(_) => break,
+                }
+            }
+            dispatch.dispatch(DashboardAction::Disconnected);
+        });
+
+        let gateway = Self {
+            outgoing,
+            pending,
+            next_id: Cell::new(1),
+        };
+        gateway.send(ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+            operator: Some(operator),
+            token,
+        });
+        Some(gateway)
+    }
+
+    /// Envoie une commande ; sa réponse sera transmise au tableau de bord
+    pub fn command(&self, command: Command) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.pending.borrow_mut().insert(id, command.clone());
+        self.send(ClientMessage::Command { id, command });
+    }
+
+    fn send(&self, message: ClientMessage) {
+        // Connexion fermée : la déconnexion est signalée par la tâche de réception
+        let _ = self.outgoing.unbounded_send(message);
+    }
+}
+
+/// Passerelle partagée via le contexte Yew (égalité = même connexion)
+#[derive(Clone)]
+pub struct GatewayHandle(pub Rc<Gateway>);
+
+impl PartialEq for GatewayHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for GatewayHandle {
+    type Target = Gateway;
+
+    fn deref(&self) -> &Gateway {
+        &self.0
+    }
+}
+
+fn handle_server_message(
+    message: ServerMessage,
+    pending: &RefCell<HashMap<u64, Command>>,
+    dispatch: &UseReducerDispatcher<Dashboard>,
+) {
+    let action = match message {
+        ServerMessage::Welcome { role, .. } => DashboardAction::Connected(role),
+        ServerMessage::Subscriptions { .. } => return,
+        ServerMessage::Event { record } => DashboardAction::Event(record),
+        ServerMessage::Response { id, data } => {
+            let Some(command) = pending.borrow_mut().remove(&id) else {
+                return;
+            };
+            match decode_response(&command, data) {
+                Ok(Some(action)) => action,
+                Ok(None) => return,
+                Err(e) => DashboardAction::Error(format!("Réponse illisible: {e}")),
+            }
+        }
+        ServerMessage::Error { id, message } => {
+            if let Some(id) = id {
+                pending.borrow_mut().remove(&id);
+            }
+            DashboardAction::Error(message)
+        }
+    };
+    dispatch.dispatch(action);
+}
+
+/// Décode la réponse selon la commande qui l'a demandée
+fn decode_response(
+    command: &Command,
+    data: serde_json::Value,
+) -> Result<Option<DashboardAction>, serde_json::Error> {
+    let detail = |name: &String, part| {
+        Some(DashboardAction::Detail {
+            name: name.clone(),
+            part,
+        })
+    };
+    Ok(match command {
+        Command::ListAgents => Some(DashboardAction::Agents(serde_json::from_value(data)?)),
+        Command::ListGenerations => {
+            Some(DashboardAction::Generations(serde_json::from_value(data)?))
+        }
+        Command::GetGenome { name } => {
+            detail(name, DetailPart::Genome(serde_json::from_value(data)?))
+        }
+        Command::GetSourceDiff { name } => {
+            detail(name, DetailPart::Diff(serde_json::from_value(data)?))
+        }
+        Command::GetLogs { name, .. } => {
+            detail(name, DetailPart::Logs(serde_json::from_value(data)?))
+        }
+        Command::GetResourceHistory { name } => {
+            detail(name, DetailPart::Resources(serde_json::from_value(data)?))
+        }
+        Command::GetAgentEvents { name, .. } => {
+            detail(name, DetailPart::Events(serde_json::from_value(data)?))
+        }
+        Command::Kill { name, .. }
+        | Command::Pause { name }
+        | Command::Resume { name }
+        | Command::Revive { name, .. } => Some(DashboardAction::AgentUpdated {
+            agent: serde_json::from_value(data)?,
+            notice: format!("{} : {}", action_label(command), name),
+        }),
+        Command::Clone { name } => {
+            let agent: AgentSummary = serde_json::from_value(data)?;
+            Some(DashboardAction::AgentUpdated {
+                notice: format!("Agent {} cloné depuis {}", agent.name, name),
+                agent,
+            })
+        }
+        Command::Snapshot { .. } => Some(DashboardAction::Notice(format!(
+            "Snapshot créé : {}",
+            data.get("archive").and_then(|a| a.as_str()).unwrap_or("?")
+        ))),
+        Command::GetSelectionParams => {
+            Some(DashboardAction::Selection(serde_json::from_value(data)?))
+        }
+        Command::SetSelectionParams { .. } => {
+            // Confirmation affichée, puis paramètres appliqués
+            let params = serde_json::from_value(data)?;
+            Some(DashboardAction::SelectionChanged(params))
+        }
+        _ => None,
+    })
+}
+
+fn action_label(command: &Command) -> &'static str {
+    match command {
+        Command::Kill { .. } => "Agent tué",
+        Command::Pause { .. } => "Agent mis en pause",
+        Command::Resume { .. } => "Agent repris",
+        Command::Revive { .. } => "Agent relancé",
+        _ => "Action effectuée",
+    }
+}
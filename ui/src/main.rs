@@ -1,110 +1,165 @@
-use futures_util::{SinkExt, StreamExt};
-use gloo_net::websocket::{futures::WebSocket, Message};
-use std::cell::RefCell;
+// Sans la feature csr, le composant n'est pas monté : tout le code UI est inutilisé
+#![cfg_attr(not(feature = "csr"), allow(dead_code))]
+
+mod dashboard;
+mod gateway;
+mod operator;
+
+use dashboard::agent_detail::AgentDetailView;
+use dashboard::agents_table::AgentsTable;
+use dashboard::charts::GenerationCharts;
+use dashboard::controls::{SelectionForm, SnapshotButton};
+use dashboard::model::{Dashboard, DashboardAction};
+use gateway::{gateway_url, Gateway, GatewayHandle};
+use gloo_timers::callback::{Interval, Timeout};
+use operator::{access_token, change_access_token, change_operator, operator_name};
+use protocol::Command;
+use protocol::Role;
 use std::rc::Rc;
-use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
+/// Rafraîchissement complet (agents + générations) toutes les N secondes,
+/// en complément du flux d'événements
+const REFRESH_EVERY_SECS: u64 = 10;
+/// Délai avant reconnexion : doublé à chaque échec, plafonné
+const RECONNECT_BASE_MS: u32 = 1000;
+const RECONNECT_MAX_MS: u32 = 30_000;
+
+/// Délai avant la tentative de reconnexion numéro `attempt` (à partir de 1)
+fn reconnect_delay(attempt: u32) -> u32 {
+    RECONNECT_BASE_MS
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(RECONNECT_MAX_MS)
+}
+
+fn now_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Tableau de bord de la population : tableau des agents et courbes par génération,
+/// mis à jour en direct depuis la passerelle ws
 #[function_component(App)]
 fn app() -> Html {
-    let msg_input = use_state(|| String::new());
-    let log = use_state(|| Vec::<String>::new());
-    // Writer WebSocket dans un Rc<RefCell<Option<...>>>
-    let ws_writer = use_state(|| Rc::new(RefCell::new(None)));
-
-    let on_connect = {
-        let ws_writer = ws_writer.clone();
-        let log = log.clone();
-        Callback::from(move |_| {
-            let ws_writer = ws_writer.clone();
-            let log = log.clone();
-            spawn_local(async move {
-                let (write, mut read) = match WebSocket::open("ws://127.0.0.1:9001") {
-                    Ok(ws) => ws.split(),
-                    Err(_) => {
-                        log.set({
-                            let mut l = (*log).clone();
-                            l.push("Erreur de connexion WebSocket".to_owned());
-                            l
-                        });
-                        return;
-                    }
-                };
-
-                log.set({
-                    let mut l = (*log).clone();
-                    l.push("[Connecté au serveur WS]".to_owned());
-                    l
-                });
-
-                // Receiver task
-                let log_reader = log.clone();
-                spawn_local(async move {
-                    while let Some(msg) = read.next().await {
-                        if let Ok(Message::Text(txt)) = msg {
-                            log_reader.set({
-                                let mut l = (*log_reader).clone();
-                                l.push(format!("Serveur: {txt}"));
-                                l
-                            });
-                        }
-                    }
-                });
-
-                // Enregistre le writer dans le state global pour envoyer après
-                *ws_writer.borrow_mut() = Some(write);
-            });
-        })
-    };
+    let dashboard = use_reducer(|| Dashboard {
+        now: now_secs(),
+        ..Dashboard::default()
+    });
 
-    let on_send = {
-        let ws_writer = ws_writer.clone();
-        let msg_input = msg_input.clone();
-        let log = log.clone();
-        Callback::from(move |_| {
-            let msg = (*msg_input).clone();
-            let log2 = log.clone();
-            let ws_writer2 = ws_writer.clone();
-            spawn_local(async move {
-                let mut writer_ref = ws_writer2.borrow_mut();
-                if let Some(ws_writer) = writer_ref.as_mut() {
-                    if ws_writer.send(Message::Text(msg.clone())).await.is_ok() {
-                        log2.set({
-                            let mut l = (*log2).clone();
-                            l.push(format!("Moi: {msg}"));
-                            l
-                        });
-                    }
+    // Connexion partagée avec les composants (vue détaillée) via le contexte
+    let gateway = use_state(|| None::<GatewayHandle>);
+    // Numéro de la connexion courante : l'incrémenter ouvre une nouvelle connexion
+    let connection = use_state(|| 0u32);
+
+    {
+        let dispatcher = dashboard.dispatcher();
+        let gateway_state = gateway.clone();
+        use_effect_with(*connection, move |_| {
+            let gateway = Gateway::connect(
+                &gateway_url(),
+                operator_name(),
+                access_token(),
+                dispatcher.clone(),
+            )
+            .map(|gateway| GatewayHandle(Rc::new(gateway)));
+            gateway_state.set(gateway.clone());
+            let refresh = move || {
+                if let Some(gateway) = &gateway {
+                    gateway.command(Command::ListAgents);
+                    gateway.command(Command::ListGenerations);
+                }
+            };
+            refresh();
+
+            let mut elapsed = 0;
+            let interval = Interval::new(1000, move || {
+                dispatcher.dispatch(DashboardAction::Tick(now_secs()));
+                elapsed += 1;
+                if elapsed % REFRESH_EVERY_SECS == 0 {
+                    refresh();
                 }
             });
-            msg_input.set(String::new());
-        })
-    };
+            move || drop(interval)
+        });
+    }
 
-    let on_input = {
-        let msg_input = msg_input.clone();
-        Callback::from(move |e: InputEvent| {
-            if let Some(val) = e
-                .target_dyn_into::<web_sys::HtmlInputElement>()
-                .map(|v| v.value())
-            {
-                msg_input.set(val);
-            }
-        })
+    // Reconnexion automatique après une déconnexion, avec attente croissante
+    {
+        let connection = connection.clone();
+        use_effect_with(dashboard.reconnect_attempts, move |attempts| {
+            let retry = (*attempts > 0).then(|| {
+                Timeout::new(reconnect_delay(*attempts), move || {
+                    connection.set(*connection + 1)
+                })
+            });
+            move || drop(retry)
+        });
+    }
+
+    let agents: Vec<_> = dashboard.agents.values().cloned().collect();
+    let generations: Vec<_> = dashboard.generations.values().cloned().collect();
+    let alive = agents.iter().filter(|a| a.state.is_alive()).count();
+
+    let on_select = {
+        let dispatcher = dashboard.dispatcher();
+        Callback::from(move |name| dispatcher.dispatch(DashboardAction::Select(Some(name))))
+    };
+    let on_close = {
+        let dispatcher = dashboard.dispatcher();
+        Callback::from(move |_| dispatcher.dispatch(DashboardAction::Select(None)))
     };
+    let selected = dashboard.detail.as_ref().map(|d| d.name.clone());
+    let can_act = dashboard.connected && dashboard.role == Role::Operator;
+    let selected_state = selected
+        .as_ref()
+        .and_then(|name| dashboard.agents.get(name))
+        .map(|agent| agent.state);
 
     html! {
-        <div style="max-width:500px;margin:2em auto; background:#fff; color:#222;">
-            <h1>{ "Yew + Rust WebSocket Demo" }</h1>
-            <button onclick={on_connect}>{ "Se connecter WebSocket" }</button>
-            <div style="margin:1em 0;">
-                <input type="text" value={(*msg_input).clone()} oninput={on_input} />
-                <button onclick={on_send}>{ "Envoyer" }</button>
-            </div>
-            <div style="background:#222;color:#ddd;padding:1em;border-radius:1em;min-height:8em;">
-                { for (*log).iter().rev().map(|line| html!{<div>{ line }</div>}) }
-            </div>
-        </div>
+        <ContextProvider<Option<GatewayHandle>> context={(*gateway).clone()}>
+        <main class="dashboard">
+            <header>
+                <h1>{ "Population" }</h1>
+                <span class={classes!("status", dashboard.connected.then_some("online"))}>
+                    { match (dashboard.connected, dashboard.reconnect_attempts) {
+                        (true, _) => "connecté".to_string(),
+                        (false, 0) => "déconnecté".to_string(),
+                        (false, n) => format!("reconnexion (tentative {n})…"),
+                    } }
+                </span>
+                <span>{ format!("{alive} vivants / {} agents", agents.len()) }</span>
+                <span class="operator">
+                    { format!("opérateur : {} ({})", operator_name(), role_label(dashboard.role)) }
+                    <button onclick={Callback::from(|_| change_operator())}>{ "Changer" }</button>
+                    <button onclick={Callback::from(|_| change_access_token())}>{ "Jeton" }</button>
+                </span>
+                if can_act {
+                    <SnapshotButton />
+                }
+            </header>
+            if let Some(error) = &dashboard.last_error {
+                <p class="error">{ error }</p>
+            }
+            if let Some(notice) = &dashboard.notice {
+                <p class="notice">{ notice }</p>
+            }
+            <GenerationCharts {generations} />
+            <details class="controls">
+                <summary>{ "Paramètres de sélection" }</summary>
+                <SelectionForm params={dashboard.selection.clone()} {can_act} />
+            </details>
+            if let Some(detail) = &dashboard.detail {
+                <AgentDetailView detail={detail.clone()} state={selected_state} {can_act} {on_close} />
+            }
+            <AgentsTable {agents} now={dashboard.now} {selected} {on_select} />
+        </main>
+        </ContextProvider<Option<GatewayHandle>>>
+    }
+}
+
+fn role_label(role: Role) -> &'static str {
+    match role {
+        Role::Viewer => "lecture seule",
+        Role::Operator => "opérateur",
     }
 }
 
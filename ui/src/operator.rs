@@ -0,0 +1,72 @@
+/// Identité de l'opérateur et boîtes de dialogue du navigateur
+///
+/// Le nom et le jeton d'accès sont conservés dans le localStorage et annoncés à la
+/// passerelle dans `Hello` : l'orchestrateur enregistre le nom avec chaque action
+/// dans son journal d'audit, le jeton détermine le rôle (viewer / operator).
+const STORAGE_KEY: &str = "evolve.operator";
+const TOKEN_STORAGE_KEY: &str = "evolve.token";
+/// Nom utilisé tant que l'opérateur ne s'est pas identifié
+const DEFAULT_OPERATOR: &str = "anonymous";
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+fn stored(key: &str) -> Option<String> {
+    storage()
+        .and_then(|storage| storage.get_item(key).ok().flatten())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Enregistre une valeur puis recharge la page pour refaire la poignée de main
+fn store_and_reload(key: &str, value: &str) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(key, value);
+    }
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().reload();
+    }
+}
+
+/// Nom de l'opérateur enregistré dans le navigateur
+pub fn operator_name() -> String {
+    stored(STORAGE_KEY).unwrap_or_else(|| DEFAULT_OPERATOR.to_string())
+}
+
+/// Jeton d'accès enregistré dans le navigateur
+pub fn access_token() -> Option<String> {
+    stored(TOKEN_STORAGE_KEY)
+}
+
+/// Demande un nouveau nom d'opérateur
+pub fn change_operator() {
+    if let Some(name) = prompt("Nom de l'opérateur", &operator_name()) {
+        store_and_reload(STORAGE_KEY, &name);
+    }
+}
+
+/// Demande le jeton d'accès (fichier access_tokens.json de l'orchestrateur)
+pub fn change_access_token() {
+    if let Some(token) = prompt("Jeton d'accès (viewer ou operator)", "") {
+        store_and_reload(TOKEN_STORAGE_KEY, &token);
+    }
+}
+
+/// Demande confirmation avant une opération destructive
+pub fn confirm(message: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(message).ok())
+        .unwrap_or(false)
+}
+
+/// Saisie d'un texte (None si l'opérateur annule)
+pub fn prompt(message: &str, default: &str) -> Option<String> {
+    web_sys::window()?
+        .prompt_with_message_and_default(message, default)
+        .ok()
+        .flatten()
+}
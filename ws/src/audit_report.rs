@@ -0,0 +1,55 @@
+use crate::control_client;
+use protocol::{AccessTokens, Command};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Nom sous lequel la passerelle envoie ses signalements à l'API de contrôle
+const GATEWAY_OPERATOR: &str = "ws";
+
+/// Transmet les refus d'accès de la passerelle au journal d'audit de l'orchestrateur
+/// (commande `report_access_denied`, avec le jeton operator du fichier des jetons)
+#[derive(Clone)]
+pub struct AuditReporter {
+    control_api: Arc<String>,
+    tokens_path: Arc<PathBuf>,
+}
+
+impl AuditReporter {
+    pub fn new(control_api: Arc<String>, tokens_path: Arc<PathBuf>) -> Self {
+        Self {
+            control_api,
+            tokens_path,
+        }
+    }
+
+    /// Affiche le refus puis le signale en tâche de fond : un orchestrateur arrêté
+    /// ou des jetons illisibles ne font que laisser le refus dans la sortie d'erreur
+    pub fn access_denied(&self, operator: String, action: String, reason: String) {
+        eprintln!("⛔ Accès refusé ({operator}, {action}): {reason}");
+        let reporter = self.clone();
+        tokio::spawn(async move {
+            let token = match AccessTokens::load(&reporter.tokens_path) {
+                Ok(tokens) => tokens.operator,
+                Err(e) => {
+                    eprintln!("Refus non transmis, jetons d'accès illisibles: {e}");
+                    return;
+                }
+            };
+            let command = Command::ReportAccessDenied {
+                operator,
+                action,
+                reason,
+            };
+            let result = control_client::send_command(
+                &reporter.control_api,
+                &command,
+                GATEWAY_OPERATOR,
+                &token,
+            )
+            .await;
+            if let Err(e) = result {
+                eprintln!("Refus non transmis au journal d'audit: {e}");
+            }
+        });
+    }
+}
@@ -0,0 +1,357 @@
+use crate::audit_report::AuditReporter;
+use crate::control_client;
+use crate::history::EventHistory;
+use futures_util::{SinkExt, StreamExt};
+use protocol::{
+    AccessTokens, ClientMessage, EventRecord, Role, ServerMessage, Topic, PROTOCOL_VERSION,
+};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{accept_async, WebSocketStream};
+
+/// Opérateur journalisé quand le client ne s'est pas nommé
+const ANONYMOUS_OPERATOR: &str = "anonymous";
+/// Ping envoyé au client à cet intervalle
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// Connexion fermée sans aucun message (pong compris) pendant ce délai
+const IDLE_TIMEOUT: Duration = Duration::from_secs(45);
+/// Un client qui n'accepte pas un message dans ce délai est déconnecté
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+/// Commandes relayées en parallèle pour un même client (au-delà : refusées)
+const MAX_PENDING_COMMANDS: usize = 16;
+
+/// État d'une connexion, établi par `Hello`
+struct Session {
+    peer: String,
+    greeted: bool,
+    // Nom d'opérateur annoncé dans `Hello`, transmis avec chaque commande
+    operator: Arc<String>,
+    // Jeton présenté dans `Hello` (relayé à l'API de contrôle) et rôle qu'il accorde
+    token: Arc<String>,
+    role: Role,
+    topics: HashSet<Topic>,
+}
+
+impl Session {
+    /// Auteur signalé au journal d'audit : adresse du client et nom qu'il a déclaré
+    fn who(&self) -> String {
+        format!("ws {} (déclaré : {})", self.peer, self.operator)
+    }
+}
+
+/// Gère une connexion WebSocket :
+/// - poignée de main `Hello` / `Welcome` (version du protocole et jeton d'accès vérifiés),
+///   suivie du rejeu des derniers événements correspondant aux abonnements
+/// - diffusion des événements correspondant aux abonnements du client ;
+///   un client trop lent perd des événements (signalé) plutôt que de les accumuler
+/// - relais des commandes vers l'API de contrôle de l'orchestrateur,
+///   selon le rôle du jeton (jetons invalides et rôles insuffisants sont signalés au
+///   journal d'audit de l'orchestrateur par `reporter`)
+/// - ping régulier, fermeture d'une connexion inactive ou bloquée
+pub async fn handle_client(
+    stream: TcpStream,
+    mut events: broadcast::Receiver<Arc<EventRecord>>,
+    history: Arc<EventHistory>,
+    control_api: Arc<String>,
+    tokens_path: Arc<PathBuf>,
+    reporter: AuditReporter,
+) {
+    let peer = stream
+        .peer_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let mut ws_stream = match accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(e) => {
+            let reason = format!("Handshake WebSocket échoué: {e}");
+            reporter.access_denied(format!("ws {peer}"), "connect".into(), reason);
+            return;
+        }
+    };
+    println!("Client connecté: {peer}");
+
+    let mut session = Session {
+        peer: peer.clone(),
+        greeted: false,
+        operator: Arc::new(ANONYMOUS_OPERATOR.to_string()),
+        token: Arc::default(),
+        role: Role::Viewer,
+        topics: HashSet::from([Topic::All]),
+    };
+    // Dernier événement rejoué : les mêmes événements reçus en direct sont ignorés
+    let mut replayed_until: Option<u64> = None;
+    // Les réponses aux commandes arrivent de façon asynchrone (au plus une par commande en cours)
+    let (responses_tx, mut responses) = mpsc::channel::<ServerMessage>(MAX_PENDING_COMMANDS);
+    let commands = Arc::new(Semaphore::new(MAX_PENDING_COMMANDS));
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        let greeted_before = session.greeted;
+        let outgoing = tokio::select! {
+            incoming = ws_stream.next() => {
+                last_seen = Instant::now();
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        let reply = handle_message(
+                            &text,
+                            &mut session,
+                            &control_api,
+                            &tokens_path,
+                            &reporter,
+                            &commands,
+                            &responses_tx,
+                        );
+                        match reply {
+                            Some(reply) => reply,
+                            None => continue,
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    // Pong et autres trames : seule l'activité compte
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => {
+                        eprintln!("Erreur client {peer}: {e}");
+                        break;
+                    }
+                }
+            }
+            Some(response) = responses.recv() => response,
+            event = events.recv() => match event {
+                Ok(record) if replayed_until.is_some_and(|seq| record.seq <= seq) => continue,
+                Ok(record) if session.greeted && session.topics.iter().any(|t| t.matches(&record)) => {
+                    ServerMessage::Event { record: (*record).clone() }
+                }
+                Ok(_) => continue,
+                // Les événements manqués ne sont pas rattrapés : un seul avertissement
+                Err(broadcast::error::RecvError::Lagged(skipped)) => ServerMessage::Error {
+                    id: None,
+                    message: format!("{skipped} événements perdus (client trop lent)"),
+                },
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > IDLE_TIMEOUT {
+                    eprintln!("Client inactif, connexion fermée: {peer}");
+                    break;
+                }
+                if let Err(e) = send(&mut ws_stream, Message::Ping(Vec::new())).await {
+                    eprintln!("Ping impossible vers {peer}: {e}");
+                    break;
+                }
+                continue;
+            }
+        };
+
+        let welcome = !greeted_before && session.greeted;
+        let rejected = !session.greeted && matches!(outgoing, ServerMessage::Error { .. });
+        if let Err(e) = send(&mut ws_stream, Message::Text(outgoing.to_json())).await {
+            eprintln!("Envoi impossible à {peer}: {e}");
+            break;
+        }
+        // Sans poignée de main valide, la connexion est refusée
+        if rejected {
+            break;
+        }
+        if welcome {
+            match replay_history(&mut ws_stream, &history, &session.topics).await {
+                Ok(last) => replayed_until = last,
+                Err(e) => {
+                    eprintln!("Rejeu impossible vers {peer}: {e}");
+                    break;
+                }
+            }
+        }
+    }
+    println!("Client déconnecté: {peer}");
+}
+
+/// Envoie un message, en abandonnant si le client ne le lit pas à temps
+async fn send(ws_stream: &mut WebSocketStream<TcpStream>, message: Message) -> Result<(), String> {
+    match tokio::time::timeout(SEND_TIMEOUT, ws_stream.send(message)).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err("délai d'envoi dépassé (client trop lent)".into()),
+    }
+}
+
+/// Rejoue les derniers événements conservés ; retourne le numéro du dernier
+async fn replay_history(
+    ws_stream: &mut WebSocketStream<TcpStream>,
+    history: &EventHistory,
+    topics: &HashSet<Topic>,
+) -> Result<Option<u64>, String> {
+    let records = history.snapshot();
+    for record in records
+        .iter()
+        .filter(|r| topics.iter().any(|t| t.matches(r)))
+    {
+        let message = ServerMessage::Event {
+            record: (**record).clone(),
+        };
+        send(ws_stream, Message::Text(message.to_json())).await?;
+    }
+    Ok(records.last().map(|record| record.seq))
+}
+
+/// Traite un message client ; retourne la réponse immédiate éventuelle
+fn handle_message(
+    text: &str,
+    session: &mut Session,
+    control_api: &Arc<String>,
+    tokens_path: &Path,
+    reporter: &AuditReporter,
+    commands: &Arc<Semaphore>,
+    responses: &mpsc::Sender<ServerMessage>,
+) -> Option<ServerMessage> {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => {
+            let message = format!("Message invalide: {e}");
+            // Avant la poignée de main, l'erreur ferme la connexion
+            if !session.greeted {
+                reporter.access_denied(session.who(), "hello".into(), message.clone());
+            }
+            return Some(ServerMessage::Error { id: None, message });
+        }
+    };
+
+    if !session.greeted {
+        return Some(match message {
+            ClientMessage::Hello {
+                version,
+                operator,
+                token,
+            } if version == PROTOCOL_VERSION => {
+                greet(session, operator, token, tokens_path, reporter)
+            }
+            ClientMessage::Hello { version, .. } => {
+                let message = format!(
+                    "Version de protocole {version} non supportée (attendue: {PROTOCOL_VERSION})"
+                );
+                reporter.access_denied(session.who(), "hello".into(), message.clone());
+                ServerMessage::Error { id: None, message }
+            }
+            _ => {
+                let message = "Hello attendu en premier message".to_string();
+                reporter.access_denied(session.who(), "hello".into(), message.clone());
+                ServerMessage::Error { id: None, message }
+            }
+        });
+    }
+
+    match message {
+        ClientMessage::Hello { .. } => Some(ServerMessage::Welcome {
+            version: PROTOCOL_VERSION,
+            role: session.role,
+        }),
+        ClientMessage::Subscribe { topic } => {
+            session.topics.insert(topic);
+            Some(subscriptions(&session.topics))
+        }
+        ClientMessage::Unsubscribe { topic } => {
+            session.topics.remove(&topic);
+            Some(subscriptions(&session.topics))
+        }
+        ClientMessage::Command { id, command } => {
+            let refusal = if command.is_privileged() {
+                Some(format!("{} réservée à la passerelle", command.name()))
+            } else if session.role < command.required_role() {
+                Some(format!("Rôle operator requis pour {}", command.name()))
+            } else {
+                None
+            };
+            if let Some(message) = refusal {
+                reporter.access_denied(session.who(), command.name(), message.clone());
+                return Some(ServerMessage::Error {
+                    id: Some(id),
+                    message,
+                });
+            }
+            let Ok(permit) = commands.clone().try_acquire_owned() else {
+                return Some(ServerMessage::Error {
+                    id: Some(id),
+                    message: format!("Trop de commandes en cours (max {MAX_PENDING_COMMANDS})"),
+                });
+            };
+            let control_api = control_api.clone();
+            let operator = session.operator.clone();
+            let token = session.token.clone();
+            let responses = responses.clone();
+            tokio::spawn(async move {
+                let result =
+                    control_client::send_command(&control_api, &command, &operator, &token).await;
+                let reply = match result {
+                    Ok(data) => ServerMessage::Response { id, data },
+                    Err(message) => ServerMessage::Error {
+                        id: Some(id),
+                        message,
+                    },
+                };
+                // Client déjà déconnecté : la réponse est perdue
+                let _ = responses.send(reply).await;
+                drop(permit);
+            });
+            None
+        }
+    }
+}
+
+/// Vérifie le jeton présenté dans `Hello` et ouvre la session.
+/// Le fichier des jetons est relu à chaque connexion : l'orchestrateur le régénère
+/// à chaque démarrage.
+fn greet(
+    session: &mut Session,
+    operator: Option<String>,
+    token: Option<String>,
+    tokens_path: &Path,
+    reporter: &AuditReporter,
+) -> ServerMessage {
+    // Caractères de contrôle retirés : nom et jeton finissent dans des en-têtes HTTP
+    let clean = |value: Option<String>| -> String {
+        value
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+    let operator = clean(operator);
+    if !operator.is_empty() {
+        session.operator = Arc::new(operator);
+    }
+    let token = clean(token);
+
+    let role = match AccessTokens::load(tokens_path) {
+        Ok(tokens) => tokens.role_of(&token),
+        Err(e) => {
+            eprintln!("Jetons d'accès illisibles ({}): {e}", tokens_path.display());
+            None
+        }
+    };
+    let Some(role) = role else {
+        let message = "Jeton d'accès manquant ou invalide".to_string();
+        reporter.access_denied(session.who(), "hello".into(), message.clone());
+        return ServerMessage::Error { id: None, message };
+    };
+
+    session.greeted = true;
+    session.role = role;
+    session.token = Arc::new(token);
+    ServerMessage::Welcome {
+        version: PROTOCOL_VERSION,
+        role,
+    }
+}
+
+fn subscriptions(topics: &HashSet<Topic>) -> ServerMessage {
+    ServerMessage::Subscriptions {
+        topics: topics.iter().cloned().collect(),
+    }
+}
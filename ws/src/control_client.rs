@@ -0,0 +1,56 @@
+use protocol::Command;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Client HTTP minimal vers l'API de contrôle locale de l'orchestrateur (POST /commands).
+/// Une connexion par commande (`Connection: close`), la réponse est lue jusqu'à la fermeture.
+/// `operator` est transmis dans l'en-tête `X-Operator` (journal d'audit de l'orchestrateur),
+/// `token` (jeton d'accès du client) dans l'en-tête `Authorization`.
+pub async fn send_command(
+    addr: &str,
+    command: &Command,
+    operator: &str,
+    token: &str,
+) -> Result<serde_json::Value, String> {
+    let body = serde_json::to_string(command).map_err(|e| e.to_string())?;
+    let mut stream = TcpStream::connect(addr)
+        .await
+        .map_err(|e| format!("Orchestrateur injoignable ({addr}): {e}"))?;
+
+    let request = format!(
+        "POST /commands HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nX-Operator: {operator}\r\nAuthorization: Bearer {token}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut raw = Vec::new();
+    stream
+        .read_to_end(&mut raw)
+        .await
+        .map_err(|e| e.to_string())?;
+    let raw = String::from_utf8_lossy(&raw);
+
+    let (head, payload) = raw
+        .split_once("\r\n\r\n")
+        .ok_or("Réponse HTTP incomplète")?;
+    let status: u16 = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or("Statut HTTP illisible")?;
+    let value: serde_json::Value =
+        serde_json::from_str(payload).map_err(|e| format!("Réponse invalide: {e}"))?;
+
+    if status == 200 {
+        Ok(value)
+    } else {
+        let message = value
+            .get("error")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Erreur inconnue");
+        Err(format!("{status}: {message}"))
+    }
+}
@@ -0,0 +1,104 @@
+use crate::history::EventHistory;
+use protocol::EventRecord;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::broadcast;
+
+/// Intervalle de scrutation du journal
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Fin du journal relue au démarrage pour remplir l'historique rejoué aux clients
+const PRELOAD_BYTES: u64 = 1024 * 1024;
+
+/// Suit le journal JSONL de l'orchestrateur (comme `tail -f`) et diffuse chaque
+/// nouvel enregistrement. Démarre à la fin du fichier : seuls les nouveaux
+/// événements sont diffusés, les derniers existants alimentent l'historique.
+/// Un fichier tronqué ou recréé est relu depuis le début.
+pub async fn follow_event_log(
+    path: PathBuf,
+    events: broadcast::Sender<Arc<EventRecord>>,
+    history: Arc<EventHistory>,
+) {
+    let mut offset = match tokio::fs::metadata(&path).await {
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    };
+    preload_history(&path, offset, &history).await;
+    let mut pending = String::new();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let len = match tokio::fs::metadata(&path).await {
+            Ok(meta) => meta.len(),
+            Err(_) => continue, // Journal pas encore créé
+        };
+        if len < offset {
+            offset = 0;
+            pending.clear();
+        }
+        if len == offset {
+            continue;
+        }
+
+        match read_from(&path, offset).await {
+            Ok(chunk) => {
+                offset += chunk.len() as u64;
+                pending.push_str(&String::from_utf8_lossy(&chunk));
+            }
+            Err(e) => {
+                eprintln!("Lecture du journal impossible ({}): {e}", path.display());
+                continue;
+            }
+        }
+
+        // Seules les lignes complètes sont traitées, le reste attend le prochain tour
+        while let Some(pos) = pending.find('\n') {
+            let line: String = pending.drain(..=pos).collect();
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<EventRecord>(line) {
+                // Aucun client connecté : l'événement est simplement perdu
+                Ok(record) => {
+                    let record = Arc::new(record);
+                    history.push(record.clone());
+                    let _ = events.send(record);
+                }
+                Err(e) => eprintln!("Ligne de journal ignorée: {e}"),
+            }
+        }
+    }
+}
+
+/// Remplit l'historique avec les derniers enregistrements déjà présents dans le journal
+async fn preload_history(path: &PathBuf, len: u64, history: &EventHistory) {
+    let start = len.saturating_sub(PRELOAD_BYTES);
+    let mut chunk = match read_from(path, start).await {
+        Ok(chunk) => chunk,
+        Err(_) => return, // Journal pas encore créé
+    };
+    // Ce qui a été écrit depuis sera lu (et diffusé) par la boucle de suivi
+    chunk.truncate((len - start) as usize);
+    let text = String::from_utf8_lossy(&chunk);
+    // Lecture au milieu du fichier : la première ligne est incomplète
+    let skip = usize::from(start > 0);
+    for line in text.lines().skip(skip) {
+        if let Ok(record) = serde_json::from_str::<EventRecord>(line) {
+            history.push(Arc::new(record));
+        }
+    }
+}
+
+async fn read_from(path: &PathBuf, offset: u64) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+    let mut chunk = Vec::new();
+    file.read_to_end(&mut chunk).await?;
+    Ok(chunk)
+}
@@ -0,0 +1,44 @@
+use protocol::EventRecord;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Derniers événements diffusés, rejoués aux clients qui se connectent
+/// pour qu'ils ne partent pas d'un écran vide.
+pub struct EventHistory {
+    capacity: usize,
+    records: Mutex<VecDeque<Arc<EventRecord>>>,
+}
+
+impl EventHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Ajoute un événement ; le plus ancien est oublié au-delà de la capacité
+    pub fn push(&self, record: Arc<EventRecord>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.lock();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Copie des événements conservés, du plus ancien au plus récent
+    pub fn snapshot(&self) -> Vec<Arc<EventRecord>> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Arc<EventRecord>>> {
+        // Un panic pendant un push ne laisse pas la file incohérente : on continue
+        match self.records.lock() {
+            Ok(records) => records,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
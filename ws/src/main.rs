@@ -1,33 +1,138 @@
-use futures_util::sink::SinkExt;
-use futures_util::StreamExt;
-use tokio::net::TcpListener;
-use tokio_tungstenite::accept_async;
+mod audit_report;
+mod client;
+mod control_client;
+mod event_log;
+mod history;
+mod static_files;
 
+use audit_report::AuditReporter;
+use history::EventHistory;
+use protocol::{EventRecord, ACCESS_TOKENS_FILE};
+use static_files::Request;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+const ADDR: &str = "127.0.0.1:9001";
+const DEFAULT_EVENT_LOG: &str = "logs/audit_log.jsonl";
+const DEFAULT_CONTROL_API: &str = "127.0.0.1:9100";
+/// Interface compilée (`trunk build` dans ui/)
+const DEFAULT_ASSETS_DIR: &str = "ui/dist";
+/// Événements en attente par client avant qu'il soit considéré en retard
+const EVENT_BUFFER: usize = 1024;
+/// Derniers événements rejoués à chaque nouveau client
+const DEFAULT_REPLAY: usize = 200;
+
+/// Passerelle d'événements : suit le journal JSONL de l'orchestrateur
+/// et diffuse ses événements aux clients WebSocket abonnés ; relaie leurs commandes
+/// vers l'API de contrôle de l'orchestrateur. Sur le même port, les requêtes HTTP
+/// ordinaires reçoivent les fichiers de l'interface (tableau de bord).
+/// Les clients présentent un jeton du fichier écrit par l'orchestrateur (`--tokens`).
+/// Usage : ws [--event-log PATH] [--control-api ADDR] [--replay N] [--assets-dir PATH]
+///            [--tokens PATH]
 #[tokio::main]
 async fn main() {
-    let listener = TcpListener::bind("127.0.0.1:9001").await.unwrap();
-    println!("WebSocket server listening on ws://127.0.0.1:9001");
-
-    while let Ok((stream, _)) = listener.accept().await {
-        tokio::spawn(async move {
-            let mut ws_stream = accept_async(stream).await.unwrap();
-            println!("New client connected!");
-            // On écoute les messages du client, et on répond "pong: ..." à chaque fois
-            while let Some(msg) = ws_stream.next().await {
-                match msg {
-                    Ok(msg) => {
-                        if msg.is_text() {
-                            let txt = msg.to_text().unwrap();
-                            println!("Received: {txt}");
-                            ws_stream.send(format!("pong: {txt}").into()).await.unwrap();
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Error: {e}");
-                        break;
-                    }
-                }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let event_log = PathBuf::from(flag_value(&args, "--event-log").unwrap_or(DEFAULT_EVENT_LOG));
+    let control_api = Arc::new(
+        flag_value(&args, "--control-api")
+            .unwrap_or(DEFAULT_CONTROL_API)
+            .to_string(),
+    );
+    let assets_dir = Arc::new(PathBuf::from(
+        flag_value(&args, "--assets-dir").unwrap_or(DEFAULT_ASSETS_DIR),
+    ));
+    let tokens_path = Arc::new(PathBuf::from(
+        flag_value(&args, "--tokens").unwrap_or(ACCESS_TOKENS_FILE),
+    ));
+    let replay = flag_value(&args, "--replay")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(DEFAULT_REPLAY);
+
+    let listener = match TcpListener::bind(ADDR).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Impossible d'écouter sur {ADDR}: {e}");
+            return;
+        }
+    };
+    println!("WebSocket server listening on ws://{ADDR}");
+    println!("Suivi du journal d'événements: {}", event_log.display());
+    println!("API de contrôle: http://{control_api}");
+    if assets_dir.join("index.html").is_file() {
+        println!(
+            "Tableau de bord sur http://{ADDR}/ ({})",
+            assets_dir.display()
+        );
+    } else {
+        eprintln!(
+            "⚠️ Interface introuvable dans {} (compiler ui/ avec trunk build)",
+            assets_dir.display()
+        );
+    }
+
+    let reporter = AuditReporter::new(control_api.clone(), tokens_path.clone());
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let history = Arc::new(EventHistory::new(replay));
+    tokio::spawn(event_log::follow_event_log(
+        event_log,
+        events.clone(),
+        history.clone(),
+    ));
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(accept(
+                    stream,
+                    events.subscribe(),
+                    history.clone(),
+                    control_api.clone(),
+                    tokens_path.clone(),
+                    assets_dir.clone(),
+                    reporter.clone(),
+                ));
+            }
+            // Erreur passagère (ex: trop de fichiers ouverts) : le serveur continue
+            Err(e) => {
+                eprintln!("Connexion refusée: {e}");
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
             }
-        });
+        }
     }
 }
+
+/// Aiguille une connexion : passerelle WebSocket ou fichier de l'interface
+async fn accept(
+    mut stream: TcpStream,
+    events: broadcast::Receiver<Arc<EventRecord>>,
+    history: Arc<EventHistory>,
+    control_api: Arc<String>,
+    tokens_path: Arc<PathBuf>,
+    assets_dir: Arc<PathBuf>,
+    reporter: AuditReporter,
+) {
+    match static_files::classify(&mut stream).await {
+        Ok(Request::WebSocket) => {
+            client::handle_client(stream, events, history, control_api, tokens_path, reporter).await
+        }
+        Ok(Request::Http { method, path }) => {
+            drop(events);
+            let served = static_files::serve(stream, &assets_dir, &method, &path, &reporter).await;
+            if let Err(e) = served {
+                eprintln!("Réponse HTTP impossible ({path}): {e}");
+            }
+        }
+        // Connexion fermée ou requête illisible : rien à répondre
+        Err(_) => {}
+    }
+}
+
+/// Valeur suivant un drapeau (`--flag valeur`)
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
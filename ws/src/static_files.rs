@@ -0,0 +1,174 @@
+use crate::audit_report::AuditReporter;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Taille maximale des en-têtes d'une requête HTTP
+const MAX_HEAD_LEN: usize = 8 * 1024;
+/// Délai pour recevoir les en-têtes (connexion lente ou muette)
+const HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Type de requête reçue sur le port de la passerelle
+pub enum Request {
+    /// Demande de passage en WebSocket : la poignée de main est laissée à tungstenite
+    WebSocket,
+    /// Requête HTTP ordinaire (déjà lue) : méthode et chemin
+    Http { method: String, path: String },
+}
+
+/// Lit les en-têtes sans les consommer pour une demande WebSocket, en les consommant
+/// sinon : le même port sert la passerelle et les fichiers de l'interface
+pub async fn classify(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let head_len = tokio::time::timeout(HEAD_TIMEOUT, peek_head(stream))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "en-têtes incomplets"))??;
+
+    let mut head = vec![0; head_len];
+    stream.peek(&mut head).await?;
+    let head = String::from_utf8_lossy(&head).to_string();
+    let upgrade = head.lines().any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("upgrade")
+                && value.trim().eq_ignore_ascii_case("websocket")
+        })
+    });
+    if upgrade {
+        return Ok(Request::WebSocket);
+    }
+
+    // Requête HTTP : les en-têtes sont retirés du flux (pas de corps attendu pour GET/HEAD)
+    let mut consumed = vec![0; head_len];
+    stream.read_exact(&mut consumed).await?;
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    Ok(Request::Http {
+        method: request_line.next().unwrap_or_default().to_string(),
+        path: request_line.next().unwrap_or("/").to_string(),
+    })
+}
+
+/// Attend que les en-têtes soient complets ; retourne leur longueur (ligne vide comprise)
+async fn peek_head(stream: &mut TcpStream) -> std::io::Result<usize> {
+    let mut buffer = vec![0; MAX_HEAD_LEN];
+    loop {
+        let read = stream.peek(&mut buffer).await?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        if let Some(end) = buffer[..read].windows(4).position(|w| w == b"\r\n\r\n") {
+            return Ok(end + 4);
+        }
+        if read == MAX_HEAD_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "en-têtes trop longs",
+            ));
+        }
+        // Données partielles : peek renverrait immédiatement les mêmes octets
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Sert un fichier du dossier de l'interface compilée (`/` = index.html).
+/// Méthodes autres que GET/HEAD et chemins sortant du dossier sont refusés et
+/// signalés au journal d'audit de l'orchestrateur.
+pub async fn serve(
+    mut stream: TcpStream,
+    assets_dir: &Path,
+    method: &str,
+    path: &str,
+    reporter: &AuditReporter,
+) -> std::io::Result<()> {
+    let peer = stream
+        .peer_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let refuse = |reason: &str| {
+        let action = format!(
+            "http {method} {}",
+            path.chars().take(200).collect::<String>()
+        );
+        reporter.access_denied(format!("http {peer}"), action, reason.to_string());
+    };
+    if method != "GET" && method != "HEAD" {
+        refuse("Méthode non autorisée");
+        return respond(&mut stream, 405, "text/plain", b"Method Not Allowed", true).await;
+    }
+    let Some(file) = resolve(assets_dir, path) else {
+        refuse("Chemin hors du dossier de l'interface");
+        return respond(&mut stream, 404, "text/plain", b"Not Found", true).await;
+    };
+    match tokio::fs::read(&file).await {
+        Ok(body) => {
+            respond(
+                &mut stream,
+                200,
+                content_type(&file),
+                &body,
+                method == "GET",
+            )
+            .await
+        }
+        Err(_) => respond(&mut stream, 404, "text/plain", b"Not Found", true).await,
+    }
+}
+
+/// Chemin du fichier demandé, sans jamais sortir du dossier des fichiers
+fn resolve(assets_dir: &Path, path: &str) -> Option<PathBuf> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let relative = Path::new(path.trim_start_matches('/'));
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let file = assets_dir.join(relative);
+    if file.is_dir() {
+        Some(file.join("index.html"))
+    } else {
+        Some(file)
+    }
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+    {
+        "html" => "text/html; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        // Requis par WebAssembly.instantiateStreaming
+        "wasm" => "application/wasm",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "ico" => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+    with_body: bool,
+) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    if with_body {
+        stream.write_all(body).await?;
+    }
+    stream.shutdown().await
+}